@@ -0,0 +1,102 @@
+//! Measures the throughput of `tokenize`, which collects the tokens into
+//! a vector, and of `lex`, which yields them one at a time, on generated
+//! schemas of 100, 5,000 and 50,000 lines.
+//!
+//! Preallocating the vector of `tokenize` from the length of the source
+//! measured within noise of collecting it, as the time goes into lexing
+//! rather than growing the vector, so `tokenize` collects without a
+//! capacity hint.
+//!
+//! Run with `cargo bench --bench lexer`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use teo_language_parser::lexer::{lex, tokenize};
+
+const ITERATIONS: u32 = 20;
+
+/// The number of times each lexer is measured, alternating between them
+/// so that neither one always runs on a cold cache.
+const ROUNDS: usize = 5;
+
+/// Returns a schema of `models` models and as many enums, ten lines each.
+fn fixture(models: usize) -> String {
+    (0..models)
+        .map(|index| {
+            format!(
+                concat!(
+                    "/// Model {0}.\n",
+                    "@map(\"m{0}\")\n",
+                    "model M{0} {{\n",
+                    "  @id @default(autoincrement()) id: Int\n",
+                    "  name: String? = \"m{0}\"\n",
+                    "  @relation(fields: [id], references: [id])\n",
+                    "  parent: M{0}?\n",
+                    "}}\n",
+                    "enum E{0} {{ A, B, C }}\n",
+                    "\n",
+                ),
+                index
+            )
+        })
+        .collect()
+}
+
+fn collected(source: &str) -> usize {
+    tokenize(source).len()
+}
+
+fn streamed(source: &str) -> usize {
+    lex(source).count()
+}
+
+/// Returns the average time `count` takes on `source`.
+fn measure(source: &str, count: fn(&str) -> usize) -> Duration {
+    black_box(count(source));
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(count(black_box(source)));
+    }
+    start.elapsed() / ITERATIONS
+}
+
+/// Returns the best average time of each of `counts` on `source` over
+/// [`ROUNDS`] rounds.
+fn compare<const N: usize>(source: &str, counts: [fn(&str) -> usize; N]) -> [Duration; N] {
+    let mut best = [Duration::MAX; N];
+    for _ in 0..ROUNDS {
+        for (count, best) in counts.iter().zip(&mut best) {
+            *best = (*best).min(measure(source, *count));
+        }
+    }
+    best
+}
+
+/// Returns `count` items per second of `duration`, in millions.
+fn millions_per_second(count: usize, duration: Duration) -> f64 {
+    count as f64 / duration.as_secs_f64() / 1e6
+}
+
+fn main() {
+    for models in [10, 500, 5000] {
+        let source = fixture(models);
+        let tokens = collected(&source);
+        assert_eq!(streamed(&source), tokens);
+        let [tokenize, lex] = compare(&source, [collected, streamed]);
+        println!(
+            "{} lines, {} tokens, {} bytes",
+            source.lines().count(),
+            tokens,
+            source.len()
+        );
+        for (name, duration) in [("tokenize", tokenize), ("lex", lex)] {
+            println!(
+                "  {:<9} {:>12?} {:>8.1}M tokens/s {:>8.1}MB/s",
+                name,
+                duration,
+                millions_per_second(tokens, duration),
+                millions_per_second(source.len(), duration)
+            );
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Compares `outline`, which lexes the whole source before scanning it,
+//! with the single pass `fast_outline` on a generated 50,000 line schema.
+//!
+//! Run with `cargo bench --bench outline`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use teo_language_parser::outline::{fast_outline, outline, OutlineItem};
+
+const ITERATIONS: u32 = 20;
+
+/// Returns a schema of 5,000 models and 5,000 enums, 50,000 lines in all.
+fn fixture() -> String {
+    (0..5000)
+        .map(|index| {
+            format!(
+                concat!(
+                    "/// Model {0}.\n",
+                    "@map(\"m{0}\")\n",
+                    "model M{0} {{\n",
+                    "  @id @default(autoincrement()) id: Int\n",
+                    "  name: String? = \"m{0}\"\n",
+                    "  @relation(fields: [id], references: [id])\n",
+                    "  parent: M{0}?\n",
+                    "}}\n",
+                    "enum E{0} {{ A, B, C }}\n",
+                    "\n",
+                ),
+                index
+            )
+        })
+        .collect()
+}
+
+/// Returns the average time `outline` takes on `source`.
+fn measure(source: &str, outline: fn(&str) -> Vec<OutlineItem>) -> Duration {
+    black_box(outline(source));
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(outline(black_box(source)));
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let source = fixture();
+    assert_eq!(source.lines().count(), 50_000);
+    assert_eq!(fast_outline(&source), outline(&source));
+    let two_pass = measure(&source, outline);
+    let single_pass = measure(&source, fast_outline);
+    println!("outline       {:?}", two_pass);
+    println!("fast_outline  {:?}", single_pass);
+    println!(
+        "speedup       {:.2}x",
+        two_pass.as_secs_f64() / single_pass.as_secs_f64()
+    );
+}
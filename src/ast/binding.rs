@@ -0,0 +1,22 @@
+use super::{Expr, Identifier, TypeExpr};
+use std::ops::Range;
+
+/// # Let declaration
+///
+/// A `let name = value` or `let name: Type = value` declaration of a named
+/// constant, like `let pageSize: Int = 20`. `var` is reserved for mutable
+/// bindings, which the language doesn't have yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetDeclaration {
+    /// The name of the constant.
+    pub name: Identifier,
+    /// The type written after the name, if any.
+    pub type_expr: Option<TypeExpr>,
+    /// The value of the constant.
+    pub value: Expr,
+    /// The byte span from the `let` keyword through the end of the value.
+    pub span: Range<usize>,
+    /// The index of the constant among all declarations of the file, in
+    /// the order they were written.
+    pub source_order: usize,
+}
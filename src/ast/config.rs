@@ -0,0 +1,54 @@
+use super::{Expr, Identifier};
+use std::ops::Range;
+
+/// # Config declaration
+///
+/// A `config kind Name { key: value, ... }` declaration, like
+/// `config server { bind: ("0.0.0.0", 5000) }`. The kind and the name are
+/// both optional, but a config with a name has a kind too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDeclaration {
+    /// The kind of config after the `config` keyword, like `server`.
+    pub kind: Option<Identifier>,
+    /// The name after the kind, telling apart several configs of the same
+    /// kind, like `Main` in `config server Main { ... }`. None for an
+    /// anonymous config.
+    pub name: Option<Identifier>,
+    /// The items of the config in source order.
+    pub items: Vec<ConfigItem>,
+    /// The byte span from the `config` keyword through the closing brace.
+    pub span: Range<usize>,
+    /// The index of the config among all declarations of the file, in the
+    /// order they were written.
+    pub source_order: usize,
+}
+
+/// # Config item
+///
+/// A `key: value` entry inside a config body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigItem {
+    /// The key of the item.
+    pub key: Identifier,
+    /// The value of the item.
+    pub value: Expr,
+    /// The byte span of the item.
+    pub span: Range<usize>,
+}
+
+/// # Config section
+///
+/// A `[name]` header on its own line followed by `key = value` entries, up
+/// to the next header or declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSection {
+    /// The name between the brackets.
+    pub name: Identifier,
+    /// The entries of the section in source order.
+    pub entries: Vec<ConfigItem>,
+    /// The byte span from the opening bracket through the last entry.
+    pub span: Range<usize>,
+    /// The index of the section among all declarations of the file, in the
+    /// order they were written.
+    pub source_order: usize,
+}
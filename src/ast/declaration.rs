@@ -0,0 +1,92 @@
+use super::{
+    ConfigDeclaration, ConfigSection, EnumDeclaration, ExportStatement, FunctionDeclaration,
+    HandlerDeclaration, Identifier, ImportStatement, LetDeclaration, MiddlewareDeclaration,
+    ModelDeclaration, NamespaceDeclaration, StructDeclaration, TypeAliasDeclaration,
+    UnknownDeclaration,
+};
+use std::ops::Range;
+
+/// # Declaration
+///
+/// A top level item of a schema file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Declaration {
+    Import(ImportStatement),
+    Export(ExportStatement),
+    Model(ModelDeclaration),
+    Enum(EnumDeclaration),
+    Config(ConfigDeclaration),
+    ConfigSection(ConfigSection),
+    TypeAlias(TypeAliasDeclaration),
+    Let(LetDeclaration),
+    Struct(StructDeclaration),
+    Function(FunctionDeclaration),
+    Middleware(MiddlewareDeclaration),
+    Handler(HandlerDeclaration),
+    Namespace(NamespaceDeclaration),
+    /// Only produced when unknown declarations are allowed.
+    Unknown(UnknownDeclaration),
+}
+
+impl Declaration {
+    /// Returns the byte span of the declaration.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Declaration::Import(import) => import.span.clone(),
+            Declaration::Export(export) => export.span.clone(),
+            Declaration::Model(model) => model.span.clone(),
+            Declaration::Enum(r#enum) => r#enum.span.clone(),
+            Declaration::Config(config) => config.span.clone(),
+            Declaration::ConfigSection(section) => section.span.clone(),
+            Declaration::TypeAlias(alias) => alias.span.clone(),
+            Declaration::Let(binding) => binding.span.clone(),
+            Declaration::Struct(r#struct) => r#struct.span.clone(),
+            Declaration::Function(function) => function.span.clone(),
+            Declaration::Middleware(middleware) => middleware.span.clone(),
+            Declaration::Handler(handler) => handler.span.clone(),
+            Declaration::Namespace(namespace) => namespace.span.clone(),
+            Declaration::Unknown(unknown) => unknown.span.clone(),
+        }
+    }
+
+    /// Returns the name the declaration declares, None for imports,
+    /// exports, configs and unknown declarations.
+    pub fn name(&self) -> Option<&Identifier> {
+        match self {
+            Declaration::Model(model) => Some(&model.name),
+            Declaration::Enum(r#enum) => Some(&r#enum.name),
+            Declaration::TypeAlias(alias) => Some(&alias.name),
+            Declaration::Let(binding) => Some(&binding.name),
+            Declaration::Struct(r#struct) => Some(&r#struct.name),
+            Declaration::Function(function) => Some(&function.name),
+            Declaration::Middleware(middleware) => Some(&middleware.name),
+            Declaration::Handler(handler) => Some(&handler.name),
+            Declaration::Namespace(namespace) => Some(&namespace.name),
+            Declaration::Import(_)
+            | Declaration::Export(_)
+            | Declaration::Config(_)
+            | Declaration::ConfigSection(_)
+            | Declaration::Unknown(_) => None,
+        }
+    }
+
+    /// Returns the index of the declaration in the order it was written.
+    pub fn source_order(&self) -> usize {
+        match self {
+            Declaration::Import(import) => import.source_order,
+            Declaration::Export(export) => export.source_order,
+            Declaration::Model(model) => model.source_order,
+            Declaration::Enum(r#enum) => r#enum.source_order,
+            Declaration::Config(config) => config.source_order,
+            Declaration::ConfigSection(section) => section.source_order,
+            Declaration::TypeAlias(alias) => alias.source_order,
+            Declaration::Let(binding) => binding.source_order,
+            Declaration::Struct(r#struct) => r#struct.source_order,
+            Declaration::Function(function) => function.source_order,
+            Declaration::Middleware(middleware) => middleware.source_order,
+            Declaration::Handler(handler) => handler.source_order,
+            Declaration::Namespace(namespace) => namespace.source_order,
+            Declaration::Unknown(unknown) => unknown.source_order,
+        }
+    }
+}
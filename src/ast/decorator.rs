@@ -0,0 +1,44 @@
+use super::{Expr, Identifier};
+use std::ops::Range;
+
+/// # Decorator
+///
+/// An `@name`, `@name()` or `@name(arguments)` annotation written before
+/// the declaration it applies to, or an `@@name` block decorator written
+/// inside the body of the model or enum it applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decorator {
+    /// The `::` separated segments of the decorator name.
+    pub path: Vec<Identifier>,
+    /// The arguments between the parentheses, or `None` if the decorator
+    /// was written without parentheses.
+    pub arguments: Option<Vec<Argument>>,
+    /// True for an `@@name` block decorator.
+    pub block: bool,
+    /// The byte span from the `@` through the closing parenthesis.
+    pub span: Range<usize>,
+}
+
+impl Decorator {
+    /// Returns the `::` joined name of the decorator.
+    pub fn name(&self) -> String {
+        self.path
+            .iter()
+            .map(|segment| segment.name.as_str())
+            .collect::<Vec<&str>>()
+            .join("::")
+    }
+}
+
+/// # Argument
+///
+/// A positional `value` or a named `name: value` argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Argument {
+    /// The label of a named argument.
+    pub name: Option<Identifier>,
+    /// The value of the argument.
+    pub value: Expr,
+    /// The byte span of the argument.
+    pub span: Range<usize>,
+}
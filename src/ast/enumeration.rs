@@ -0,0 +1,41 @@
+use super::{Decorator, Identifier, Literal, TypeExpr};
+use std::ops::Range;
+
+/// # Enum declaration
+///
+/// An `enum Name { A, B }` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumDeclaration {
+    /// The text of the `///` comments directly above the enum,
+    /// one line per comment, if there are any.
+    pub doc: Option<String>,
+    /// The decorators written before the declaration.
+    pub decorators: Vec<Decorator>,
+    /// The name of the enum.
+    pub name: Identifier,
+    /// The type written after a `:` following the name, which the values
+    /// of the members must have, like the `Int` of `enum Status: Int`.
+    pub backing: Option<TypeExpr>,
+    /// The members of the enum in source order.
+    pub members: Vec<EnumMember>,
+    /// The byte span from the `enum` keyword through the closing brace.
+    pub span: Range<usize>,
+    /// The index of the enum among all declarations of the file, in the
+    /// order they were written.
+    pub source_order: usize,
+}
+
+/// # Enum member
+///
+/// A `NAME` or `NAME = value` entry inside an enum body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumMember {
+    /// The decorators written before the declaration.
+    pub decorators: Vec<Decorator>,
+    /// The name of the member.
+    pub name: Identifier,
+    /// The value associated with the member, if any.
+    pub value: Option<Literal>,
+    /// The byte span of the member.
+    pub span: Range<usize>,
+}
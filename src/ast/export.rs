@@ -0,0 +1,23 @@
+use super::Identifier;
+use std::ops::Range;
+
+/// # Export statement
+///
+/// An `export { a, b }` statement re-exporting symbols declared in the same
+/// file, or an `export { a, b } from "path"` statement re-exporting symbols
+/// of another file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportStatement {
+    /// The identifiers listed between the braces.
+    pub names: Vec<Identifier>,
+    /// The decoded content of the source string literal, if the statement
+    /// has a `from` clause.
+    pub source: Option<String>,
+    /// The byte span of the source string literal, quotes included.
+    pub source_span: Option<Range<usize>>,
+    /// The byte span of the whole statement.
+    pub span: Range<usize>,
+    /// The index of the statement among all declarations of the file, in
+    /// the order they were written.
+    pub source_order: usize,
+}
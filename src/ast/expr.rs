@@ -0,0 +1,175 @@
+use super::{Argument, Identifier, Literal};
+use std::ops::Range;
+
+/// # Expression
+///
+/// A value written in the source, like a decorator argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// A string or number literal.
+    Literal(Literal),
+    /// A reference to a name.
+    Identifier(Identifier),
+    /// An `[a, b]` array literal.
+    Array {
+        elements: Vec<Expr>,
+        span: Range<usize>,
+    },
+    /// A `(a, b)` tuple literal.
+    Tuple {
+        elements: Vec<Expr>,
+        span: Range<usize>,
+    },
+    /// A `{ key: value }` dictionary literal.
+    Dict {
+        entries: Vec<DictEntry>,
+        span: Range<usize>,
+    },
+    /// A prefix operator applied to an operand, like `!flag`.
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expr>,
+        span: Range<usize>,
+    },
+    /// An infix operator applied to two operands, like `a + b`.
+    Binary {
+        operator: BinaryOperator,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+        span: Range<usize>,
+    },
+    /// A `object.property` member access.
+    Member {
+        object: Box<Expr>,
+        property: Identifier,
+        span: Range<usize>,
+    },
+    /// An `object?.property` member access, which is null when the object
+    /// is.
+    OptionalMember {
+        object: Box<Expr>,
+        property: Identifier,
+        span: Range<usize>,
+    },
+    /// An `object[index]` subscript.
+    Index {
+        object: Box<Expr>,
+        index: Box<Expr>,
+        span: Range<usize>,
+    },
+    /// A `callee(arguments)` call.
+    Call {
+        callee: Box<Expr>,
+        arguments: Vec<Argument>,
+        span: Range<usize>,
+    },
+    /// A string literal with embedded expressions, like `"hello ${name}"`.
+    /// A string without any is a [`Expr::Literal`].
+    InterpolatedString {
+        parts: Vec<StringPart>,
+        span: Range<usize>,
+    },
+}
+
+impl Expr {
+    /// Returns the byte span of the expression.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Expr::Literal(literal) => literal.span.clone(),
+            Expr::Identifier(identifier) => identifier.span.clone(),
+            Expr::Array { span, .. }
+            | Expr::Tuple { span, .. }
+            | Expr::Dict { span, .. }
+            | Expr::Unary { span, .. }
+            | Expr::Binary { span, .. }
+            | Expr::Member { span, .. }
+            | Expr::OptionalMember { span, .. }
+            | Expr::Index { span, .. }
+            | Expr::Call { span, .. }
+            | Expr::InterpolatedString { span, .. } => span.clone(),
+        }
+    }
+}
+
+/// # Dictionary entry
+///
+/// A `key: value` entry of a dictionary literal. The key is a name or a
+/// string literal, like `"content-type": "json"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictEntry {
+    /// The key of the entry. The value of the string for a quoted key,
+    /// spanning the whole literal.
+    pub key: Identifier,
+    /// Whether the key is written as a string literal.
+    pub quoted: bool,
+    /// The value of the entry.
+    pub value: Expr,
+    /// The byte span of the entry.
+    pub span: Range<usize>,
+}
+
+/// # String part
+///
+/// A piece of an interpolated string: text, with its escapes decoded, or
+/// the expression of a `${...}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StringPart {
+    Text(String),
+    Expr(Expr),
+}
+
+/// A prefix operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOperator {
+    /// `!`
+    Not,
+    /// `-`
+    Negate,
+}
+
+/// An infix operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    /// `||`
+    Or,
+    /// `&&`
+    And,
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `|`
+    BitOr,
+    /// `^`
+    BitXor,
+    /// `&`
+    BitAnd,
+    /// `+`
+    Add,
+    /// `-`
+    Subtract,
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `%`
+    Modulo,
+}
+
+impl BinaryOperator {
+    /// Returns how tightly the operator binds. Operators with a higher
+    /// precedence are applied first. All binary operators are left
+    /// associative.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => 1,
+            BinaryOperator::And => 2,
+            BinaryOperator::Equal | BinaryOperator::NotEqual => 3,
+            BinaryOperator::BitOr => 4,
+            BinaryOperator::BitXor => 5,
+            BinaryOperator::BitAnd => 6,
+            BinaryOperator::Add | BinaryOperator::Subtract => 7,
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 8,
+        }
+    }
+}
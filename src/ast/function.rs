@@ -0,0 +1,46 @@
+use super::{Expr, Identifier, TypeExpr, TypeParam};
+use std::ops::Range;
+
+/// # Function declaration
+///
+/// A `function name(parameters): ReturnType { body }` declaration, or one
+/// without a body like `declare native function now(): DateTime`, whose
+/// implementation is provided by the runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionDeclaration {
+    /// Whether the function is written after `declare`.
+    pub declare: bool,
+    /// Whether the function is written after `native`.
+    pub native: bool,
+    /// The name of the function.
+    pub name: Identifier,
+    /// The type parameters between `<` and `>`, empty if there are none.
+    pub type_parameters: Vec<TypeParam>,
+    /// The parameters in source order.
+    pub parameters: Vec<FunctionParameter>,
+    /// The type after the `:` following the parameters, if any.
+    pub return_type: Option<TypeExpr>,
+    /// The expression between the braces of the body, if there is one.
+    pub body: Option<Expr>,
+    /// The byte span from the first keyword through the end of the
+    /// declaration.
+    pub span: Range<usize>,
+    /// The index of the function among all declarations of the file, in
+    /// the order they were written.
+    pub source_order: usize,
+}
+
+/// # Function parameter
+///
+/// A `name: Type` or `name: Type = default` parameter of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionParameter {
+    /// The name of the parameter.
+    pub name: Identifier,
+    /// The type of the parameter.
+    pub type_expr: TypeExpr,
+    /// The value used when the argument is left out, if any.
+    pub default: Option<Expr>,
+    /// The byte span from the name through the end of the parameter.
+    pub span: Range<usize>,
+}
@@ -0,0 +1,22 @@
+use super::{Decorator, Expr, Identifier};
+use std::ops::Range;
+
+/// # Handler declaration
+///
+/// A `handler name { pipeline }` declaration, like
+/// `handler signIn { identity.signIn() }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerDeclaration {
+    /// The decorators written before the handler.
+    pub decorators: Vec<Decorator>,
+    /// The name of the handler.
+    pub name: Identifier,
+    /// The pipeline run by the handler. A handler without one is reported
+    /// as an error but still kept.
+    pub body: Option<Expr>,
+    /// The byte span from the `handler` keyword through the end of the body.
+    pub span: Range<usize>,
+    /// The index of the handler among all declarations of the file, in the
+    /// order they were written.
+    pub source_order: usize,
+}
@@ -0,0 +1,22 @@
+use std::ops::Range;
+
+/// # Identifier
+///
+/// A name written in the source, together with where it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identifier {
+    /// The name as written in the source.
+    pub name: String,
+    /// The byte span of the name.
+    pub span: Range<usize>,
+}
+
+impl Identifier {
+    /// Create a new identifier.
+    pub fn new(name: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            name: name.into(),
+            span,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use super::Identifier;
+use std::ops::Range;
+
+/// The keyword an import statement starts with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    /// `import { a, b } from "path"` or `import "path"`.
+    Import,
+    /// `include "path"`.
+    Include,
+}
+
+/// # Import statement
+///
+/// An `import { a, b } from "path"` or an `include "path"` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportStatement {
+    /// Whether this is an `import` or an `include`.
+    pub kind: ImportKind,
+    /// The identifiers listed between the braces. Empty for `include` and
+    /// for `import "path"`.
+    pub identifiers: Vec<Identifier>,
+    /// The decoded content of the source string literal.
+    pub source: String,
+    /// The byte span of the source string literal, quotes included.
+    pub source_span: Range<usize>,
+    /// The byte span of the whole statement.
+    pub span: Range<usize>,
+    /// The index of the statement among all declarations of the file, in
+    /// the order they were written.
+    pub source_order: usize,
+}
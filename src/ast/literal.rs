@@ -0,0 +1,24 @@
+use std::ops::Range;
+
+/// The value of a literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralValue {
+    /// A string literal with its escapes decoded.
+    String(String),
+    /// A number literal as it was written.
+    Number(String),
+    /// A `/pattern/` regex literal, holding the pattern between the
+    /// slashes.
+    Regex(String),
+}
+
+/// # Literal
+///
+/// A string, number or regex written directly in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Literal {
+    /// The value of the literal.
+    pub value: LiteralValue,
+    /// The byte span of the literal.
+    pub span: Range<usize>,
+}
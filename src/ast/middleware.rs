@@ -0,0 +1,21 @@
+use super::{Decorator, Expr, Identifier};
+use std::ops::Range;
+
+/// # Middleware declaration
+///
+/// A `middleware name` or `middleware name { pipeline }` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiddlewareDeclaration {
+    /// The decorators written before the middleware.
+    pub decorators: Vec<Decorator>,
+    /// The name of the middleware.
+    pub name: Identifier,
+    /// The pipeline run by the middleware, if it has one.
+    pub body: Option<Expr>,
+    /// The byte span from the `middleware` keyword through the end of the
+    /// declaration.
+    pub span: Range<usize>,
+    /// The index of the middleware among all declarations of the file, in
+    /// the order they were written.
+    pub source_order: usize,
+}
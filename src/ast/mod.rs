@@ -0,0 +1,43 @@
+pub mod binding;
+pub mod config;
+pub mod declaration;
+pub mod decorator;
+pub mod enumeration;
+pub mod export;
+pub mod function;
+pub mod expr;
+pub mod handler;
+pub mod identifier;
+pub mod import;
+pub mod literal;
+pub mod middleware;
+pub mod model;
+pub mod namespace;
+pub mod schema;
+pub mod structure;
+pub mod type_alias;
+pub mod type_expr;
+pub mod type_param;
+pub mod unknown;
+
+pub use binding::LetDeclaration;
+pub use config::{ConfigDeclaration, ConfigItem, ConfigSection};
+pub use declaration::Declaration;
+pub use decorator::{Argument, Decorator};
+pub use enumeration::{EnumDeclaration, EnumMember};
+pub use export::ExportStatement;
+pub use function::{FunctionDeclaration, FunctionParameter};
+pub use expr::{BinaryOperator, DictEntry, Expr, StringPart, UnaryOperator};
+pub use handler::HandlerDeclaration;
+pub use identifier::Identifier;
+pub use import::{ImportKind, ImportStatement};
+pub use literal::{Literal, LiteralValue};
+pub use middleware::MiddlewareDeclaration;
+pub use model::{FieldDeclaration, ModelDeclaration};
+pub use namespace::NamespaceDeclaration;
+pub use schema::SchemaFile;
+pub use structure::StructDeclaration;
+pub use type_alias::TypeAliasDeclaration;
+pub use type_expr::TypeExpr;
+pub use type_param::{TypeConstraint, TypeParam};
+pub use unknown::UnknownDeclaration;
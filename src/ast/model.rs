@@ -0,0 +1,61 @@
+use super::{Decorator, Expr, Identifier, TypeExpr, TypeParam};
+use std::ops::Range;
+
+/// # Model declaration
+///
+/// A `model Name { field: Type, ... }` declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelDeclaration {
+    /// The text of the `///` comments directly above the model,
+    /// one line per comment, if there are any.
+    pub doc: Option<String>,
+    /// The decorators written before the declaration.
+    pub decorators: Vec<Decorator>,
+    /// The name of the model.
+    pub name: Identifier,
+    /// The type parameters between `<` and `>`, empty if there are none.
+    pub type_parameters: Vec<TypeParam>,
+    /// The fields of the model in source order.
+    pub fields: Vec<FieldDeclaration>,
+    /// The byte span from the `model` keyword through the closing brace.
+    pub span: Range<usize>,
+    /// The index of the model among all declarations of the file, in the
+    /// order they were written.
+    pub source_order: usize,
+}
+
+impl ModelDeclaration {
+    /// Returns the `@@` block decorators written inside the body, which
+    /// apply to the whole model, like `@@index([a, b])`.
+    pub fn block_decorators(&self) -> impl Iterator<Item = &Decorator> {
+        self.decorators.iter().filter(|decorator| decorator.block)
+    }
+}
+
+/// # Field declaration
+///
+/// A `name: Type` or `name: Type = default` entry inside a model body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDeclaration {
+    /// The text of the `///` comments directly above the field,
+    /// one line per comment, if there are any.
+    pub doc: Option<String>,
+    /// The decorators written before the declaration.
+    pub decorators: Vec<Decorator>,
+    /// The name of the field.
+    pub name: Identifier,
+    /// The type of the field.
+    pub type_expr: TypeExpr,
+    /// The default value written after the type, like the `0` of
+    /// `count: Int = 0`.
+    pub default: Option<Expr>,
+    /// The byte span of the field.
+    pub span: Range<usize>,
+}
+
+impl FieldDeclaration {
+    /// Returns true if the field type is optional.
+    pub fn is_optional(&self) -> bool {
+        self.type_expr.is_optional()
+    }
+}
@@ -0,0 +1,20 @@
+use super::{Declaration, Identifier};
+use std::ops::Range;
+
+/// # Namespace declaration
+///
+/// A `namespace Name { ... }` block grouping declarations under a
+/// qualified name, like the `blog::Post` of
+/// `namespace blog { model Post { ... } }`. Namespaces may be nested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceDeclaration {
+    /// The name of the namespace.
+    pub name: Identifier,
+    /// The declarations inside the block in source order.
+    pub declarations: Vec<Declaration>,
+    /// The byte span from the `namespace` keyword through the closing brace.
+    pub span: Range<usize>,
+    /// The index of the namespace among all declarations of the file, in
+    /// the order they were written.
+    pub source_order: usize,
+}
@@ -0,0 +1,174 @@
+use super::{Declaration, EnumDeclaration, ModelDeclaration, StructDeclaration};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// # Schema file
+///
+/// The root of the syntax tree of a single schema source file.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaFile {
+    /// The top level declarations in source order.
+    ///
+    /// The lookups by name, like [`SchemaFile::model`], index the
+    /// declarations the first time they are used. Call
+    /// [`SchemaFile::clear_index`] after changing the declarations.
+    pub declarations: Vec<Declaration>,
+    /// The position of the declaration of each name, built on first
+    /// lookup: the index in `declarations`, followed by the index in the
+    /// namespace at each level for namespaced names.
+    index: OnceLock<HashMap<String, Vec<usize>>>,
+}
+
+impl SchemaFile {
+    /// Create a schema file of `declarations`.
+    pub fn new(declarations: Vec<Declaration>) -> Self {
+        Self {
+            declarations,
+            index: OnceLock::new(),
+        }
+    }
+
+    /// Returns the declaration called `name`, where the declarations of a
+    /// namespace are called by their qualified names, like `blog::Post`.
+    /// When a name is declared more than once, the first declaration wins.
+    pub fn declaration(&self, name: &str) -> Option<&Declaration> {
+        let index = self.index.get_or_init(|| {
+            let mut index = HashMap::new();
+            collect(&self.declarations, "", &mut vec![], &mut index);
+            index
+        });
+        let (first, rest) = index.get(name)?.split_first()?;
+        let mut declaration = self.declarations.get(*first)?;
+        for position in rest {
+            let Declaration::Namespace(namespace) = declaration else {
+                return None;
+            };
+            declaration = namespace.declarations.get(*position)?;
+        }
+        Some(declaration)
+    }
+
+    /// Returns the model called `name`, if there is one.
+    pub fn model(&self, name: &str) -> Option<&ModelDeclaration> {
+        match self.declaration(name)? {
+            Declaration::Model(model) => Some(model),
+            _ => None,
+        }
+    }
+
+    /// Returns the enum called `name`, if there is one.
+    pub fn r#enum(&self, name: &str) -> Option<&EnumDeclaration> {
+        match self.declaration(name)? {
+            Declaration::Enum(r#enum) => Some(r#enum),
+            _ => None,
+        }
+    }
+
+    /// Returns the struct called `name`, if there is one.
+    pub fn r#struct(&self, name: &str) -> Option<&StructDeclaration> {
+        match self.declaration(name)? {
+            Declaration::Struct(r#struct) => Some(r#struct),
+            _ => None,
+        }
+    }
+
+    /// Forget the index of the lookups by name, so that the next lookup
+    /// sees the declarations as they are now.
+    pub fn clear_index(&mut self) {
+        self.index = OnceLock::new();
+    }
+}
+
+/// Record the position of every named declaration of `declarations`, which
+/// are at `position`, under its name qualified with `prefix`.
+fn collect(
+    declarations: &[Declaration],
+    prefix: &str,
+    position: &mut Vec<usize>,
+    index: &mut HashMap<String, Vec<usize>>,
+) {
+    for (at, declaration) in declarations.iter().enumerate() {
+        let Some(identifier) = declaration.name() else {
+            continue;
+        };
+        let name = format!("{}{}", prefix, identifier.name);
+        position.push(at);
+        if let Declaration::Namespace(namespace) = declaration {
+            let prefix = format!("{}::", name);
+            collect(&namespace.declarations, &prefix, position, index);
+        }
+        index.entry(name).or_insert_with(|| position.clone());
+        position.pop();
+    }
+}
+
+impl PartialEq for SchemaFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.declarations == other.declarations
+    }
+}
+
+impl Eq for SchemaFile {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    #[test]
+    fn looks_up_declarations_by_name() {
+        let schema = parse_schema(
+            "model User { id: Int }\nenum Role { ADMIN }\nstruct Point { x: Int }",
+            "schema.teo",
+        )
+        .schema;
+        assert_eq!(schema.model("User").unwrap().name.name, "User");
+        assert_eq!(schema.r#enum("Role").unwrap().members.len(), 1);
+        assert_eq!(schema.r#struct("Point").unwrap().fields.len(), 1);
+        assert!(schema.model("Post").is_none());
+        assert!(schema.model("Role").is_none());
+    }
+
+    #[test]
+    fn looks_up_namespaced_declarations() {
+        let schema = parse_schema(
+            "model Post { id: Int }\nnamespace blog {\n  namespace drafts { enum Status { DRAFT } }\n  model Post { title: String }\n}",
+            "schema.teo",
+        )
+        .schema;
+        assert_eq!(schema.model("Post").unwrap().fields[0].name.name, "id");
+        assert_eq!(
+            schema.model("blog::Post").unwrap().fields[0].name.name,
+            "title"
+        );
+        assert_eq!(
+            schema.r#enum("blog::drafts::Status").unwrap().name.name,
+            "Status"
+        );
+        assert!(matches!(
+            schema.declaration("blog"),
+            Some(Declaration::Namespace(_))
+        ));
+        assert!(schema.r#enum("Status").is_none());
+        assert!(schema.r#enum("drafts::Status").is_none());
+    }
+
+    #[test]
+    fn the_first_declaration_of_a_name_wins() {
+        let schema = parse_schema("enum User { A }\nmodel User { id: Int }", "schema.teo").schema;
+        assert!(schema.r#enum("User").is_some());
+        assert!(schema.model("User").is_none());
+    }
+
+    #[test]
+    fn clearing_the_index_sees_changed_declarations() {
+        let mut schema = parse_schema("model User { id: Int }", "schema.teo").schema;
+        assert!(schema.model("User").is_some());
+        let other = parse_schema("model Post { id: Int }", "schema.teo").schema;
+        schema.declarations = other.declarations.clone();
+        schema.clear_index();
+        assert!(schema.model("User").is_none());
+        assert!(schema.model("Post").is_some());
+        assert_eq!(schema, other);
+    }
+}
@@ -0,0 +1,22 @@
+use super::{FieldDeclaration, Identifier, TypeParam};
+use std::ops::Range;
+
+/// # Struct declaration
+///
+/// A `struct Name { ... }` declaration of a reusable shape, like
+/// `struct Point { x: Int, y: Int }`, which may be generic, like
+/// `struct Pair<A, B> { first: A, second: B }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructDeclaration {
+    /// The name of the struct.
+    pub name: Identifier,
+    /// The type parameters between `<` and `>`, empty if there are none.
+    pub type_parameters: Vec<TypeParam>,
+    /// The fields of the struct in source order.
+    pub fields: Vec<FieldDeclaration>,
+    /// The byte span from the `struct` keyword through the closing brace.
+    pub span: Range<usize>,
+    /// The index of the struct among all declarations of the file, in the
+    /// order they were written.
+    pub source_order: usize,
+}
@@ -0,0 +1,22 @@
+use super::{Identifier, TypeExpr, TypeParam};
+use std::ops::Range;
+
+/// # Type alias declaration
+///
+/// A `type Name = Target` declaration, like `type ID = String` or
+/// `type Pair<A, B> = (A, B)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeAliasDeclaration {
+    /// The name of the alias.
+    pub name: Identifier,
+    /// The `<A, B>` type parameters after the name, which the target may
+    /// refer to. Empty if there are none.
+    pub type_parameters: Vec<TypeParam>,
+    /// The type the alias stands for.
+    pub target: TypeExpr,
+    /// The byte span from the `type` keyword through the end of the target.
+    pub span: Range<usize>,
+    /// The index of the alias among all declarations of the file, in the
+    /// order they were written.
+    pub source_order: usize,
+}
@@ -0,0 +1,127 @@
+use super::Identifier;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// # Type expression
+///
+/// A type written in the source, like `String`, `Foo::Bar`, `Int[]?`,
+/// `Map<String, Int>`, `(Int, String)` or `Int | String`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeExpr {
+    /// A type referred to by name, like `String` or `Foo::Bar`.
+    Named {
+        /// The `::` separated segments of the name.
+        path: Vec<Identifier>,
+        /// The type arguments between `<` and `>`, like the `Int` of
+        /// `Array<Int>`. Empty if there are none.
+        arguments: Vec<TypeExpr>,
+        span: Range<usize>,
+    },
+    /// A type followed by `?`.
+    Optional {
+        inner: Box<TypeExpr>,
+        span: Range<usize>,
+    },
+    /// A type followed by `[]`.
+    Array {
+        inner: Box<TypeExpr>,
+        span: Range<usize>,
+    },
+    /// Two or more types separated by `|`.
+    Union {
+        members: Vec<TypeExpr>,
+        span: Range<usize>,
+    },
+    /// Types between parentheses separated by `,`, like `(Int, String)`.
+    Tuple {
+        elements: Vec<TypeExpr>,
+        span: Range<usize>,
+    },
+}
+
+impl TypeExpr {
+    /// Returns the byte span of the type expression.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            TypeExpr::Named { span, .. }
+            | TypeExpr::Optional { span, .. }
+            | TypeExpr::Array { span, .. }
+            | TypeExpr::Union { span, .. }
+            | TypeExpr::Tuple { span, .. } => span.clone(),
+        }
+    }
+
+    /// Returns true if the outermost type is optional.
+    pub fn is_optional(&self) -> bool {
+        matches!(self, TypeExpr::Optional { .. })
+    }
+
+    /// Returns the `::` joined name if this is a named type.
+    pub fn name(&self) -> Option<String> {
+        match self {
+            TypeExpr::Named { path, .. } => Some(
+                path.iter()
+                    .map(|segment| segment.name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join("::"),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TypeExpr {
+    /// Writes the type the way it is written in a schema, without spaces
+    /// except around `|` and after the `,` between type arguments and
+    /// tuple elements. A union made optional or an array, like one an
+    /// alias expands to, is parenthesized, and a tuple of one element
+    /// keeps a trailing `,`, so the text parses back to the same type.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeExpr::Named { arguments, .. } => {
+                f.write_str(&self.name().unwrap_or_default())?;
+                if arguments.is_empty() {
+                    return Ok(());
+                }
+                f.write_str("<")?;
+                for (index, argument) in arguments.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", argument)?;
+                }
+                f.write_str(">")
+            }
+            TypeExpr::Optional { inner, .. } if matches!(**inner, TypeExpr::Union { .. }) => {
+                write!(f, "({})?", inner)
+            }
+            TypeExpr::Optional { inner, .. } => write!(f, "{}?", inner),
+            TypeExpr::Array { inner, .. } if matches!(**inner, TypeExpr::Union { .. }) => {
+                write!(f, "({})[]", inner)
+            }
+            TypeExpr::Array { inner, .. } => write!(f, "{}[]", inner),
+            TypeExpr::Union { members, .. } => {
+                for (index, member) in members.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(" | ")?;
+                    }
+                    write!(f, "{}", member)?;
+                }
+                Ok(())
+            }
+            TypeExpr::Tuple { elements, .. } => {
+                f.write_str("(")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                match elements.len() {
+                    1 => f.write_str(",)"),
+                    _ => f.write_str(")"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use super::{Identifier, TypeExpr};
+use std::ops::Range;
+
+/// # Type parameter
+///
+/// A generic parameter of a declaration, like the `T` of `model Cache<T>`,
+/// with the bound given to it in a `where T: Bound` clause, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeParam {
+    /// The name of the parameter.
+    pub name: Identifier,
+    /// The type the parameter is constrained to.
+    pub bound: Option<TypeExpr>,
+}
+
+/// # Type constraint
+///
+/// A `Subject: Bound` constraint of a `where` clause, like the
+/// `T: Scalar` of `where T: Scalar`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeConstraint {
+    /// The constrained type, before the `:`.
+    pub subject: TypeExpr,
+    /// The type the subject is constrained to, after the `:`.
+    pub bound: TypeExpr,
+    /// The byte span from the subject through the end of the bound.
+    pub span: Range<usize>,
+}
@@ -0,0 +1,24 @@
+use super::Identifier;
+use std::ops::Range;
+
+/// # Unknown declaration
+///
+/// A `keyword Name { ... }` declaration whose keyword the parser doesn't
+/// know, like `view Active { ... }`. It is only produced when the parser is
+/// built with [`crate::parser::Parser::with_unknown_declarations`], so that
+/// tools can pass declarations of newer schema versions through unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDeclaration {
+    /// The leading identifier in place of a keyword.
+    pub keyword: Identifier,
+    /// The name of the declaration.
+    pub name: Identifier,
+    /// The byte span of the body, from the opening brace through the
+    /// closing brace. The body is not parsed.
+    pub body_span: Range<usize>,
+    /// The byte span from the keyword through the closing brace.
+    pub span: Range<usize>,
+    /// The index of the declaration among all declarations of the file, in
+    /// the order they were written.
+    pub source_order: usize,
+}
@@ -0,0 +1,159 @@
+use crate::ast::{Declaration, FieldDeclaration, Identifier, SchemaFile};
+use crate::diagnostic::Diagnostic;
+use std::collections::HashMap;
+
+/// Check that no name is declared twice in the same scope: the top level
+/// names of the file, the fields of each model and struct, and the members
+/// of each enum.
+///
+/// Every occurrence after the first produces an error spanning its name,
+/// with a note pointing at the first one. The codes are
+/// `"duplicate-declaration"`, `"duplicate-field"` and
+/// `"duplicate-enum-member"`. Imported names count as top level names.
+/// The body of a `namespace` is a scope of its own.
+pub fn check_duplicates(schema: &SchemaFile) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    check_scope(&schema.declarations, &mut diagnostics);
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+    diagnostics
+}
+
+fn check_scope(declarations: &[Declaration], diagnostics: &mut Vec<Diagnostic>) {
+    let mut top_level = vec![];
+    for declaration in declarations {
+        match declaration {
+            Declaration::Import(import) => top_level.extend(&import.identifiers),
+            Declaration::Model(model) => {
+                top_level.push(&model.name);
+                check_fields(&model.fields, diagnostics);
+            }
+            Declaration::Struct(r#struct) => {
+                top_level.push(&r#struct.name);
+                check_fields(&r#struct.fields, diagnostics);
+            }
+            Declaration::Enum(r#enum) => {
+                top_level.push(&r#enum.name);
+                let members = r#enum.members.iter().map(|member| &member.name);
+                report(members, "enum member", "duplicate-enum-member", diagnostics);
+            }
+            Declaration::TypeAlias(alias) => top_level.push(&alias.name),
+            Declaration::Let(binding) => top_level.push(&binding.name),
+            Declaration::Function(function) => top_level.push(&function.name),
+            Declaration::Middleware(middleware) => top_level.push(&middleware.name),
+            Declaration::Handler(handler) => top_level.push(&handler.name),
+            Declaration::Namespace(namespace) => {
+                top_level.push(&namespace.name);
+                check_scope(&namespace.declarations, diagnostics);
+            }
+            Declaration::Export(_)
+            | Declaration::Config(_)
+            | Declaration::ConfigSection(_)
+            | Declaration::Unknown(_) => {}
+        }
+    }
+    report(
+        top_level.into_iter(),
+        "declaration",
+        "duplicate-declaration",
+        diagnostics,
+    );
+}
+
+fn check_fields(fields: &[FieldDeclaration], diagnostics: &mut Vec<Diagnostic>) {
+    let names = fields.iter().map(|field| &field.name);
+    report(names, "field", "duplicate-field", diagnostics);
+}
+
+/// Report every name of `names` that an earlier one already took.
+fn report<'a>(
+    names: impl Iterator<Item = &'a Identifier>,
+    what: &str,
+    code: &'static str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut first: HashMap<&str, &Identifier> = HashMap::new();
+    for name in names {
+        match first.get(name.name.as_str()) {
+            Some(previous) => diagnostics.push(
+                Diagnostic::error(
+                    format!("duplicate {} `{}`", what, name.name),
+                    name.span.clone(),
+                )
+                .with_code(code)
+                .with_note(
+                    format!("`{}` is first declared here", name.name),
+                    previous.span.clone(),
+                ),
+            ),
+            None => {
+                first.insert(&name.name, name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    fn check(source: &str) -> Vec<Diagnostic> {
+        let result = parse_schema(source, "schema.teo");
+        assert!(result.diagnostics.is_empty());
+        check_duplicates(&result.schema)
+    }
+
+    #[test]
+    fn accepts_distinct_names() {
+        assert!(
+            check("model User { id: Int }\nmodel Post { id: Int }\nenum Role { ADMIN, USER }")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn reports_a_duplicate_model() {
+        let diagnostics = check("model User { id: Int }\nenum User { ADMIN }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("duplicate-declaration"));
+        assert_eq!(diagnostics[0].message, "duplicate declaration `User`");
+        assert_eq!(diagnostics[0].span, 28..32);
+        assert_eq!(diagnostics[0].notes.len(), 1);
+        assert_eq!(diagnostics[0].notes[0].span, 6..10);
+    }
+
+    #[test]
+    fn reports_a_duplicate_field() {
+        let diagnostics = check("model User { id: Int, name: String, id: String }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("duplicate-field"));
+        assert_eq!(diagnostics[0].message, "duplicate field `id`");
+        assert_eq!(diagnostics[0].span, 36..38);
+        assert_eq!(diagnostics[0].notes[0].span, 13..15);
+    }
+
+    #[test]
+    fn reports_a_duplicate_enum_member() {
+        let diagnostics = check("enum Role { ADMIN, USER, ADMIN }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("duplicate-enum-member"));
+        assert_eq!(diagnostics[0].message, "duplicate enum member `ADMIN`");
+        assert_eq!(diagnostics[0].span, 25..30);
+        assert_eq!(diagnostics[0].notes[0].span, 12..17);
+    }
+
+    #[test]
+    fn a_namespace_is_a_scope_of_its_own() {
+        let diagnostics = check(
+            "model Post { id: Int }\nnamespace blog { model Post { id: Int } enum Post { A } }",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "duplicate declaration `Post`");
+        assert_eq!(diagnostics[0].span, 68..72);
+    }
+
+    #[test]
+    fn fields_of_different_models_do_not_clash() {
+        assert!(check("model A { id: Int }\nstruct B { id: Int }").is_empty());
+    }
+}
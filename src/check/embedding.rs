@@ -0,0 +1,164 @@
+use crate::ast::{Declaration, ModelDeclaration, NamespaceDeclaration, TypeExpr};
+use crate::diagnostic::{cover_spans, Diagnostic};
+use crate::resolve::{ImportGraph, Namespaces, Scope};
+use crate::symbol::SymbolKind;
+use crate::visit::{walk, Visitor};
+use std::collections::BTreeMap;
+
+/// Check that no model of any file of `graph` embeds itself: a field whose
+/// type, or the type an optional field wraps, refers to the model the
+/// field belongs to, without a `@relation` decorator.
+///
+/// Each such field produces a `"self-embedding"` warning spanning its
+/// type, reported under the path of its file. A field decorated with
+/// `@relation`, like the parent of a tree, refers to another record
+/// rather than containing one, and so does a list like `children: Node[]`.
+/// Names are looked up the way [`check_type_references`] looks them up.
+///
+/// [`check_type_references`]: super::check_type_references
+pub fn check_self_embedding(graph: &ImportGraph) -> BTreeMap<String, Vec<Diagnostic>> {
+    graph
+        .files
+        .iter()
+        .map(|(path, file)| {
+            let mut checker = SelfEmbedding {
+                path,
+                scope: graph.scope(path),
+                namespaces: Namespaces::new(),
+                diagnostics: vec![],
+            };
+            walk(&mut checker, &file.schema);
+            (path.clone(), checker.diagnostics)
+        })
+        .collect()
+}
+
+struct SelfEmbedding<'a> {
+    path: &'a str,
+    scope: Scope,
+    namespaces: Namespaces,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl SelfEmbedding<'_> {
+    /// Returns true if `segments` name the model declared in this file as
+    /// `model`, which is its qualified name.
+    fn is_model(&self, segments: &[&str], model: &str) -> bool {
+        let found = self.scope.resolve(segments, &self.namespaces, |_| true);
+        found.is_some_and(|(_, entry)| {
+            entry.path == self.path
+                && entry.symbol.kind == SymbolKind::Model
+                && entry.symbol.name == model
+        })
+    }
+}
+
+impl Visitor for SelfEmbedding<'_> {
+    fn visit_declaration(&mut self, declaration: &Declaration) {
+        self.namespaces.leave(declaration);
+    }
+
+    fn visit_namespace(&mut self, namespace: &NamespaceDeclaration) {
+        self.namespaces.enter(namespace);
+    }
+
+    fn visit_model(&mut self, model: &ModelDeclaration) {
+        let name = format!("{}{}", self.namespaces.prefix(), model.name.name);
+        for field in &model.fields {
+            if field
+                .decorators
+                .iter()
+                .any(|decorator| decorator.name() == "relation")
+            {
+                continue;
+            }
+            let type_expr = match &field.type_expr {
+                TypeExpr::Optional { inner, .. } => inner,
+                type_expr => type_expr,
+            };
+            let TypeExpr::Named { path, span, .. } = type_expr else {
+                continue;
+            };
+            let segments: Vec<&str> = path.iter().map(|segment| segment.name.as_str()).collect();
+            if !self.is_model(&segments, &name) {
+                continue;
+            }
+            let span =
+                cover_spans(path.iter().map(|segment| &segment.span)).unwrap_or(span.clone());
+            self.diagnostics.push(
+                Diagnostic::warning(
+                    format!(
+                        "field `{}` embeds its own model `{}`",
+                        field.name.name, model.name.name
+                    ),
+                    span,
+                )
+                .with_code("self-embedding")
+                .with_note(
+                    "add `@relation` to refer to another record",
+                    field.name.span.clone(),
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fsutil::FSUtil;
+    use crate::resolve::resolve_imports;
+    use std::collections::HashMap;
+
+    fn check(source: &str) -> Vec<Diagnostic> {
+        let files = HashMap::from([("schema/main.teo".to_owned(), source.to_owned())]);
+        let graph = resolve_imports("schema/main.teo", &FSUtil::in_memory(files));
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+        check_self_embedding(&graph)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn warns_on_a_field_embedding_its_own_model() {
+        let diagnostics = check("model Node { id: Int, copy: Node, backup: Node? }");
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "field `copy` embeds its own model `Node`",
+                "field `backup` embeds its own model `Node`"
+            ]
+        );
+        assert_eq!(diagnostics[0].code, Some("self-embedding"));
+        assert_eq!(diagnostics[0].span, 28..32);
+    }
+
+    #[test]
+    fn allows_a_relation_to_its_own_model() {
+        let source = "model Node {\n\
+                      id: Int\n\
+                      parentId: Int?\n\
+                      @relation(fields: [parentId], references: [id]) parent: Node?\n\
+                      children: Node[]\n\
+                      }";
+        assert!(check(source).is_empty());
+    }
+
+    #[test]
+    fn resolves_names_inside_namespaces() {
+        let source = "model Node { id: Int }\n\
+                      namespace tree { model Node { inner: Node, outer: Int, top: tree::Node } }\n\
+                      model Leaf { node: Node }";
+        let diagnostics = check(source);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "field `inner` embeds its own model `Node`",
+                "field `top` embeds its own model `Node`"
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::ast::{Declaration, SchemaFile};
+use crate::diagnostic::Diagnostic;
+use crate::symbol::SymbolTable;
+
+/// Check that every name of a local `export { ... }` statement is declared
+/// or imported in the same file.
+///
+/// Each unknown name produces an `"unknown-export"` error spanning the name.
+/// Names re-exported with a `from` clause belong to another file and are
+/// not checked.
+pub fn check_exports(schema: &SchemaFile, table: &SymbolTable) -> Vec<Diagnostic> {
+    schema
+        .declarations
+        .iter()
+        .filter_map(|declaration| match declaration {
+            Declaration::Export(export) if export.source.is_none() => Some(export),
+            _ => None,
+        })
+        .flat_map(|export| &export.names)
+        .filter(|name| !table.contains(&name.name))
+        .map(|name| {
+            Diagnostic::error(format!("unknown export `{}`", name.name), name.span.clone())
+                .with_code("unknown-export")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    fn check(source: &str) -> Vec<Diagnostic> {
+        let result = parse_schema(source, "schema.teo");
+        assert!(result.diagnostics.is_empty());
+        check_exports(&result.schema, &SymbolTable::from_schema(&result.schema))
+    }
+
+    #[test]
+    fn accepts_a_local_export() {
+        assert!(check("model User { id: Int }\nexport { User }").is_empty());
+    }
+
+    #[test]
+    fn accepts_a_re_export_with_from() {
+        assert!(check("export { Post } from \"./post\"").is_empty());
+    }
+
+    #[test]
+    fn reports_an_unknown_export() {
+        let diagnostics = check("model User { id: Int }\nexport { User, Post }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("unknown-export"));
+        assert_eq!(diagnostics[0].message, "unknown export `Post`");
+        assert_eq!(diagnostics[0].span, 38..42);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::diagnostic::Diagnostic;
+use crate::source_map::source_map::next_column;
+use crate::source_map::SourceMap;
+
+/// Check that no line of `source` is longer than `max` characters.
+///
+/// A tab counts as [`SourceMap::DEFAULT_TAB_WIDTH`] columns, like in the
+/// locations of a default source map. See
+/// [`check_line_length_with_tab_width`].
+pub fn check_line_length(source: &str, max: usize) -> Vec<Diagnostic> {
+    check_line_length_with_tab_width(source, max, SourceMap::DEFAULT_TAB_WIDTH)
+}
+
+/// Check that no line of `source` is longer than `max` characters, with
+/// tabs advancing to the next multiple of `tab_width` columns. Pass the
+/// [`tab_width`](SourceMap::tab_width) of the source map the diagnostics
+/// are shown with, so that columns agree.
+///
+/// Each longer line produces a `"line-too-long"` warning spanning the
+/// characters past the limit. Line breaks are not counted.
+pub fn check_line_length_with_tab_width(
+    source: &str,
+    max: usize,
+    tab_width: usize,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut line_start = 0;
+    for line in source.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let mut column = 0;
+        let mut overflow = None;
+        for (offset, c) in text.char_indices() {
+            column = next_column(column, c == '\t', tab_width);
+            if column > max && overflow.is_none() {
+                overflow = Some(offset);
+            }
+        }
+        if let Some(offset) = overflow {
+            diagnostics.push(
+                Diagnostic::warning(
+                    format!("line is {} characters long, the limit is {}", column, max),
+                    line_start + offset..line_start + text.len(),
+                )
+                .with_code("line-too-long"),
+            );
+        }
+        line_start += line.len();
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_the_overflow_of_a_long_line() {
+        let source = "model User {\n  name: String\n}\n";
+        let diagnostics = check_line_length(source, 10);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].code, Some("line-too-long"));
+        assert_eq!(
+            diagnostics[0].message,
+            "line is 12 characters long, the limit is 10"
+        );
+        assert_eq!(&source[diagnostics[0].span.clone()], " {");
+        assert_eq!(&source[diagnostics[1].span.clone()], "ring");
+    }
+
+    #[test]
+    fn accepts_a_line_exactly_at_the_limit() {
+        assert!(check_line_length("enum Role {\r\n  ADMIN\r\n}", 11).is_empty());
+        assert!(check_line_length("enum Rôle {", 11).is_empty());
+    }
+
+    #[test]
+    fn expands_tabs_to_the_next_tab_stop() {
+        let source = "model User {\n\tid: Int\n}";
+        assert!(check_line_length_with_tab_width(source, 12, 4).is_empty());
+        let diagnostics = check_line_length_with_tab_width(source, 12, 8);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(&source[diagnostics[0].span.clone()], "Int");
+    }
+
+    #[test]
+    fn counts_tabs_like_a_default_source_map() {
+        let source = "\t\tid: Int";
+        assert!(check_line_length(source, 9).is_empty());
+        let map = SourceMap::new().with_tab_width(4);
+        let diagnostics = check_line_length_with_tab_width(source, 9, map.tab_width());
+        assert_eq!(
+            diagnostics[0].message,
+            "line is 15 characters long, the limit is 9"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+pub mod duplicates;
+pub mod embedding;
+pub mod exports;
+pub mod line_length;
+#[cfg(feature = "regex-validate")]
+pub mod regex;
+pub mod scalars;
+pub mod types;
+
+pub use duplicates::check_duplicates;
+pub use embedding::check_self_embedding;
+pub use exports::check_exports;
+pub use line_length::{check_line_length, check_line_length_with_tab_width};
+#[cfg(feature = "regex-validate")]
+pub use regex::check_regex_literals;
+pub use scalars::{ScalarRegistry, ScalarType};
+pub use types::{check_type_references, check_type_references_with_scalars};
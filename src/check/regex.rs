@@ -0,0 +1,65 @@
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{tokenize, Token};
+
+/// Check that every regex literal in `source` is a valid pattern.
+///
+/// The delimiting slashes are stripped before the pattern is compiled.
+/// Each invalid pattern produces an `"invalid-regex"` error spanning the
+/// whole literal, with the compile error as the message.
+pub fn check_regex_literals(source: &str) -> Vec<Diagnostic> {
+    tokenize(source)
+        .into_iter()
+        .filter(|(token, _)| *token == Token::Regex)
+        .filter_map(|(_, span)| {
+            let message = compile_error(regex_pattern(&source[span.clone()]))?;
+            Some(
+                Diagnostic::error(format!("invalid regex: {}", message), span)
+                    .with_code("invalid-regex"),
+            )
+        })
+        .collect()
+}
+
+/// Returns the pattern between the slashes of a `/pattern/` literal.
+fn regex_pattern(literal: &str) -> &str {
+    &literal[1..literal.len() - 1]
+}
+
+/// Returns a description of why `pattern` doesn't compile, if it doesn't.
+fn compile_error(pattern: &str) -> Option<String> {
+    match regex_syntax::Parser::new().parse(pattern) {
+        Ok(_) => None,
+        Err(regex_syntax::Error::Parse(error)) => Some(error.kind().to_string()),
+        Err(regex_syntax::Error::Translate(error)) => Some(error.kind().to_string()),
+        Err(error) => Some(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_pattern_produces_no_diagnostic() {
+        assert!(check_regex_literals(r#"@regex(/^[a-z]+@[a-z]+\.com$/)"#).is_empty());
+    }
+
+    #[test]
+    fn invalid_pattern_produces_an_error() {
+        let source = "let pattern = /[a-/";
+        let diagnostics = check_regex_literals(source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("invalid-regex"));
+        assert_eq!(&source[diagnostics[0].span.clone()], "/[a-/");
+        assert_eq!(
+            diagnostics[0].message,
+            "invalid regex: unclosed character class"
+        );
+    }
+
+    #[test]
+    fn regex_pattern_strips_delimiters() {
+        assert_eq!(regex_pattern("/abc/"), "abc");
+        assert_eq!(regex_pattern(r"/a\/b/"), r"a\/b");
+    }
+}
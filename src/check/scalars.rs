@@ -0,0 +1,166 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// # Scalar type
+///
+/// A primitive type every schema can refer to without declaring it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ScalarType {
+    Any,
+    Bool,
+    Date,
+    DateTime,
+    Decimal,
+    File,
+    Float,
+    Float32,
+    Int,
+    Int64,
+    Null,
+    ObjectId,
+    String,
+}
+
+impl ScalarType {
+    /// Every scalar type, ordered by name.
+    pub const ALL: [ScalarType; 13] = [
+        ScalarType::Any,
+        ScalarType::Bool,
+        ScalarType::Date,
+        ScalarType::DateTime,
+        ScalarType::Decimal,
+        ScalarType::File,
+        ScalarType::Float,
+        ScalarType::Float32,
+        ScalarType::Int,
+        ScalarType::Int64,
+        ScalarType::Null,
+        ScalarType::ObjectId,
+        ScalarType::String,
+    ];
+
+    /// Returns the name the type is written with in a schema.
+    pub fn name(self) -> &'static str {
+        match self {
+            ScalarType::Any => "Any",
+            ScalarType::Bool => "Bool",
+            ScalarType::Date => "Date",
+            ScalarType::DateTime => "DateTime",
+            ScalarType::Decimal => "Decimal",
+            ScalarType::File => "File",
+            ScalarType::Float => "Float",
+            ScalarType::Float32 => "Float32",
+            ScalarType::Int => "Int",
+            ScalarType::Int64 => "Int64",
+            ScalarType::Null => "Null",
+            ScalarType::ObjectId => "ObjectId",
+            ScalarType::String => "String",
+        }
+    }
+
+    /// Returns the scalar type written as `name`, if there is one.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scalar| scalar.name() == name)
+    }
+}
+
+impl Display for ScalarType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// # Scalar registry
+///
+/// The names type checks treat as primitive types rather than types the
+/// schema has to declare. The default registry knows every
+/// [`ScalarType`], and callers with scalars of their own add them with
+/// [`ScalarRegistry::with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScalarRegistry {
+    names: BTreeSet<String>,
+}
+
+impl Default for ScalarRegistry {
+    fn default() -> Self {
+        Self {
+            names: ScalarType::ALL
+                .into_iter()
+                .map(|scalar| scalar.name().to_owned())
+                .collect(),
+        }
+    }
+}
+
+impl ScalarRegistry {
+    /// Create a registry of the built-in scalar types.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry without any scalar type, not even the built-in
+    /// ones.
+    pub fn empty() -> Self {
+        Self {
+            names: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the registry with `name` registered as a scalar type.
+    pub fn with(mut self, name: impl Into<String>) -> Self {
+        self.register(name);
+        self
+    }
+
+    /// Register `name` as a scalar type.
+    pub fn register(&mut self, name: impl Into<String>) {
+        self.names.insert(name.into());
+    }
+
+    /// Returns true if `name` is a scalar type.
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    /// Returns the names of the scalar types ordered by name.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knows_the_built_in_scalars() {
+        let registry = ScalarRegistry::new();
+        assert_eq!(
+            registry.names().collect::<Vec<&str>>(),
+            vec![
+                "Any", "Bool", "Date", "DateTime", "Decimal", "File", "Float", "Float32", "Int",
+                "Int64", "Null", "ObjectId", "String"
+            ]
+        );
+        for scalar in ScalarType::ALL {
+            assert_eq!(ScalarType::from_name(scalar.name()), Some(scalar));
+            assert!(registry.contains(&scalar.to_string()));
+        }
+    }
+
+    #[test]
+    fn a_user_type_is_not_a_scalar() {
+        assert_eq!(ScalarType::from_name("User"), None);
+        assert!(!ScalarRegistry::new().contains("User"));
+        assert!(!ScalarRegistry::new().contains("string"));
+    }
+
+    #[test]
+    fn the_registry_can_be_extended() {
+        let registry = ScalarRegistry::new().with("Uuid");
+        assert!(registry.contains("Uuid"));
+        assert!(registry.contains("Int"));
+        let registry = ScalarRegistry::empty().with("Uuid");
+        assert_eq!(registry.names().collect::<Vec<&str>>(), vec!["Uuid"]);
+    }
+}
@@ -0,0 +1,218 @@
+use super::ScalarRegistry;
+use crate::ast::{
+    Declaration, FunctionDeclaration, ModelDeclaration, NamespaceDeclaration, StructDeclaration,
+    TypeAliasDeclaration, TypeExpr, TypeParam,
+};
+use crate::diagnostic::{cover_spans, Diagnostic};
+use crate::resolve::{ImportGraph, Namespaces, Scope};
+use crate::symbol::SymbolKind;
+use crate::visit::{walk, Visitor};
+use std::collections::BTreeMap;
+
+/// Check that every named type of every file of `graph` refers to a type:
+/// a built-in [`ScalarType`](super::ScalarType), a type parameter of the enclosing
+/// declaration, or a model, enum, struct or type alias visible in the
+/// [`Scope`] of the file.
+///
+/// Each unknown name produces an `"unknown-type"` error spanning the name,
+/// reported under the path of its file. Inside a `namespace`, a name is
+/// looked up in the namespace, then in the enclosing ones, then at the top
+/// level. A qualified name like `blog::Post` refers to a declaration of a
+/// namespace, or to the `blog.Post` of an `import "./blog"`.
+pub fn check_type_references(graph: &ImportGraph) -> BTreeMap<String, Vec<Diagnostic>> {
+    check_type_references_with_scalars(graph, &ScalarRegistry::new())
+}
+
+/// Check the type references of `graph` like [`check_type_references`],
+/// with the scalar types of `scalars` instead of the built-in ones.
+pub fn check_type_references_with_scalars(
+    graph: &ImportGraph,
+    scalars: &ScalarRegistry,
+) -> BTreeMap<String, Vec<Diagnostic>> {
+    graph
+        .files
+        .iter()
+        .map(|(path, file)| {
+            let mut checker = TypeReferences {
+                scope: graph.scope(path),
+                scalars,
+                parameters: vec![],
+                namespaces: Namespaces::new(),
+                diagnostics: vec![],
+            };
+            walk(&mut checker, &file.schema);
+            (path.clone(), checker.diagnostics)
+        })
+        .collect()
+}
+
+struct TypeReferences<'a> {
+    scope: Scope,
+    scalars: &'a ScalarRegistry,
+    /// The type parameters of the declaration being checked.
+    parameters: Vec<String>,
+    namespaces: Namespaces,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl TypeReferences<'_> {
+    fn enter(&mut self, parameters: &[TypeParam]) {
+        self.parameters = parameters
+            .iter()
+            .map(|parameter| parameter.name.name.clone())
+            .collect();
+    }
+
+    fn is_type(&self, segments: &[&str]) -> bool {
+        let name = segments.join("::");
+        if self.scalars.contains(&name) || self.parameters.contains(&name) {
+            return true;
+        }
+        let found = self.scope.resolve(segments, &self.namespaces, |entry| {
+            matches!(
+                entry.symbol.kind,
+                SymbolKind::Model | SymbolKind::Enum | SymbolKind::Struct | SymbolKind::TypeAlias
+            )
+        });
+        found.is_some()
+    }
+}
+
+impl Visitor for TypeReferences<'_> {
+    fn visit_declaration(&mut self, declaration: &Declaration) {
+        self.parameters.clear();
+        self.namespaces.leave(declaration);
+    }
+
+    fn visit_namespace(&mut self, namespace: &NamespaceDeclaration) {
+        self.namespaces.enter(namespace);
+    }
+
+    fn visit_model(&mut self, model: &ModelDeclaration) {
+        self.enter(&model.type_parameters);
+    }
+
+    fn visit_struct(&mut self, r#struct: &StructDeclaration) {
+        self.enter(&r#struct.type_parameters);
+    }
+
+    fn visit_function(&mut self, function: &FunctionDeclaration) {
+        self.enter(&function.type_parameters);
+    }
+
+    fn visit_type_alias(&mut self, alias: &TypeAliasDeclaration) {
+        self.enter(&alias.type_parameters);
+    }
+
+    fn visit_type_expr(&mut self, type_expr: &TypeExpr) {
+        let TypeExpr::Named { path, span, .. } = type_expr else {
+            return;
+        };
+        let segments: Vec<&str> = path.iter().map(|segment| segment.name.as_str()).collect();
+        if self.is_type(&segments) {
+            return;
+        }
+        let span = cover_spans(path.iter().map(|segment| &segment.span)).unwrap_or(span.clone());
+        self.diagnostics.push(
+            Diagnostic::error(format!("unknown type `{}`", segments.join("::")), span)
+                .with_code("unknown-type"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fsutil::FSUtil;
+    use crate::resolve::resolve_imports;
+    use std::collections::HashMap;
+
+    fn check(files: &[(&str, &str)], scalars: &ScalarRegistry) -> Vec<Diagnostic> {
+        let files = files
+            .iter()
+            .map(|(path, source)| (path.to_string(), source.to_string()))
+            .collect::<HashMap<String, String>>();
+        let graph = resolve_imports("schema/main.teo", &FSUtil::in_memory(files));
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+        check_type_references_with_scalars(&graph, scalars)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn accepts_declared_and_built_in_types() {
+        let source = "enum Role { ADMIN }\n\
+                      model User<T> { id: Int, role: Role?, tags: String[], extra: T }";
+        assert!(check(&[("schema/main.teo", source)], &ScalarRegistry::new()).is_empty());
+    }
+
+    #[test]
+    fn reports_an_unknown_type() {
+        let source = "enum Role { ADMIN }\nmodel User { role: Rol, ids: Array<Uuid> }";
+        let diagnostics = check(&[("schema/main.teo", source)], &ScalarRegistry::new());
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "unknown type `Rol`",
+                "unknown type `Array`",
+                "unknown type `Uuid`"
+            ]
+        );
+        assert_eq!(diagnostics[0].code, Some("unknown-type"));
+        assert_eq!(diagnostics[0].span, 39..42);
+    }
+
+    #[test]
+    fn resolves_type_aliases() {
+        let source = "type Id = Int\ntype Pair<A, B> = (A, B[])\n\
+                      model User { id: Id, pair: Pair<Id, String>, other: (A, Id) }";
+        let diagnostics = check(&[("schema/main.teo", source)], &ScalarRegistry::new());
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, vec!["unknown type `A`"]);
+    }
+
+    #[test]
+    fn resolves_types_through_imports() {
+        let diagnostics = check(
+            &[
+                (
+                    "schema/main.teo",
+                    "import { Role } from \"./role.teo\"\n\
+                     import \"./user\"\n\
+                     model Post { role: Role, author: user::User, editor: User }",
+                ),
+                ("schema/role.teo", "enum Role { ADMIN }"),
+                ("schema/user.teo", "model User { id: Int }"),
+            ],
+            &ScalarRegistry::new(),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "unknown type `User`");
+    }
+
+    #[test]
+    fn resolves_types_inside_namespaces() {
+        let source = "namespace blog {\n\
+                      namespace drafts { model Draft { post: Post, status: Status } }\n\
+                      model Post { author: User, draft: drafts::Draft, status: Status }\n\
+                      }\n\
+                      enum Status { DRAFT }\n\
+                      model User { post: blog::Post, draft: Draft }";
+        let diagnostics = check(&[("schema/main.teo", source)], &ScalarRegistry::new());
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, vec!["unknown type `Draft`"]);
+    }
+
+    #[test]
+    fn built_in_scalars_can_be_replaced() {
+        let source = "model User { id: Uuid, name: String }";
+        let diagnostics = check(
+            &[("schema/main.teo", source)],
+            &ScalarRegistry::empty().with("Uuid"),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "unknown type `String`");
+    }
+}
@@ -0,0 +1,132 @@
+use super::lossless::parse_tree;
+use super::GreenNode;
+use crate::ast::SchemaFile;
+use crate::diagnostic::Diagnostic;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// # Full parse result
+///
+/// The concrete and the abstract syntax tree of a source, built by
+/// [`parse_full`] from a single pass of the parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullParseResult {
+    /// The lossless concrete syntax tree.
+    pub cst: GreenNode,
+    /// The abstract syntax tree.
+    pub ast: SchemaFile,
+    /// Every problem found while parsing, in source order.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The [id](GreenNode::id) of the `"declaration"` node of each
+    /// declaration of `ast`, by index.
+    pub declaration_ids: Vec<usize>,
+    /// The ids of the nodes of `cst` by their span, outermost first, so
+    /// that a node of `ast` at any depth, like a model inside a namespace,
+    /// finds its node by its own span.
+    pub node_ids: HashMap<Range<usize>, Vec<usize>>,
+}
+
+impl FullParseResult {
+    /// Returns the node of the concrete syntax tree with the id `id`.
+    pub fn node(&self, id: usize) -> Option<&GreenNode> {
+        self.cst.find(id)
+    }
+
+    /// Returns the `"declaration"` node of the concrete syntax tree for the
+    /// declaration at `index` in the abstract syntax tree.
+    pub fn declaration_node(&self, index: usize) -> Option<&GreenNode> {
+        self.node(*self.declaration_ids.get(index)?)
+    }
+
+    /// Returns the `kind` node of the concrete syntax tree spanning `span`,
+    /// like the `"model"` node of a model of the abstract syntax tree.
+    pub fn node_at(&self, kind: &str, span: Range<usize>) -> Option<&GreenNode> {
+        self.node_ids
+            .get(&span)?
+            .iter()
+            .filter_map(|id| self.node(*id))
+            .find(|node| node.kind == kind)
+    }
+}
+
+/// Parse `source` into both a concrete and an abstract syntax tree.
+///
+/// This is cheaper than calling [`parse_lossless`](super::parse_lossless)
+/// and [`parse_schema`](crate::parser::parse_schema) separately, since the
+/// source is lexed and parsed once, but the result keeps both trees alive:
+/// besides the syntax tree, the concrete tree holds a node per traced node
+/// and a copy of the text of every token, whitespace and comments
+/// included, which is a few times the size of the source.
+pub fn parse_full(source: &str) -> FullParseResult {
+    let (ast, cst, diagnostics) = parse_tree(source);
+    let declaration_ids = cst
+        .nodes()
+        .filter(|node| node.kind == "declaration")
+        .map(|node| node.id)
+        .collect();
+    let mut node_ids = HashMap::<Range<usize>, Vec<usize>>::new();
+    let mut pending = vec![&cst];
+    while let Some(node) = pending.pop() {
+        node_ids.entry(node.span.clone()).or_default().push(node.id);
+        pending.extend(node.nodes());
+    }
+    for ids in node_ids.values_mut() {
+        ids.sort();
+    }
+    FullParseResult {
+        cst,
+        ast,
+        diagnostics,
+        declaration_ids,
+        node_ids,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Declaration;
+
+    #[test]
+    fn builds_both_trees_from_one_parse() {
+        let source = "// models\nmodel User {\n  id: Int\n}\n\nenum Role { ADMIN }\n@map(\"posts\")\nmodel Post { id: Int }\n";
+        let result = parse_full(source);
+        assert!(result.diagnostics.is_empty());
+        assert_eq!(result.cst.to_string(), source);
+        let names: Vec<&str> = result
+            .ast
+            .declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                Declaration::Model(model) => Some(model.name.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, vec!["User", "Post"]);
+        assert_eq!(result.declaration_ids.len(), 3);
+        let post = result.declaration_node(2).unwrap();
+        assert_eq!(post.to_string(), "@map(\"posts\")\nmodel Post { id: Int }");
+        assert_eq!(result.node(post.id), Some(post));
+        assert_eq!(result.declaration_node(3), None);
+    }
+
+    #[test]
+    fn finds_the_nodes_of_nested_declarations() {
+        let source = "namespace blog {\n  /// A post.\n  model Post { title: String }\n}\n";
+        let result = parse_full(source);
+        assert!(result.diagnostics.is_empty());
+        assert_eq!(result.declaration_ids.len(), 1);
+        let Declaration::Namespace(namespace) = &result.ast.declarations[0] else {
+            panic!("{:?}", result.ast.declarations);
+        };
+        let Declaration::Model(post) = &namespace.declarations[0] else {
+            panic!("{:?}", namespace.declarations);
+        };
+        let node = result.node_at("model", post.span.clone()).unwrap();
+        assert_eq!(node.to_string(), "model Post { title: String }");
+        let field = &post.fields[0];
+        let node = result.node_at("field", field.span.clone()).unwrap();
+        assert_eq!(node.to_string(), "title: String");
+        assert_eq!(result.node_at("enum", post.span.clone()), None);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::lexer::Token;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// # Green node
+///
+/// A node of the concrete syntax tree, which keeps every token of the
+/// source, whitespace and comments included, so that writing the tree
+/// back reproduces the source exactly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GreenNode {
+    /// The index of the node in a pre-order walk of the tree, which is 0
+    /// for the root.
+    pub id: usize,
+    /// The kind of node, like `"schema"`, `"declaration"` or `"model"`.
+    pub kind: &'static str,
+    /// The byte span of the node, from its first token through its last.
+    pub span: Range<usize>,
+    /// The nodes and tokens of the node in source order.
+    pub children: Vec<GreenElement>,
+}
+
+/// A child of a [`GreenNode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GreenElement {
+    Node(GreenNode),
+    Token(GreenToken),
+}
+
+/// # Green token
+///
+/// A token of the concrete syntax tree with its source text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GreenToken {
+    /// The kind of token.
+    pub kind: Token,
+    /// The text of the token as written in the source.
+    pub text: String,
+    /// The byte span of the token.
+    pub span: Range<usize>,
+}
+
+impl GreenNode {
+    /// Returns the child nodes of the node.
+    pub fn nodes(&self) -> impl Iterator<Item = &GreenNode> {
+        self.children.iter().filter_map(|child| match child {
+            GreenElement::Node(node) => Some(node),
+            GreenElement::Token(_) => None,
+        })
+    }
+
+    /// Returns the node with the id `id` in the subtree of the node.
+    pub fn find(&self, id: usize) -> Option<&GreenNode> {
+        if self.id == id {
+            return Some(self);
+        }
+        let mut nodes = self.nodes().peekable();
+        while let Some(node) = nodes.next() {
+            if nodes.peek().is_none_or(|next| next.id > id) {
+                return node.find(id);
+            }
+        }
+        None
+    }
+
+    /// Returns every token under the node, in source order.
+    pub fn tokens(&self) -> Vec<&GreenToken> {
+        let mut tokens = vec![];
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a GreenToken>) {
+        for child in &self.children {
+            match child {
+                GreenElement::Node(node) => node.collect_tokens(tokens),
+                GreenElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+}
+
+impl Display for GreenNode {
+    /// Writes the source text of the node.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for token in self.tokens() {
+            f.write_str(&token.text)?;
+        }
+        Ok(())
+    }
+}
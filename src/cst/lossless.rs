@@ -0,0 +1,162 @@
+use super::{GreenElement, GreenNode, GreenToken};
+use crate::ast::SchemaFile;
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{tokenize_lossless, Token};
+use crate::parser::doc_comment::attach_doc_comments;
+use crate::parser::{Parser, TraceEntry};
+use std::iter::Peekable;
+use std::ops::Range;
+
+/// Parse `source` into a concrete syntax tree that keeps its whitespace
+/// and comments, together with the problems found.
+///
+/// The root node is a `"schema"` node. Below it, every node the parser
+/// traced becomes a node of the same kind, covering the tokens it was
+/// parsed from, so the tree has the shape of the syntax tree down to
+/// decorators, fields and members. Whitespace and comments between the
+/// tokens of a node belong to that node and the rest to the enclosing
+/// node. Writing the tree back with its [`Display`](std::fmt::Display)
+/// implementation reproduces `source` byte for byte.
+pub fn parse_lossless(source: &str) -> (GreenNode, Vec<Diagnostic>) {
+    let (_, cst, diagnostics) = parse_tree(source);
+    (cst, diagnostics)
+}
+
+/// Parse `source` into its syntax tree, with doc comments attached, and
+/// its concrete syntax tree.
+pub(super) fn parse_tree(source: &str) -> (SchemaFile, GreenNode, Vec<Diagnostic>) {
+    let lossless = tokenize_lossless(source);
+    let (positions, tokens): (Vec<usize>, Vec<(Token, Range<usize>)>) = lossless
+        .iter()
+        .enumerate()
+        .filter(|(_, (token, _))| !token.is_trivia())
+        .map(|(index, token)| (index, token.clone()))
+        .unzip();
+    let mut parser = Parser::new(source, &tokens);
+    parser.enable_trace();
+    let mut schema = parser.parse_schema_file();
+    let doc_comments: Vec<Range<usize>> = lossless
+        .iter()
+        .filter(|(token, _)| *token == Token::DocComment)
+        .map(|(_, span)| span.clone())
+        .collect();
+    attach_doc_comments(&mut schema, source, &doc_comments);
+    // Nodes are traced as they are completed, so an enclosing node with
+    // the same tokens as a child comes after it. Reversing before the
+    // stable sort puts it first.
+    let mut nodes: Vec<(&'static str, Range<usize>)> = parser
+        .trace()
+        .iter()
+        .rev()
+        .filter(|entry| !entry.token_range.is_empty())
+        .map(|entry| (entry.node, lossless_range(entry, &positions)))
+        .collect();
+    nodes.sort_by_key(|(_, range)| (range.start, std::cmp::Reverse(range.end)));
+    let mut builder = Builder {
+        source,
+        tokens: &lossless,
+        next_id: 0,
+    };
+    let root = builder.node(
+        "schema",
+        0..lossless.len(),
+        &mut nodes.into_iter().peekable(),
+    );
+    (schema, root, parser.take_diagnostics())
+}
+
+/// Returns the indices of the lossless tokens from the first token of
+/// `entry` through its last.
+fn lossless_range(entry: &TraceEntry, positions: &[usize]) -> Range<usize> {
+    positions[entry.token_range.start]..positions[entry.token_range.end - 1] + 1
+}
+
+struct Builder<'a> {
+    source: &'a str,
+    tokens: &'a [(Token, Range<usize>)],
+    /// The id of the next node to build.
+    next_id: usize,
+}
+
+impl Builder<'_> {
+    /// Build the `kind` node over the lossless tokens at `range`, taking
+    /// the child nodes that start inside it from `nodes`, which is sorted
+    /// by start and then by decreasing length.
+    fn node(
+        &mut self,
+        kind: &'static str,
+        range: Range<usize>,
+        nodes: &mut Peekable<impl Iterator<Item = (&'static str, Range<usize>)>>,
+    ) -> GreenNode {
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut children = vec![];
+        let mut index = range.start;
+        while index < range.end {
+            match nodes.next_if(|(_, child)| child.start == index && child.end <= range.end) {
+                Some((child_kind, child)) => {
+                    index = child.end;
+                    children.push(GreenElement::Node(self.node(child_kind, child, nodes)));
+                }
+                None => {
+                    let (token, span) = &self.tokens[index];
+                    children.push(GreenElement::Token(GreenToken {
+                        kind: *token,
+                        text: self.source[span.clone()].to_owned(),
+                        span: span.clone(),
+                    }));
+                    index += 1;
+                }
+            }
+        }
+        let span = match (self.tokens.get(range.start), range.end.checked_sub(1)) {
+            (Some((_, first)), Some(last)) => first.start..self.tokens[last].1.end,
+            _ => 0..0,
+        };
+        GreenNode {
+            id,
+            kind,
+            span,
+            children,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "// Users of the app.\n\n/// A user.\n@map(\"users\")\nmodel User {\r\n\t@id id: Int // the id\n  name :String?\n}\n\nenum Role { ADMIN }   \n";
+
+    #[test]
+    fn reproduces_the_source_byte_for_byte() {
+        let (cst, diagnostics) = parse_lossless(SOURCE);
+        assert_eq!(cst.to_string(), SOURCE);
+        assert_eq!(cst.span, 0..SOURCE.len());
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn reproduces_source_that_fails_to_parse() {
+        let source = "model { id: }\n# enum Role {";
+        assert_eq!(parse_lossless(source).0.to_string(), source);
+    }
+
+    #[test]
+    fn nests_nodes_like_the_syntax_tree() {
+        let (cst, _) = parse_lossless(SOURCE);
+        let declarations: Vec<&GreenNode> = cst.nodes().collect();
+        assert_eq!(declarations.len(), 2);
+        let kinds: Vec<&str> = declarations[0].nodes().map(|node| node.kind).collect();
+        assert_eq!(kinds, vec!["decorator", "model"]);
+        let model = declarations[0].nodes().nth(1).unwrap();
+        assert_eq!(
+            &SOURCE[model.span.clone()],
+            &SOURCE[48..SOURCE.find("}\n").unwrap() + 1]
+        );
+        let fields: Vec<String> = model.nodes().map(|field| field.to_string()).collect();
+        assert_eq!(fields, vec!["@id id: Int", "name :String?"]);
+        let kinds: Vec<&str> = declarations[1].nodes().map(|node| node.kind).collect();
+        assert_eq!(kinds, vec!["enum"]);
+    }
+}
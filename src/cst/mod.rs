@@ -0,0 +1,7 @@
+pub mod full;
+pub mod green;
+pub mod lossless;
+
+pub use full::{parse_full, FullParseResult};
+pub use green::{GreenElement, GreenNode, GreenToken};
+pub use lossless::parse_lossless;
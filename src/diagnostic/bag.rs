@@ -0,0 +1,161 @@
+use super::{Diagnostic, DiagnosticSink};
+use std::collections::BTreeMap;
+
+/// # Diagnostic bag
+///
+/// Diagnostics of many files grouped by the path of their file, like the
+/// ones an import graph is resolved with. As a [`DiagnosticSink`] it takes
+/// them while they are found, a diagnostic without a path going under the
+/// empty path.
+///
+/// Files are kept in the order of their paths, and the diagnostics of a
+/// file in the order they came in until [`DiagnosticBag::sort`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiagnosticBag {
+    files: BTreeMap<String, Vec<Diagnostic>>,
+}
+
+impl DiagnosticBag {
+    /// Create an empty bag.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `diagnostic` about the file at `path`.
+    pub fn push(&mut self, path: impl Into<String>, diagnostic: Diagnostic) {
+        self.files.entry(path.into()).or_default().push(diagnostic);
+    }
+
+    /// Add every diagnostic of `diagnostics` about the file at `path`.
+    pub fn extend(
+        &mut self,
+        path: impl Into<String>,
+        diagnostics: impl IntoIterator<Item = Diagnostic>,
+    ) {
+        let mut diagnostics = diagnostics.into_iter().peekable();
+        if diagnostics.peek().is_some() {
+            self.files
+                .entry(path.into())
+                .or_default()
+                .extend(diagnostics);
+        }
+    }
+
+    /// Sort the diagnostics of each file by where their spans start, which
+    /// is by line and then column, and then by where they end. Diagnostics
+    /// of the same span keep their order.
+    pub fn sort(&mut self) {
+        for diagnostics in self.files.values_mut() {
+            diagnostics.sort_by_key(|diagnostic| (diagnostic.span.start, diagnostic.span.end));
+        }
+    }
+
+    /// Returns the diagnostics of the file at `path`.
+    pub fn file(&self, path: &str) -> &[Diagnostic] {
+        self.files.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns each file with diagnostics and its diagnostics, in the
+    /// order of the paths.
+    pub fn files(&self) -> impl Iterator<Item = (&str, &[Diagnostic])> {
+        self.files
+            .iter()
+            .map(|(path, diagnostics)| (path.as_str(), diagnostics.as_slice()))
+    }
+
+    /// Returns every diagnostic with the path of its file, file by file.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Diagnostic)> {
+        self.files().flat_map(|(path, diagnostics)| {
+            diagnostics.iter().map(move |diagnostic| (path, diagnostic))
+        })
+    }
+
+    /// Returns the number of diagnostics of every file.
+    pub fn len(&self) -> usize {
+        self.files.values().map(Vec::len).sum()
+    }
+
+    /// Returns true if there are no diagnostics.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns the diagnostics grouped by the path of their file.
+    pub fn into_files(self) -> BTreeMap<String, Vec<Diagnostic>> {
+        self.files
+    }
+}
+
+impl DiagnosticSink for DiagnosticBag {
+    fn emit(&mut self, diagnostic: Diagnostic) {
+        self.push("", diagnostic);
+    }
+
+    fn emit_in_file(&mut self, path: &str, diagnostic: Diagnostic) {
+        self.push(path, diagnostic);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fsutil::FSUtil;
+    use crate::resolve::resolve_imports_with_sink;
+    use std::collections::HashMap;
+
+    #[test]
+    fn groups_and_sorts_diagnostics_by_file() {
+        let mut bag = DiagnosticBag::new();
+        bag.push("b.teo", Diagnostic::error("b late", 30..32));
+        bag.push("a.teo", Diagnostic::warning("a late", 12..14));
+        bag.push("b.teo", Diagnostic::error("b early", 2..3));
+        bag.emit_in_file("a.teo", Diagnostic::error("a early", 4..9));
+        bag.extend("b.teo", [Diagnostic::error("b middle", 2..8)]);
+        bag.extend("c.teo", []);
+        bag.sort();
+        let messages: Vec<(&str, &str)> = bag
+            .iter()
+            .map(|(path, diagnostic)| (path, diagnostic.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("a.teo", "a early"),
+                ("a.teo", "a late"),
+                ("b.teo", "b early"),
+                ("b.teo", "b middle"),
+                ("b.teo", "b late"),
+            ]
+        );
+        assert_eq!(bag.len(), 5);
+        assert_eq!(bag.files().count(), 2);
+        assert_eq!(bag.file("a.teo").len(), 2);
+        assert!(bag.file("c.teo").is_empty());
+    }
+
+    #[test]
+    fn collects_the_diagnostics_of_an_import_graph() {
+        let files = HashMap::from([
+            (
+                "schema/main.teo".to_owned(),
+                "import \"./user\"\nimport \"./missing\"\nmodel A { id: }".to_owned(),
+            ),
+            ("schema/user.teo".to_owned(), "model User { : }".to_owned()),
+        ]);
+        let mut bag = DiagnosticBag::new();
+        resolve_imports_with_sink("schema/main.teo", &FSUtil::in_memory(files), &mut bag);
+        bag.sort();
+        let starts: Vec<(&str, usize)> = bag
+            .iter()
+            .map(|(path, diagnostic)| (path, diagnostic.span.start))
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                ("schema/main.teo", 23),
+                ("schema/main.teo", 49),
+                ("schema/user.teo", 13),
+            ]
+        );
+    }
+}
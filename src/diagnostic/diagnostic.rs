@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// How serious a diagnostic is. Severities are ordered from the least to
+/// the most serious.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// # Diagnostic
+///
+/// A problem found in a schema source, pointing at the span it is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// A short stable identifier of the kind of problem, like
+    /// `"invalid-regex"`, for tools that filter diagnostics.
+    pub code: Option<&'static str>,
+    /// A human readable description of the problem.
+    pub message: String,
+    /// The byte span the diagnostic points at.
+    pub span: Range<usize>,
+    /// Further messages about other spans related to the problem, like the
+    /// first declaration of a name declared twice.
+    pub notes: Vec<Note>,
+}
+
+/// A message about a span related to a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    /// A human readable description of the related span.
+    pub message: String,
+    /// The byte span the note points at.
+    pub span: Range<usize>,
+}
+
+impl Diagnostic {
+    /// Create a new error diagnostic.
+    pub fn error(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self::new(Severity::Error, message, span)
+    }
+
+    /// Create a new warning diagnostic.
+    pub fn warning(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self::new(Severity::Warning, message, span)
+    }
+
+    /// Create a new diagnostic without a code.
+    pub fn new(severity: Severity, message: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            severity,
+            code: None,
+            message: message.into(),
+            span,
+            notes: vec![],
+        }
+    }
+
+    /// Returns the diagnostic with `code` attached.
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Returns the diagnostic with a note about `span` attached.
+    pub fn with_note(mut self, message: impl Into<String>, span: Range<usize>) -> Self {
+        self.notes.push(Note {
+            message: message.into(),
+            span,
+        });
+        self
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "{}[{}]: {}", self.severity, code, self.message),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::{Diagnostic, Severity};
+
+/// Returns the most serious severity among `diagnostics`, or `None` if
+/// there are none.
+pub fn worst_severity(diagnostics: &[Diagnostic]) -> Option<Severity> {
+    diagnostics.iter().map(|diagnostic| diagnostic.severity).max()
+}
+
+/// Returns the process exit code for a command line tool that reported
+/// `diagnostics`: `1` if any of them is at least as serious as `fail_on`,
+/// `0` otherwise.
+///
+/// Pass `Severity::Warning` to fail on warnings in strict mode.
+pub fn exit_code(diagnostics: &[Diagnostic], fail_on: Severity) -> i32 {
+    match worst_severity(diagnostics) {
+        Some(severity) if severity >= fail_on => 1,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fails_when_an_error_is_present() {
+        let diagnostics = vec![
+            Diagnostic::warning("unused import", 0..6),
+            Diagnostic::error("expected `}`", 10..11),
+        ];
+        assert_eq!(worst_severity(&diagnostics), Some(Severity::Error));
+        assert_eq!(exit_code(&diagnostics, Severity::Error), 1);
+    }
+
+    #[test]
+    fn warnings_only_fail_in_strict_mode() {
+        let diagnostics = vec![Diagnostic::warning("unused import", 0..6)];
+        assert_eq!(worst_severity(&diagnostics), Some(Severity::Warning));
+        assert_eq!(exit_code(&diagnostics, Severity::Error), 0);
+        assert_eq!(exit_code(&diagnostics, Severity::Warning), 1);
+    }
+
+    #[test]
+    fn succeeds_without_diagnostics() {
+        assert_eq!(worst_severity(&[]), None);
+        assert_eq!(exit_code(&[], Severity::Info), 0);
+    }
+}
@@ -0,0 +1,16 @@
+pub mod bag;
+#[allow(clippy::module_inception)]
+pub mod diagnostic;
+pub mod exit_code;
+pub mod render;
+pub mod sink;
+pub mod span;
+
+pub use bag::DiagnosticBag;
+pub use diagnostic::{Diagnostic, Note, Severity};
+pub use exit_code::{exit_code, worst_severity};
+#[cfg(feature = "color")]
+pub use render::render_colored;
+pub use render::render;
+pub use sink::{DiagnosticSink, VecSink};
+pub use span::{cover_spans, merge_spans, span_text, span_text_lossy};
@@ -0,0 +1,223 @@
+use super::{span_text_lossy, Diagnostic, Severity};
+use crate::source_map::{FileId, SourceMap};
+
+/// Render `diagnostic` about the file `file` of `source_map` the way the
+/// Rust compiler prints errors: the severity, code and message, the
+/// `path:line:column` of the span, and the line the span starts on with
+/// the span underlined by carets.
+///
+/// A span running over several lines is underlined to the end of its
+/// first line. An empty span gets a single caret. Each note follows on a
+/// line of its own with the line and column it points at. Without the
+/// file in `source_map` only the first line is rendered.
+pub fn render(diagnostic: &Diagnostic, file: FileId, source_map: &SourceMap) -> String {
+    render_with(diagnostic, file, source_map, &Style::PLAIN)
+}
+
+/// Render `diagnostic` like [`render`], with ANSI colors for terminals.
+#[cfg(feature = "color")]
+pub fn render_colored(diagnostic: &Diagnostic, file: FileId, source_map: &SourceMap) -> String {
+    render_with(diagnostic, file, source_map, &Style::COLORED)
+}
+
+/// The escape codes written around the parts of a rendered diagnostic.
+struct Style {
+    error: &'static str,
+    warning: &'static str,
+    info: &'static str,
+    gutter: &'static str,
+    bold: &'static str,
+    reset: &'static str,
+}
+
+impl Style {
+    const PLAIN: Style = Style {
+        error: "",
+        warning: "",
+        info: "",
+        gutter: "",
+        bold: "",
+        reset: "",
+    };
+
+    #[cfg(feature = "color")]
+    const COLORED: Style = Style {
+        error: "\x1b[1;31m",
+        warning: "\x1b[1;33m",
+        info: "\x1b[1;36m",
+        gutter: "\x1b[1;34m",
+        bold: "\x1b[1m",
+        reset: "\x1b[0m",
+    };
+
+    fn severity(&self, severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => self.error,
+            Severity::Warning => self.warning,
+            Severity::Info => self.info,
+        }
+    }
+}
+
+fn render_with(
+    diagnostic: &Diagnostic,
+    file: FileId,
+    source_map: &SourceMap,
+    style: &Style,
+) -> String {
+    let mut output = format!(
+        "{}{}",
+        style.severity(diagnostic.severity),
+        diagnostic.severity
+    );
+    if let Some(code) = diagnostic.code {
+        output.push_str(&format!("[{}]", code));
+    }
+    output.push_str(&format!(
+        "{}{}: {}{}\n",
+        style.reset, style.bold, diagnostic.message, style.reset
+    ));
+    let Some(source) = source_map.source(file) else {
+        return output;
+    };
+    let start = diagnostic.span.start.min(source.len());
+    let Some(location) = source_map.location(file, start) else {
+        return output;
+    };
+    let bytes = source.as_bytes();
+    let line_start = bytes[..start]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    let line_end = bytes[start..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map_or(source.len(), |index| start + index);
+    let line = span_text_lossy(source, &(line_start..line_end)).trim_end_matches('\r');
+    let indent: String = span_text_lossy(source, &(line_start..start))
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let end = diagnostic.span.end.clamp(start, line_start + line.len());
+    let carets = span_text_lossy(source, &(start..end))
+        .chars()
+        .count()
+        .max(1);
+    let number = location.line.to_string();
+    let pad = " ".repeat(number.len());
+    output.push_str(&format!(
+        "{}{}-->{} {}:{}:{}\n",
+        pad, style.gutter, style.reset, location.path, location.line, location.column
+    ));
+    output.push_str(&format!("{} {}|{}\n", pad, style.gutter, style.reset));
+    output.push_str(&format!(
+        "{}{} |{} {}\n",
+        style.gutter, number, style.reset, line
+    ));
+    output.push_str(&format!(
+        "{} {}|{} {}{}{}{}\n",
+        pad,
+        style.gutter,
+        style.reset,
+        indent,
+        style.severity(diagnostic.severity),
+        "^".repeat(carets),
+        style.reset
+    ));
+    for note in &diagnostic.notes {
+        output.push_str(&format!(
+            "{} {}={} note: {}",
+            pad, style.gutter, style.reset, note.message
+        ));
+        if let Some(location) = source_map.location(file, note.span.start.min(source.len())) {
+            output.push_str(&format!(" at {}:{}", location.line, location.column));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_map() -> (SourceMap, FileId) {
+        let mut map = SourceMap::new();
+        map.add_file("other.teo", "");
+        let file = map.add_file(
+            "schema/main.teo",
+            "model User {\n  id: Int\n}\n\n\n\n\n\n\nenum Role {\n\tADMIN = ?\n}",
+        );
+        (map, file)
+    }
+
+    #[test]
+    fn underlines_a_single_line_span() {
+        let (map, file) = source_map();
+        let diagnostic = Diagnostic::error("unknown type `Int`", 19..22).with_code("unknown-type");
+        assert_eq!(
+            render(&diagnostic, file, &map),
+            concat!(
+                "error[unknown-type]: unknown type `Int`\n",
+                " --> schema/main.teo:2:7\n",
+                "  |\n",
+                "2 |   id: Int\n",
+                "  |       ^^^\n",
+            )
+        );
+    }
+
+    #[test]
+    fn underlines_the_first_line_of_a_multi_line_span() {
+        let (map, file) = source_map();
+        let diagnostic = Diagnostic::warning("empty enum", 31..51);
+        assert_eq!(
+            render(&diagnostic, file, &map),
+            concat!(
+                "warning: empty enum\n",
+                "  --> schema/main.teo:10:1\n",
+                "   |\n",
+                "10 | enum Role {\n",
+                "   | ^^^^^^^^^^^\n",
+            )
+        );
+        let diagnostic = Diagnostic::error("expected an expression", 52..52);
+        assert!(render(&diagnostic, file, &map).ends_with("11 | \tADMIN = ?\n   | \t        ^\n"));
+    }
+
+    #[test]
+    fn renders_notes_after_the_underline() {
+        let (map, file) = source_map();
+        let diagnostic = Diagnostic::error("unknown type `Int`", 19..22)
+            .with_note("in the model declared here", 6..10);
+        assert!(render(&diagnostic, file, &map)
+            .ends_with("  |       ^^^\n  = note: in the model declared here at 1:7\n"));
+    }
+
+    #[test]
+    fn renders_only_the_message_without_the_file() {
+        let (_, file) = source_map();
+        let diagnostic = Diagnostic::error("cannot find `./user`", 0..8);
+        assert_eq!(
+            render(&diagnostic, file, &SourceMap::new()),
+            "error: cannot find `./user`\n"
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn colors_are_the_only_difference() {
+        let (map, file) = source_map();
+        let diagnostic = Diagnostic::error("unknown type `Int`", 19..22);
+        let colored = render_colored(&diagnostic, file, &map);
+        assert!(colored.starts_with("\x1b[1;31merror"));
+        let mut plain = String::new();
+        let mut rest = colored.as_str();
+        while let Some(index) = rest.find('\x1b') {
+            plain.push_str(&rest[..index]);
+            rest = &rest[index + rest[index..].find('m').unwrap() + 1..];
+        }
+        plain.push_str(rest);
+        assert_eq!(plain, render(&diagnostic, file, &map));
+    }
+}
@@ -0,0 +1,80 @@
+use super::Diagnostic;
+
+/// # Diagnostic sink
+///
+/// Receives diagnostics as soon as they are found, so that a tool can
+/// report them while the parser or the import resolver is still running.
+pub trait DiagnosticSink {
+    /// Receive a diagnostic.
+    fn emit(&mut self, diagnostic: Diagnostic);
+
+    /// Receive a diagnostic about the file at `path`. Sinks that don't need
+    /// the path can rely on the default, which forwards to [`emit`].
+    ///
+    /// [`emit`]: DiagnosticSink::emit
+    fn emit_in_file(&mut self, path: &str, diagnostic: Diagnostic) {
+        let _ = path;
+        self.emit(diagnostic);
+    }
+}
+
+/// # Vec sink
+///
+/// A sink that collects the diagnostics in the order they are emitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VecSink {
+    /// The diagnostics emitted so far.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl DiagnosticSink for VecSink {
+    fn emit(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use crate::parser::Parser;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct CountingSink(Rc<Cell<usize>>);
+
+    impl DiagnosticSink for CountingSink {
+        fn emit(&mut self, _diagnostic: Diagnostic) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn emits_each_diagnostic_as_it_is_found() {
+        let source = "model A { id: }\nmodel B { id: Int }\nenum C { \"c\" }\nmodel D { : }";
+        let tokens = tokenize(source);
+        let count = Rc::new(Cell::new(0));
+        let mut sink = CountingSink(count.clone());
+        let mut parser = Parser::new(source, &tokens).with_sink(&mut sink);
+        let mut counts = vec![];
+        while !parser.is_at_end() {
+            parser.parse_declaration().unwrap();
+            counts.push(count.get());
+        }
+        assert_eq!(counts, vec![1, 1, 2, 3]);
+        assert!(parser.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn vec_sink_collects_in_order() {
+        let mut sink = VecSink::default();
+        sink.emit(Diagnostic::error("first", 0..1));
+        sink.emit_in_file("schema.teo", Diagnostic::warning("second", 1..2));
+        let messages: Vec<&str> = sink
+            .diagnostics
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["first", "second"]);
+    }
+}
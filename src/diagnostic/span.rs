@@ -0,0 +1,82 @@
+use std::ops::Range;
+
+/// Returns the text of `source` at `span`, or `None` if the span is out of
+/// range, reversed or doesn't start and end on character boundaries.
+pub fn span_text<'a>(source: &'a str, span: &Range<usize>) -> Option<&'a str> {
+    source.get(span.clone())
+}
+
+/// Returns the text of `source` at `span`, clamped to the source and
+/// widened to the characters the span touches. A reversed span gives an
+/// empty string.
+pub fn span_text_lossy<'a>(source: &'a str, span: &Range<usize>) -> &'a str {
+    let mut start = span.start.min(source.len());
+    while !source.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = span.end.clamp(start, source.len());
+    while !source.is_char_boundary(end) {
+        end += 1;
+    }
+    &source[start..end]
+}
+
+/// Returns the smallest span covering both `a` and `b`, like the span of a
+/// node from the spans of its first and last children.
+pub fn merge_spans(a: &Range<usize>, b: &Range<usize>) -> Range<usize> {
+    a.start.min(b.start)..a.end.max(b.end)
+}
+
+/// Returns the smallest span covering every span of `spans`, or `None` if
+/// there are none.
+pub fn cover_spans<'a>(spans: impl IntoIterator<Item = &'a Range<usize>>) -> Option<Range<usize>> {
+    spans.into_iter().fold(None, |covered, span| match covered {
+        Some(covered) => Some(merge_spans(&covered, span)),
+        None => Some(span.clone()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_text_of_an_in_bounds_span() {
+        assert_eq!(span_text("model User", &(6..10)), Some("User"));
+        assert_eq!(span_text_lossy("model User", &(6..10)), "User");
+    }
+
+    #[test]
+    fn handles_an_out_of_range_span() {
+        assert_eq!(span_text("model", &(3..9)), None);
+        assert_eq!(span_text("model", &(7..9)), None);
+        assert_eq!(span_text_lossy("model", &(3..9)), "el");
+        assert_eq!(span_text_lossy("model", &(7..9)), "");
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 4..2;
+        assert_eq!(span_text("model", &reversed), None);
+        assert_eq!(span_text_lossy("model", &reversed), "");
+    }
+
+    #[test]
+    fn merges_spans_in_either_order() {
+        assert_eq!(merge_spans(&(0..5), &(11..12)), 0..12);
+        assert_eq!(merge_spans(&(11..12), &(0..5)), 0..12);
+        assert_eq!(merge_spans(&(0..12), &(6..10)), 0..12);
+    }
+
+    #[test]
+    fn covers_every_span() {
+        assert_eq!(cover_spans(&[6..10, 0..5, 13..25]), Some(0..25));
+        assert_eq!(cover_spans(std::iter::once(&(3..4))), Some(3..4));
+        assert_eq!(cover_spans(&[]), None);
+    }
+
+    #[test]
+    fn handles_a_span_splitting_a_multibyte_character() {
+        let source = "name: \"café\"";
+        assert_eq!(span_text(source, &(7..11)), None);
+        assert_eq!(span_text_lossy(source, &(7..11)), "café");
+        assert_eq!(span_text_lossy(source, &(11..12)), "é");
+    }
+}
@@ -0,0 +1,201 @@
+use super::SchemaChange;
+use crate::ast::TypeExpr;
+use std::collections::BTreeSet;
+
+/// Whether a schema change keeps existing data and clients working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compatibility {
+    /// The change cannot break existing data or clients.
+    Safe,
+    /// The change may break existing data or clients.
+    Breaking,
+    /// The change cannot be classified from the schema alone.
+    Unknown,
+}
+
+/// Classify each of `changes`.
+///
+/// The rules are:
+///
+/// * Adding a model, an enum or an enum member is safe.
+/// * Removing a model, a field, an enum or an enum member is breaking.
+/// * Adding an optional field or a field with a default is safe. Adding a
+///   required field without one is breaking, since existing records have
+///   no value for it. A union with an optional member, like
+///   `Int | String?`, isn't optional.
+/// * Changing a field type is safe if it widens the type, like `Int` to
+///   `Int?` or `Int` to `Int | String`, and breaking if it narrows it, like
+///   `Int?` to `Int` or `Int | String` to `Int`. Any other type change is
+///   unknown.
+pub fn classify_changes(changes: &[SchemaChange]) -> Vec<(SchemaChange, Compatibility)> {
+    changes
+        .iter()
+        .map(|change| (change.clone(), classify(change)))
+        .collect()
+}
+
+fn classify(change: &SchemaChange) -> Compatibility {
+    match change {
+        SchemaChange::ModelAdded { .. }
+        | SchemaChange::EnumAdded { .. }
+        | SchemaChange::EnumMemberAdded { .. } => Compatibility::Safe,
+        SchemaChange::ModelRemoved { .. }
+        | SchemaChange::FieldRemoved { .. }
+        | SchemaChange::EnumRemoved { .. }
+        | SchemaChange::EnumMemberRemoved { .. } => Compatibility::Breaking,
+        SchemaChange::FieldAdded {
+            optional,
+            has_default,
+            ..
+        } => match *optional || *has_default {
+            true => Compatibility::Safe,
+            false => Compatibility::Breaking,
+        },
+        SchemaChange::FieldTypeChanged {
+            old_type_expr,
+            new_type_expr,
+            ..
+        } => {
+            if widens(old_type_expr, new_type_expr) {
+                Compatibility::Safe
+            } else if widens(new_type_expr, old_type_expr) {
+                Compatibility::Breaking
+            } else {
+                Compatibility::Unknown
+            }
+        }
+    }
+}
+
+/// Returns true if every value of the type `narrow` is also a value of the
+/// type `wide`.
+fn widens(narrow: &TypeExpr, wide: &TypeExpr) -> bool {
+    match (narrow, wide) {
+        (TypeExpr::Optional { inner: narrow, .. }, TypeExpr::Optional { inner: wide, .. }) => {
+            widens(narrow, wide)
+        }
+        (_, TypeExpr::Optional { inner, .. }) => same_type(inner, narrow) || widens(narrow, inner),
+        _ => {
+            let (narrow_members, wide_members) = (members(narrow), members(wide));
+            wide_members.len() > narrow_members.len() && narrow_members.is_subset(&wide_members)
+        }
+    }
+}
+
+/// Returns true if `a` and `b` are the same type, with the members of
+/// their unions in any order.
+pub(crate) fn same_type(a: &TypeExpr, b: &TypeExpr) -> bool {
+    match (a, b) {
+        (TypeExpr::Optional { inner: a, .. }, TypeExpr::Optional { inner: b, .. })
+        | (TypeExpr::Array { inner: a, .. }, TypeExpr::Array { inner: b, .. }) => same_type(a, b),
+        _ => members(a) == members(b),
+    }
+}
+
+/// Returns the members of `type_expr` as written, or the type itself if it
+/// isn't a union.
+fn members(type_expr: &TypeExpr) -> BTreeSet<String> {
+    match type_expr {
+        TypeExpr::Union { members, .. } => members.iter().map(ToString::to_string).collect(),
+        type_expr => BTreeSet::from([type_expr.to_string()]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::diff_schemas;
+    use crate::parser::parse_schema;
+
+    fn classify_diff(old: &str, new: &str) -> Vec<Compatibility> {
+        let (old, new) = (parse_schema(old, "old.teo"), parse_schema(new, "new.teo"));
+        classify_changes(&diff_schemas(&old.schema, &new.schema))
+            .into_iter()
+            .map(|(_, compatibility)| compatibility)
+            .collect()
+    }
+
+    #[test]
+    fn a_removed_field_is_breaking() {
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int\n email: String }",
+                "model User { id: Int }"
+            ),
+            vec![Compatibility::Breaking]
+        );
+    }
+
+    #[test]
+    fn an_added_optional_field_is_safe() {
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int }",
+                "model User { id: Int\n bio: String? }"
+            ),
+            vec![Compatibility::Safe]
+        );
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int }",
+                "model User { id: Int\n bio: String }"
+            ),
+            vec![Compatibility::Breaking]
+        );
+    }
+
+    #[test]
+    fn a_type_widening_is_safe() {
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int\n tag: String }",
+                "model User { id: Int | String\n tag: String? }"
+            ),
+            vec![Compatibility::Safe, Compatibility::Safe]
+        );
+    }
+
+    #[test]
+    fn a_type_narrowing_is_breaking_and_a_swap_is_unknown() {
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int | String\n tag: String? }",
+                "model User { id: Int\n tag: Bool }"
+            ),
+            vec![Compatibility::Breaking, Compatibility::Unknown]
+        );
+    }
+
+    #[test]
+    fn union_members_are_compared_as_sets() {
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int\n tag: String | Int }",
+                "model User { id: String | Int\n tag: (Int | String | Bool)? }"
+            ),
+            vec![Compatibility::Safe, Compatibility::Safe]
+        );
+    }
+
+    #[test]
+    fn a_union_with_an_optional_member_is_not_optional() {
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int }",
+                "model User { id: Int\n tag: Int | String? }"
+            ),
+            vec![Compatibility::Breaking]
+        );
+    }
+
+    #[test]
+    fn an_added_field_with_a_default_is_safe() {
+        assert_eq!(
+            classify_diff(
+                "model User { id: Int }",
+                "model User { id: Int\n count: Int = 0\n @default(false) admin: Bool }"
+            ),
+            vec![Compatibility::Safe, Compatibility::Safe]
+        );
+    }
+}
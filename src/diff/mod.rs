@@ -0,0 +1,5 @@
+pub mod compatibility;
+pub mod schema;
+
+pub use compatibility::{classify_changes, Compatibility};
+pub use schema::{diff_schemas, SchemaChange};
@@ -0,0 +1,395 @@
+use super::compatibility::same_type;
+use crate::ast::{Declaration, EnumDeclaration, ModelDeclaration, SchemaFile, TypeExpr};
+
+/// A semantic difference between two versions of a schema.
+///
+/// Declarations, fields and members are matched by name. Types are
+/// compared as written, like `String?` or `Int[]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    ModelAdded {
+        model: String,
+    },
+    ModelRemoved {
+        model: String,
+    },
+    FieldAdded {
+        model: String,
+        field: String,
+        field_type: String,
+        /// Whether the outermost type is optional, like `String?`.
+        optional: bool,
+        /// Whether the field has a default, written inline or with
+        /// `@default`.
+        has_default: bool,
+    },
+    FieldRemoved {
+        model: String,
+        field: String,
+    },
+    FieldTypeChanged {
+        model: String,
+        field: String,
+        old_type: String,
+        new_type: String,
+        /// The types as parsed, with the spans of their files.
+        old_type_expr: TypeExpr,
+        new_type_expr: TypeExpr,
+    },
+    EnumAdded {
+        r#enum: String,
+    },
+    EnumRemoved {
+        r#enum: String,
+    },
+    EnumMemberAdded {
+        r#enum: String,
+        member: String,
+    },
+    EnumMemberRemoved {
+        r#enum: String,
+        member: String,
+    },
+}
+
+/// Returns the changes that turn `old` into `new`.
+///
+/// Formatting, comments and the order of declarations and fields are
+/// ignored. Changes to models come before changes to enums; within each,
+/// declarations of `old` are visited in order, followed by the declarations
+/// only found in `new`. Declarations of a `namespace` are named by their
+/// qualified names, like `blog::Post`, so moving a model into a namespace
+/// removes it and adds another.
+pub fn diff_schemas(old: &SchemaFile, new: &SchemaFile) -> Vec<SchemaChange> {
+    let mut changes = vec![];
+    let (old, new) = (flatten(old), flatten(new));
+    let (old_models, new_models) = (models(&old), models(&new));
+    for (name, old_model) in &old_models {
+        match find(&new_models, name) {
+            Some(new_model) => diff_models(name, old_model, new_model, &mut changes),
+            None => changes.push(SchemaChange::ModelRemoved {
+                model: name.clone(),
+            }),
+        }
+    }
+    for (name, _) in &new_models {
+        if find(&old_models, name).is_none() {
+            changes.push(SchemaChange::ModelAdded {
+                model: name.clone(),
+            });
+        }
+    }
+    let (old_enums, new_enums) = (enums(&old), enums(&new));
+    for (name, old_enum) in &old_enums {
+        match find(&new_enums, name) {
+            Some(new_enum) => diff_enums(name, old_enum, new_enum, &mut changes),
+            None => changes.push(SchemaChange::EnumRemoved {
+                r#enum: name.clone(),
+            }),
+        }
+    }
+    for (name, _) in &new_enums {
+        if find(&old_enums, name).is_none() {
+            changes.push(SchemaChange::EnumAdded {
+                r#enum: name.clone(),
+            });
+        }
+    }
+    changes
+}
+
+fn diff_models(
+    model: &str,
+    old: &ModelDeclaration,
+    new: &ModelDeclaration,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_field in &old.fields {
+        match new
+            .fields
+            .iter()
+            .find(|f| f.name.name == old_field.name.name)
+        {
+            Some(new_field) => {
+                let (old_type, new_type) = (
+                    old_field.type_expr.to_string(),
+                    new_field.type_expr.to_string(),
+                );
+                if !same_type(&old_field.type_expr, &new_field.type_expr) {
+                    changes.push(SchemaChange::FieldTypeChanged {
+                        model: model.to_owned(),
+                        field: old_field.name.name.clone(),
+                        old_type,
+                        new_type,
+                        old_type_expr: old_field.type_expr.clone(),
+                        new_type_expr: new_field.type_expr.clone(),
+                    });
+                }
+            }
+            None => changes.push(SchemaChange::FieldRemoved {
+                model: model.to_owned(),
+                field: old_field.name.name.clone(),
+            }),
+        }
+    }
+    for new_field in &new.fields {
+        if !old
+            .fields
+            .iter()
+            .any(|f| f.name.name == new_field.name.name)
+        {
+            changes.push(SchemaChange::FieldAdded {
+                model: model.to_owned(),
+                field: new_field.name.name.clone(),
+                field_type: new_field.type_expr.to_string(),
+                optional: new_field.type_expr.is_optional(),
+                has_default: new_field.default.is_some()
+                    || new_field
+                        .decorators
+                        .iter()
+                        .any(|decorator| decorator.name() == "default"),
+            });
+        }
+    }
+}
+
+fn diff_enums(
+    r#enum: &str,
+    old: &EnumDeclaration,
+    new: &EnumDeclaration,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_member in &old.members {
+        if !new
+            .members
+            .iter()
+            .any(|m| m.name.name == old_member.name.name)
+        {
+            changes.push(SchemaChange::EnumMemberRemoved {
+                r#enum: r#enum.to_owned(),
+                member: old_member.name.name.clone(),
+            });
+        }
+    }
+    for new_member in &new.members {
+        if !old
+            .members
+            .iter()
+            .any(|m| m.name.name == new_member.name.name)
+        {
+            changes.push(SchemaChange::EnumMemberAdded {
+                r#enum: r#enum.to_owned(),
+                member: new_member.name.name.clone(),
+            });
+        }
+    }
+}
+
+/// Returns the declarations of `schema` and of its namespaces, each with
+/// its qualified name, in source order.
+fn flatten(schema: &SchemaFile) -> Vec<(String, &Declaration)> {
+    let mut declarations = vec![];
+    collect(&schema.declarations, "", &mut declarations);
+    declarations
+}
+
+fn collect<'a>(
+    declarations: &'a [Declaration],
+    prefix: &str,
+    flattened: &mut Vec<(String, &'a Declaration)>,
+) {
+    for declaration in declarations {
+        let Some(name) = declaration.name() else {
+            continue;
+        };
+        let name = format!("{}{}", prefix, name.name);
+        if let Declaration::Namespace(namespace) = declaration {
+            collect(&namespace.declarations, &format!("{}::", name), flattened);
+        }
+        flattened.push((name, declaration));
+    }
+}
+
+fn models<'a>(declarations: &[(String, &'a Declaration)]) -> Vec<(String, &'a ModelDeclaration)> {
+    declarations
+        .iter()
+        .filter_map(|(name, declaration)| match declaration {
+            Declaration::Model(model) => Some((name.clone(), model)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn enums<'a>(declarations: &[(String, &'a Declaration)]) -> Vec<(String, &'a EnumDeclaration)> {
+    declarations
+        .iter()
+        .filter_map(|(name, declaration)| match declaration {
+            Declaration::Enum(r#enum) => Some((name.clone(), r#enum)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn find<'a, T>(items: &'a [(String, T)], name: &str) -> Option<&'a T> {
+    items
+        .iter()
+        .find(|(item, _)| item == name)
+        .map(|(_, item)| item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    fn diff(old: &str, new: &str) -> Vec<SchemaChange> {
+        let (old, new) = (parse_schema(old, "old.teo"), parse_schema(new, "new.teo"));
+        assert!(old.diagnostics.is_empty() && new.diagnostics.is_empty());
+        diff_schemas(&old.schema, &new.schema)
+    }
+
+    #[test]
+    fn reports_an_added_field() {
+        let changes = diff(
+            "model User { id: Int }",
+            "// users\nmodel User {\n  id: Int\n  email: String\n}",
+        );
+        assert_eq!(
+            changes,
+            vec![SchemaChange::FieldAdded {
+                model: "User".to_owned(),
+                field: "email".to_owned(),
+                field_type: "String".to_owned(),
+                optional: false,
+                has_default: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_a_removed_model() {
+        let changes = diff(
+            "model User { id: Int }\nmodel Post { id: Int }",
+            "model User { id: Int }",
+        );
+        assert_eq!(
+            changes,
+            vec![SchemaChange::ModelRemoved {
+                model: "Post".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_an_added_field_and_a_removed_model_together() {
+        let changes = diff(
+            "model User { id: Int }\nmodel Post { id: Int }",
+            "model User { id: Int, name: String? }",
+        );
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::FieldAdded {
+                    model: "User".to_owned(),
+                    field: "name".to_owned(),
+                    field_type: "String?".to_owned(),
+                    optional: true,
+                    has_default: false,
+                },
+                SchemaChange::ModelRemoved {
+                    model: "Post".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_a_field_type_change() {
+        let changes = diff(
+            "model User { id: Int\n tags: String[] }",
+            "model User { tags: String[]?\n id: Int }",
+        );
+        let [SchemaChange::FieldTypeChanged {
+            model,
+            field,
+            old_type,
+            new_type,
+            old_type_expr,
+            new_type_expr,
+        }] = changes.as_slice()
+        else {
+            panic!("{:?}", changes);
+        };
+        assert_eq!((model.as_str(), field.as_str()), ("User", "tags"));
+        assert_eq!(
+            (old_type.as_str(), new_type.as_str()),
+            ("String[]", "String[]?")
+        );
+        assert_eq!(old_type_expr.span(), 28..36);
+        assert!(new_type_expr.is_optional());
+    }
+
+    #[test]
+    fn reports_enum_member_changes() {
+        let changes = diff(
+            "enum Role { ADMIN\n USER }",
+            "enum Role { USER\n GUEST }\nenum Sex { MALE }",
+        );
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::EnumMemberRemoved {
+                    r#enum: "Role".to_owned(),
+                    member: "ADMIN".to_owned(),
+                },
+                SchemaChange::EnumMemberAdded {
+                    r#enum: "Role".to_owned(),
+                    member: "GUEST".to_owned(),
+                },
+                SchemaChange::EnumAdded {
+                    r#enum: "Sex".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn names_declarations_of_namespaces_by_their_qualified_names() {
+        let changes = diff(
+            "namespace blog { model Post { id: Int } }\nmodel Post { id: Int }",
+            "namespace blog { model Post { id: Int, title: String } enum Status { DRAFT } }",
+        );
+        assert_eq!(
+            changes,
+            vec![
+                SchemaChange::FieldAdded {
+                    model: "blog::Post".to_owned(),
+                    field: "title".to_owned(),
+                    field_type: "String".to_owned(),
+                    optional: false,
+                    has_default: false,
+                },
+                SchemaChange::ModelRemoved {
+                    model: "Post".to_owned(),
+                },
+                SchemaChange::EnumAdded {
+                    r#enum: "blog::Status".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_formatting_and_order() {
+        assert!(diff(
+            "model A { id: Int }\nmodel B { id: Int | String }",
+            "model B {\n  id: Int|String\n}\n\nmodel A { id: Int }",
+        )
+        .is_empty());
+        assert!(diff(
+            "model A { id: Int | String, tags: (Int | String)[] }",
+            "model A { id: String | Int, tags: (String | Int)[] }",
+        )
+        .is_empty());
+    }
+}
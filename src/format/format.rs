@@ -0,0 +1,870 @@
+use crate::ast::{
+    Argument, BinaryOperator, ConfigItem, Declaration, Decorator, EnumDeclaration, Expr,
+    FieldDeclaration, FunctionDeclaration, Identifier, ImportKind, Literal, LiteralValue,
+    ModelDeclaration, SchemaFile, StringPart, TypeParam, UnaryOperator,
+};
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::lexer::{tokenize, Token};
+use crate::parser::doc_comment::attach_doc_comments;
+use crate::parser::Parser;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// # Format options
+///
+/// How [`format_with_options`] lays out a schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// The number of spaces each level of a body is indented by.
+    pub indent: usize,
+    /// Whether the types of the fields of a body are aligned in a column,
+    /// by padding the space after the `:` of the shorter fields.
+    pub align_field_types: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            align_field_types: false,
+        }
+    }
+}
+
+/// Print `schema` in the canonical layout, with the default
+/// [`FormatOptions`].
+///
+/// See [`format_with_options`].
+pub fn format(schema: &SchemaFile) -> String {
+    format_with_options(schema, &FormatOptions::default())
+}
+
+/// Print `schema` in the canonical layout described by `options`.
+///
+/// Declarations are separated by a blank line and members are written one
+/// per line. Decorators of a declaration are written on the lines above
+/// it, those of a member before it on the same line. Single spaces go
+/// around binary operators and after `,` and `:`. Doc comments attached
+/// to the tree are kept, other comments are not part of it and are
+/// dropped. The body of an unknown declaration is not part of the tree
+/// either and is printed empty. [`format_source`] keeps both or refuses.
+///
+/// Formatting the output again gives the same text.
+pub fn format_with_options(schema: &SchemaFile, options: &FormatOptions) -> String {
+    let mut printer = Printer {
+        options,
+        source: None,
+        output: String::new(),
+    };
+    printer.declarations(&schema.declarations);
+    printer.output
+}
+
+/// # Format error
+///
+/// Why [`format_source`] left a source unformatted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    /// The source has syntax errors, so some of it isn't in the tree.
+    Syntax(Vec<Diagnostic>),
+    /// The comment at the span isn't a doc comment attached to the tree
+    /// and would be lost.
+    Comment(Range<usize>),
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatError::Syntax(diagnostics) => {
+                write!(f, "the source has {} syntax errors", diagnostics.len())
+            }
+            FormatError::Comment(span) => {
+                write!(f, "the comment at {:?} would be lost", span)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// Parse `source` and print it like [`format_with_options`], unless that
+/// would lose some of it.
+///
+/// Declarations with unknown keywords are kept, with their bodies as
+/// written. A source with syntax errors, or with a comment other than a
+/// doc comment attached to a declaration or member, is an error.
+pub fn format_source(source: &str, options: &FormatOptions) -> Result<String, FormatError> {
+    let (comments, tokens): (Vec<_>, Vec<_>) = tokenize(source)
+        .into_iter()
+        .partition(|(token, _)| token.is_comment());
+    let mut parser = Parser::new(source, &tokens).with_unknown_declarations();
+    let mut schema = parser.parse_schema_file();
+    let diagnostics = parser.take_diagnostics();
+    if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+        return Err(FormatError::Syntax(diagnostics));
+    }
+    let doc_comments: Vec<Range<usize>> = comments
+        .iter()
+        .filter(|(token, _)| *token == Token::DocComment)
+        .map(|(_, span)| span.clone())
+        .collect();
+    attach_doc_comments(&mut schema, source, &doc_comments);
+    let mut printer = Printer {
+        options,
+        source: Some(source),
+        output: String::new(),
+    };
+    printer.declarations(&schema.declarations);
+    // The doc comments of the output, in order, must include every one
+    // of the source.
+    let output = printer.output;
+    let printed = tokenize(&output)
+        .into_iter()
+        .filter(|(token, _)| *token == Token::DocComment)
+        .map(|(_, span)| doc_text(&output[span]).to_owned())
+        .collect::<Vec<String>>();
+    let mut printed = printed.iter();
+    for (token, span) in comments {
+        let text = doc_text(&source[span.clone()]);
+        if token != Token::DocComment || !printed.any(|printed| printed == text) {
+            return Err(FormatError::Comment(span));
+        }
+    }
+    Ok(output)
+}
+
+/// Returns the text of a `///` comment without the slashes.
+fn doc_text(comment: &str) -> &str {
+    comment.trim_start_matches('/').trim()
+}
+
+struct Printer<'a> {
+    options: &'a FormatOptions,
+    /// The source the tree was parsed from, to print the bodies of unknown
+    /// declarations from.
+    source: Option<&'a str>,
+    output: String,
+}
+
+impl Printer<'_> {
+    /// Write `text` as a line indented by `level` levels.
+    fn line(&mut self, level: usize, text: &str) {
+        self.output
+            .extend(std::iter::repeat_n(' ', level * self.options.indent));
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    fn doc(&mut self, level: usize, doc: Option<&str>) {
+        for line in doc.into_iter().flat_map(str::lines) {
+            match line.is_empty() {
+                true => self.line(level, "///"),
+                false => self.line(level, &format!("/// {}", line)),
+            }
+        }
+    }
+
+    /// Write the decorators that aren't block decorators on their own
+    /// lines.
+    fn outer_decorators(&mut self, decorators: &[Decorator]) {
+        for decorator in decorators.iter().filter(|d| !d.block) {
+            self.line(0, &decorator_text(decorator));
+        }
+    }
+
+    /// Write the `{ ... }` body of a declaration whose header is `header`,
+    /// with the block decorators first.
+    fn body(&mut self, header: &str, decorators: &[Decorator], members: Vec<Vec<String>>) {
+        let block: Vec<&Decorator> = decorators.iter().filter(|d| d.block).collect();
+        if block.is_empty() && members.is_empty() {
+            self.line(0, &format!("{} {{}}", header));
+            return;
+        }
+        self.line(0, &format!("{} {{", header));
+        for decorator in block {
+            self.line(1, &decorator_text(decorator));
+        }
+        for lines in members {
+            for line in lines {
+                self.line(1, &line);
+            }
+        }
+        self.line(0, "}");
+    }
+
+    /// Write `declarations` separated by blank lines.
+    fn declarations(&mut self, declarations: &[Declaration]) {
+        for (index, declaration) in declarations.iter().enumerate() {
+            if index > 0 {
+                self.output.push('\n');
+            }
+            self.declaration(declaration);
+        }
+    }
+
+    fn declaration(&mut self, declaration: &Declaration) {
+        match declaration {
+            Declaration::Import(import) => {
+                let source = string_literal(&import.source);
+                let text = match (import.kind, import.identifiers.is_empty()) {
+                    (ImportKind::Include, _) => format!("include {}", source),
+                    (ImportKind::Import, true) => format!("import {}", source),
+                    (ImportKind::Import, false) => format!(
+                        "import {{ {} }} from {}",
+                        names(&import.identifiers),
+                        source
+                    ),
+                };
+                self.line(0, &text);
+            }
+            Declaration::Export(export) => {
+                let mut text = format!("export {{ {} }}", names(&export.names));
+                if let Some(source) = &export.source {
+                    text.push_str(&format!(" from {}", string_literal(source)));
+                }
+                self.line(0, &text);
+            }
+            Declaration::Model(model) => self.model(model),
+            Declaration::Enum(r#enum) => self.r#enum(r#enum),
+            Declaration::Config(config) => {
+                let mut header = "config".to_owned();
+                for label in config.kind.iter().chain(&config.name) {
+                    header.push_str(&format!(" {}", label.name));
+                }
+                let items = config
+                    .items
+                    .iter()
+                    .map(|item| vec![config_item(item, ": ")]);
+                self.body(&header, &[], items.collect());
+            }
+            Declaration::ConfigSection(section) => {
+                self.line(0, &format!("[{}]", section.name.name));
+                for item in &section.entries {
+                    self.line(0, &config_item(item, " = "));
+                }
+            }
+            Declaration::TypeAlias(alias) => {
+                self.line(
+                    0,
+                    &format!(
+                        "type {}{} = {}",
+                        alias.name.name,
+                        type_parameters(&alias.type_parameters),
+                        alias.target
+                    ),
+                );
+            }
+            Declaration::Let(binding) => {
+                let mut text = format!("let {}", binding.name.name);
+                if let Some(type_expr) = &binding.type_expr {
+                    text.push_str(&format!(": {}", type_expr));
+                }
+                text.push_str(&format!(" = {}", expr(&binding.value)));
+                self.line(0, &text);
+            }
+            Declaration::Struct(r#struct) => {
+                let header = format!(
+                    "struct {}{}",
+                    r#struct.name.name,
+                    type_parameters(&r#struct.type_parameters)
+                );
+                let fields = self.fields(&r#struct.fields);
+                self.body(&header, &[], fields);
+            }
+            Declaration::Function(function) => self.line(0, &function_text(function)),
+            Declaration::Middleware(middleware) => {
+                self.outer_decorators(&middleware.decorators);
+                let mut text = format!("middleware {}", middleware.name.name);
+                if let Some(body) = &middleware.body {
+                    text.push_str(&format!(" {{ {} }}", expr(body)));
+                }
+                self.line(0, &text);
+            }
+            Declaration::Handler(handler) => {
+                self.outer_decorators(&handler.decorators);
+                let mut text = format!("handler {}", handler.name.name);
+                if let Some(body) = &handler.body {
+                    text.push_str(&format!(" {{ {} }}", expr(body)));
+                }
+                self.line(0, &text);
+            }
+            Declaration::Namespace(namespace) => {
+                let header = format!("namespace {}", namespace.name.name);
+                if namespace.declarations.is_empty() {
+                    self.line(0, &format!("{} {{}}", header));
+                    return;
+                }
+                let mut inner = Printer {
+                    options: self.options,
+                    source: self.source,
+                    output: String::new(),
+                };
+                inner.declarations(&namespace.declarations);
+                self.line(0, &format!("{} {{", header));
+                for line in inner.output.lines() {
+                    match line.is_empty() {
+                        true => self.output.push('\n'),
+                        false => self.line(1, line),
+                    }
+                }
+                self.line(0, "}");
+            }
+            Declaration::Unknown(unknown) => {
+                let body = match self.source {
+                    Some(source) => &source[unknown.body_span.clone()],
+                    None => "{}",
+                };
+                let text = format!("{} {} {}", unknown.keyword.name, unknown.name.name, body);
+                for line in text.lines() {
+                    self.line(0, line);
+                }
+            }
+        }
+    }
+
+    fn model(&mut self, model: &ModelDeclaration) {
+        self.doc(0, comment_doc(model.doc.as_deref(), &model.decorators));
+        self.outer_decorators(&model.decorators);
+        let header = format!(
+            "model {}{}",
+            model.name.name,
+            type_parameters(&model.type_parameters)
+        );
+        let fields = self.fields(&model.fields);
+        self.body(&header, &model.decorators, fields);
+    }
+
+    fn r#enum(&mut self, r#enum: &EnumDeclaration) {
+        self.doc(0, comment_doc(r#enum.doc.as_deref(), &r#enum.decorators));
+        self.outer_decorators(&r#enum.decorators);
+        let mut header = format!("enum {}", r#enum.name.name);
+        if let Some(backing) = &r#enum.backing {
+            header.push_str(&format!(": {}", backing));
+        }
+        let members = r#enum.members.iter().map(|member| {
+            let mut text = decorators_prefix(&member.decorators);
+            text.push_str(&member.name.name);
+            if let Some(value) = &member.value {
+                text.push_str(&format!(" = {}", literal(value)));
+            }
+            vec![text]
+        });
+        self.body(&header, &r#enum.decorators, members.collect());
+    }
+
+    /// Returns the lines of each field, its doc comments followed by the
+    /// field itself.
+    fn fields(&self, fields: &[FieldDeclaration]) -> Vec<Vec<String>> {
+        let prefixes: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "{}{}:",
+                    decorators_prefix(&field.decorators),
+                    field.name.name
+                )
+            })
+            .collect();
+        let width = match self.options.align_field_types {
+            true => prefixes.iter().map(|p| p.chars().count()).max(),
+            false => None,
+        };
+        fields
+            .iter()
+            .zip(prefixes)
+            .map(|(field, prefix)| {
+                let mut lines: Vec<String> = field
+                    .doc
+                    .as_deref()
+                    .into_iter()
+                    .flat_map(str::lines)
+                    .map(|line| format!("/// {}", line).trim_end().to_owned())
+                    .collect();
+                let padding = width.unwrap_or(0).saturating_sub(prefix.chars().count());
+                let mut text = format!("{}{} {}", prefix, " ".repeat(padding), field.type_expr);
+                if let Some(default) = &field.default {
+                    text.push_str(&format!(" = {}", expr(default)));
+                }
+                lines.push(text);
+                lines
+            })
+            .collect()
+    }
+}
+
+/// Returns the part of `doc` written as `///` comments, which comes before
+/// the documentation of the `@@doc` block decorators.
+fn comment_doc<'a>(doc: Option<&'a str>, decorators: &[Decorator]) -> Option<&'a str> {
+    let block: Vec<&str> = decorators
+        .iter()
+        .filter(|d| d.block && d.name() == "doc")
+        .filter_map(|d| match d.arguments.as_deref() {
+            Some(
+                [Argument {
+                    name: None,
+                    value:
+                        Expr::Literal(Literal {
+                            value: LiteralValue::String(doc),
+                            ..
+                        }),
+                    ..
+                }],
+            ) => Some(doc.as_str()),
+            _ => None,
+        })
+        .collect();
+    let doc = doc?;
+    if block.is_empty() {
+        return Some(doc);
+    }
+    let comments = doc.strip_suffix(block.join("\n").as_str())?;
+    comments.strip_suffix('\n')
+}
+
+fn function_text(function: &FunctionDeclaration) -> String {
+    let mut text = String::new();
+    if function.declare {
+        text.push_str("declare ");
+    }
+    if function.native {
+        text.push_str("native ");
+    }
+    let parameters: Vec<String> = function
+        .parameters
+        .iter()
+        .map(|parameter| {
+            let mut text = format!("{}: {}", parameter.name.name, parameter.type_expr);
+            if let Some(default) = &parameter.default {
+                text.push_str(&format!(" = {}", expr(default)));
+            }
+            text
+        })
+        .collect();
+    text.push_str(&format!(
+        "function {}{}({})",
+        function.name.name,
+        type_parameters(&function.type_parameters),
+        parameters.join(", ")
+    ));
+    if let Some(return_type) = &function.return_type {
+        text.push_str(&format!(": {}", return_type));
+    }
+    if let Some(body) = &function.body {
+        text.push_str(&format!(" {{ {} }}", expr(body)));
+    }
+    text
+}
+
+/// Returns `<A, B>` followed by a `where` clause for the bounded
+/// parameters, or nothing if there are no parameters.
+fn type_parameters(parameters: &[TypeParam]) -> String {
+    if parameters.is_empty() {
+        return String::new();
+    }
+    let names: Vec<&str> = parameters.iter().map(|p| p.name.name.as_str()).collect();
+    let bounds: Vec<String> = parameters
+        .iter()
+        .filter_map(|p| Some(format!("{}: {}", p.name.name, p.bound.as_ref()?)))
+        .collect();
+    match bounds.is_empty() {
+        true => format!("<{}>", names.join(", ")),
+        false => format!("<{}> where {}", names.join(", "), bounds.join(", ")),
+    }
+}
+
+fn config_item(item: &ConfigItem, separator: &str) -> String {
+    format!("{}{}{}", item.key.name, separator, expr(&item.value))
+}
+
+fn names(identifiers: &[Identifier]) -> String {
+    let names: Vec<&str> = identifiers.iter().map(|i| i.name.as_str()).collect();
+    names.join(", ")
+}
+
+/// Returns the decorators followed by a space each, to write before a
+/// member on the same line.
+fn decorators_prefix(decorators: &[Decorator]) -> String {
+    decorators
+        .iter()
+        .map(|decorator| format!("{} ", decorator_text(decorator)))
+        .collect()
+}
+
+fn decorator_text(decorator: &Decorator) -> String {
+    let at = match decorator.block {
+        true => "@@",
+        false => "@",
+    };
+    match &decorator.arguments {
+        Some(arguments) => format!("{}{}({})", at, decorator.name(), arguments_text(arguments)),
+        None => format!("{}{}", at, decorator.name()),
+    }
+}
+
+fn arguments_text(arguments: &[Argument]) -> String {
+    let arguments: Vec<String> = arguments
+        .iter()
+        .map(|argument| match &argument.name {
+            Some(name) => format!("{}: {}", name.name, expr(&argument.value)),
+            None => expr(&argument.value),
+        })
+        .collect();
+    arguments.join(", ")
+}
+
+fn literal(literal: &Literal) -> String {
+    match &literal.value {
+        LiteralValue::String(value) => string_literal(value),
+        LiteralValue::Number(number) => number.clone(),
+        LiteralValue::Regex(pattern) => format!("/{}/", pattern),
+    }
+}
+
+/// Returns `value` as a string literal, with the characters that need it
+/// escaped.
+fn string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    push_escaped(&mut literal, value);
+    literal.push('"');
+    literal
+}
+
+/// Append `value` to `literal` with the escapes a string literal needs,
+/// including a `\$` for every `${` so that it isn't read as an
+/// interpolation.
+fn push_escaped(literal: &mut String, value: &str) {
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            '\r' => literal.push_str("\\r"),
+            '\0' => literal.push_str("\\0"),
+            '$' if chars.peek() == Some(&'{') => literal.push_str("\\$"),
+            c => literal.push(c),
+        }
+    }
+}
+
+fn expr(expr: &Expr) -> String {
+    match expr {
+        Expr::Literal(value) => literal(value),
+        Expr::Identifier(identifier) => identifier.name.clone(),
+        Expr::Array { elements, .. } => format!("[{}]", exprs(elements)),
+        Expr::Tuple { elements, .. } if elements.len() == 1 => {
+            format!("({},)", self::expr(&elements[0]))
+        }
+        Expr::Tuple { elements, .. } => format!("({})", exprs(elements)),
+        Expr::Dict { entries, .. } if entries.is_empty() => "{}".to_owned(),
+        Expr::Dict { entries, .. } => {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|entry| {
+                    let key = match entry.quoted {
+                        true => string_literal(&entry.key.name),
+                        false => entry.key.name.clone(),
+                    };
+                    format!("{}: {}", key, self::expr(&entry.value))
+                })
+                .collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        Expr::Unary {
+            operator, operand, ..
+        } => {
+            let operator = match operator {
+                UnaryOperator::Not => "!",
+                UnaryOperator::Negate => "-",
+            };
+            format!("{}{}", operator, operand_text(operand, 9))
+        }
+        Expr::Binary {
+            operator, lhs, rhs, ..
+        } => {
+            let precedence = operator.precedence();
+            format!(
+                "{} {} {}",
+                operand_text(lhs, precedence),
+                binary_operator(*operator),
+                operand_text(rhs, precedence + 1)
+            )
+        }
+        Expr::Member {
+            object, property, ..
+        } => format!("{}.{}", operand_text(object, 10), property.name),
+        Expr::OptionalMember {
+            object, property, ..
+        } => format!("{}?.{}", operand_text(object, 10), property.name),
+        Expr::Index { object, index, .. } => {
+            format!("{}[{}]", operand_text(object, 10), self::expr(index))
+        }
+        Expr::Call {
+            callee, arguments, ..
+        } => format!(
+            "{}({})",
+            operand_text(callee, 10),
+            arguments_text(arguments)
+        ),
+        Expr::InterpolatedString { parts, .. } => {
+            let mut literal = String::from('"');
+            for part in parts {
+                match part {
+                    StringPart::Text(text) => push_escaped(&mut literal, text),
+                    StringPart::Expr(embedded) => {
+                        literal.push_str(&format!("${{{}}}", self::expr(embedded)))
+                    }
+                }
+            }
+            literal.push('"');
+            literal
+        }
+    }
+}
+
+fn exprs(exprs: &[Expr]) -> String {
+    let exprs: Vec<String> = exprs.iter().map(expr).collect();
+    exprs.join(", ")
+}
+
+/// Returns `operand` in parentheses if it binds less tightly than
+/// `min_precedence`. Binary operators have their own precedence, unary
+/// operators bind tighter than all of them and postfix operators tighter
+/// still.
+fn operand_text(operand: &Expr, min_precedence: u8) -> String {
+    let precedence = match operand {
+        Expr::Binary { operator, .. } => operator.precedence(),
+        Expr::Unary { .. } => 9,
+        _ => 10,
+    };
+    match precedence < min_precedence {
+        true => format!("({})", expr(operand)),
+        false => expr(operand),
+    }
+}
+
+fn binary_operator(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Or => "||",
+        BinaryOperator::And => "&&",
+        BinaryOperator::Equal => "==",
+        BinaryOperator::NotEqual => "!=",
+        BinaryOperator::BitOr => "|",
+        BinaryOperator::BitXor => "^",
+        BinaryOperator::BitAnd => "&",
+        BinaryOperator::Add => "+",
+        BinaryOperator::Subtract => "-",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Modulo => "%",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    const USER: &str = r#"
+/// A registered user.
+@map("users")
+model User {
+  @id   @default(1)  id : Int
+    /// The address mail is sent to.
+  @unique email:String
+  name: String?    = "anonymous"
+  roles: Role[]
+}
+"#;
+
+    fn formatted(source: &str, options: &FormatOptions) -> String {
+        let result = parse_schema(source, "schema.teo");
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        format_with_options(&result.schema, options)
+    }
+
+    #[test]
+    fn formats_the_user_model() {
+        let once = formatted(USER, &FormatOptions::default());
+        assert_eq!(
+            once,
+            r#"/// A registered user.
+@map("users")
+model User {
+    @id @default(1) id: Int
+    /// The address mail is sent to.
+    @unique email: String
+    name: String? = "anonymous"
+    roles: Role[]
+}
+"#
+        );
+        assert_eq!(formatted(&once, &FormatOptions::default()), once);
+    }
+
+    #[test]
+    fn aligns_field_types() {
+        let options = FormatOptions {
+            indent: 2,
+            align_field_types: true,
+        };
+        let once = formatted(
+            "model User {\n id: Int\n @unique email: String\n}",
+            &options,
+        );
+        assert_eq!(
+            once,
+            "model User {\n  id:            Int\n  @unique email: String\n}\n"
+        );
+        assert_eq!(formatted(&once, &options), once);
+    }
+
+    #[test]
+    fn keeps_quoted_dictionary_keys() {
+        let source = "config client { headers: {\"content-type\":\"json\",accept:\"*\"} }";
+        let once = formatted(source, &FormatOptions::default());
+        assert_eq!(
+            once,
+            "config client {\n    headers: { \"content-type\": \"json\", accept: \"*\" }\n}\n"
+        );
+        assert_eq!(formatted(&once, &FormatOptions::default()), once);
+    }
+
+    #[test]
+    fn keeps_the_name_of_a_config() {
+        let once = formatted(
+            "config  client   Web{provider:\"ts\"}",
+            &FormatOptions::default(),
+        );
+        assert_eq!(once, "config client Web {\n    provider: \"ts\"\n}\n");
+        assert_eq!(formatted(&once, &FormatOptions::default()), once);
+    }
+
+    #[test]
+    fn keeps_interpolated_strings() {
+        let source = "config client { greeting: \"hi ${ user.name }\\n\", price: \"\\${x}\" }";
+        let once = formatted(source, &FormatOptions::default());
+        assert_eq!(
+            once,
+            "config client {\n    greeting: \"hi ${user.name}\\n\"\n    price: \"\\${x}\"\n}\n"
+        );
+        assert_eq!(formatted(&once, &FormatOptions::default()), once);
+    }
+
+    #[test]
+    fn indents_the_body_of_a_namespace() {
+        let source = "namespace blog { model Post { id: Int } namespace drafts { enum Status { DRAFT } } namespace empty {} }";
+        let once = formatted(source, &FormatOptions::default());
+        assert_eq!(
+            once,
+            "namespace blog {\n    model Post {\n        id: Int\n    }\n\n    namespace drafts {\n        enum Status {\n            DRAFT\n        }\n    }\n\n    namespace empty {}\n}\n"
+        );
+        assert_eq!(formatted(&once, &FormatOptions::default()), once);
+    }
+
+    #[test]
+    fn normalizes_expressions() {
+        let source = "config server { bind: (\"0.0.0.0\",5000), limit: (1+2)*-x.y [0] , user: a ?. b?.c , retry: 1-(2-3) }\nhandler signIn {identity.signIn( credentials , name:\"a\\\"b\" )}";
+        let once = formatted(source, &FormatOptions::default());
+        assert_eq!(
+            once,
+            "config server {\n    bind: (\"0.0.0.0\", 5000)\n    limit: (1 + 2) * -x.y[0]\n    user: a?.b?.c\n    retry: 1 - (2 - 3)\n}\n\nhandler signIn { identity.signIn(credentials, name: \"a\\\"b\") }\n"
+        );
+        assert_eq!(formatted(&once, &FormatOptions::default()), once);
+    }
+
+    #[test]
+    fn formats_every_kind_of_declaration() {
+        let source = r#"
+import { A, B } from "./a"
+include "./b"
+export { A }
+/// Roles.
+enum Role: String {
+    @@doc("Who a user is.")
+    @map("admin") ADMIN = "admin"
+    USER = "user"
+}
+[server]
+port = 5000
+type Id = String | Int
+type Entry<K,V>=( K , V? )
+let pageSize: Int = 20
+struct Pair<A, B> where A: String { first: A, second: B }
+declare native function now(): DateTime
+@before middleware timing
+"#;
+        let once = formatted(source, &FormatOptions::default());
+        assert_eq!(
+            once,
+            r#"import { A, B } from "./a"
+
+include "./b"
+
+export { A }
+
+/// Roles.
+enum Role: String {
+    @@doc("Who a user is.")
+    @map("admin") ADMIN = "admin"
+    USER = "user"
+}
+
+[server]
+port = 5000
+
+type Id = String | Int
+
+type Entry<K, V> = (K, V?)
+
+let pageSize: Int = 20
+
+struct Pair<A, B> where A: String {
+    first: A
+    second: B
+}
+
+declare native function now(): DateTime
+
+@before
+middleware timing
+"#
+        );
+        assert_eq!(formatted(&once, &FormatOptions::default()), once);
+    }
+
+    #[test]
+    fn format_source_keeps_unknown_bodies() {
+        let source = "view  Active {\n  filter: x\n}\nmodel User { id: Int }";
+        let once = format_source(source, &FormatOptions::default()).unwrap();
+        assert_eq!(
+            once,
+            "view Active {\n  filter: x\n}\n\nmodel User {\n    id: Int\n}\n"
+        );
+        assert_eq!(format_source(&once, &FormatOptions::default()), Ok(once));
+    }
+
+    #[test]
+    fn format_source_keeps_doc_comments() {
+        let once = format_source(USER, &FormatOptions::default()).unwrap();
+        assert_eq!(once, formatted(USER, &FormatOptions::default()));
+    }
+
+    #[test]
+    fn format_source_refuses_to_lose_comments() {
+        let source = "model User {\n  id: Int // the id\n}";
+        assert_eq!(
+            format_source(source, &FormatOptions::default()),
+            Err(FormatError::Comment(23..32))
+        );
+        let source = "/// Stray.\nlet x = 1\nmodel User { id: Int }";
+        assert_eq!(
+            format_source(source, &FormatOptions::default()),
+            Err(FormatError::Comment(0..10))
+        );
+        let error = format_source("model {", &FormatOptions::default()).unwrap_err();
+        assert!(matches!(error, FormatError::Syntax(_)));
+    }
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod format;
+
+pub use format::{format, format_source, format_with_options, FormatError, FormatOptions};
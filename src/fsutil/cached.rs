@@ -0,0 +1,137 @@
+use super::{FSUtil, FsError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct Cache {
+    contents: HashMap<String, Result<String, FsError>>,
+    exists: HashMap<String, bool>,
+}
+
+impl FSUtil {
+    /// Create a utility that remembers the answers of `inner` to
+    /// `read_file` and `file_exists`, so that a parse session reads each
+    /// file once.
+    ///
+    /// Writing a file through the returned utility forgets what was cached
+    /// about it. Changes made behind its back are seen only after
+    /// [`FSUtil::clear_cache`].
+    pub fn cached(inner: FSUtil) -> Self {
+        let cache = Arc::new(Mutex::new(Cache::default()));
+        let check_case = inner.check_case;
+        let (read, read_cache) = (inner.clone(), cache.clone());
+        let (exists, exists_cache) = (inner.clone(), cache.clone());
+        let (write, write_cache) = (inner.clone(), cache.clone());
+        let (bytes, is_directory, join, parent, absolute, read_dir) = (
+            inner.clone(),
+            inner.clone(),
+            inner.clone(),
+            inner.clone(),
+            inner.clone(),
+            inner.clone(),
+        );
+        let (canonicalize, extension, stem) = (inner.clone(), inner.clone(), inner);
+        let try_read_file = Arc::new(move |path: &str| {
+            if let Some(content) = read_cache.lock().unwrap().contents.get(path) {
+                return content.clone();
+            }
+            let content = read.try_read_file(path);
+            read_cache
+                .lock()
+                .unwrap()
+                .contents
+                .insert(path.to_owned(), content.clone());
+            content
+        });
+        let read_file = try_read_file.clone();
+        let mut fs_util = Self::new(
+            move |path| read_file(path).ok(),
+            move |path| {
+                if let Some(exists) = exists_cache.lock().unwrap().exists.get(path) {
+                    return *exists;
+                }
+                let file_exists = exists.file_exists(path);
+                exists_cache
+                    .lock()
+                    .unwrap()
+                    .exists
+                    .insert(path.to_owned(), file_exists);
+                file_exists
+            },
+            move |path| is_directory.file_is_directory(path),
+            move |base, path| join.path_join(base, path),
+            move |path| parent.parent_directory(path),
+            move |path| absolute.path_is_absolute(path),
+            move |path, contents| {
+                let mut cache = write_cache.lock().unwrap();
+                cache.contents.remove(path);
+                cache.exists.remove(path);
+                write.write_file(path, contents)
+            },
+            move |path| read_dir.read_dir(path),
+        )
+        .with_read_file_bytes(move |path| bytes.read_file_bytes(path))
+        .with_try_read_file(move |path| try_read_file(path))
+        .with_canonicalize(move |path| canonicalize.canonicalize(path))
+        .with_path_extension(move |path| extension.path_extension(path))
+        .with_file_stem(move |path| stem.file_stem(path));
+        fs_util.check_case = check_case;
+        fs_util.clear_cache = Arc::new(move || {
+            let mut cache = cache.lock().unwrap();
+            cache.contents.clear();
+            cache.exists.clear();
+        });
+        fs_util
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting(reads: Arc<AtomicUsize>) -> FSUtil {
+        let files = FSUtil::in_memory(HashMap::from([(
+            "schema.teo".to_owned(),
+            "model User { id: Int }".to_owned(),
+        )]));
+        let inner = files.clone();
+        files.with_read_file(move |path| {
+            reads.fetch_add(1, Ordering::SeqCst);
+            inner.read_file(path)
+        })
+    }
+
+    #[test]
+    fn reads_each_file_once() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let fs_util = FSUtil::cached(counting(reads.clone()));
+        for _ in 0..3 {
+            assert!(fs_util.read_file("schema.teo").is_some());
+            assert_eq!(fs_util.read_file("missing.teo"), None);
+        }
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn clear_cache_reads_again() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let fs_util = FSUtil::cached(counting(reads.clone()));
+        fs_util.read_file("schema.teo");
+        fs_util.clear_cache();
+        fs_util.read_file("schema.teo");
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn writes_forget_the_written_file() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let fs_util = FSUtil::cached(counting(reads.clone()));
+        assert!(!fs_util.file_exists("out.rs"));
+        assert_eq!(fs_util.read_file("out.rs"), None);
+        fs_util.write_file("out.rs", "struct User;").unwrap();
+        assert!(fs_util.file_exists("out.rs"));
+        assert_eq!(fs_util.read_file("out.rs").as_deref(), Some("struct User;"));
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+}
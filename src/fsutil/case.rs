@@ -0,0 +1,116 @@
+use super::FSUtil;
+
+impl FSUtil {
+    /// Returns the utility with case checking enabled: import resolution
+    /// warns when an import names a file in a case other than the one it is
+    /// stored in, see [`FSUtil::case_mismatch`]. The imports work on a
+    /// case-insensitive file system, like the defaults of macOS and
+    /// Windows, but break on a case-sensitive one.
+    pub fn with_case_check(mut self) -> Self {
+        self.check_case = true;
+        self
+    }
+
+    /// Returns true if import resolution checks the case of imported paths.
+    pub fn checks_case(&self) -> bool {
+        self.check_case
+    }
+
+    /// Returns `path` with the name of every entry in the case it is stored
+    /// in, found by listing the directories along the path and comparing
+    /// names ignoring case. An exact match is preferred over one ignoring
+    /// case. None if some entry of the path has no match.
+    ///
+    /// The first entry of a relative path is taken as written when the
+    /// utility cannot list the current directory.
+    pub fn stored_case(&self, path: &str) -> Option<String> {
+        self.stored(path).map(|(stored, _)| stored)
+    }
+
+    /// Returns the path `path` is stored at if it differs from `path` in
+    /// case. None if the case matches or there is no such path.
+    pub fn case_mismatch(&self, path: &str) -> Option<String> {
+        match self.stored(path)? {
+            (stored, true) => Some(stored),
+            (_, false) => None,
+        }
+    }
+
+    /// Returns the stored path of `path` and whether its case differs.
+    fn stored(&self, path: &str) -> Option<(String, bool)> {
+        let parent = self.parent_directory(path);
+        let Some(name) = path.rsplit(['/', '\\']).find(|name| !name.is_empty()) else {
+            return Some((path.to_owned(), false));
+        };
+        if parent == path || name == "." || name == ".." {
+            return Some((path.to_owned(), false));
+        }
+        let (stored_parent, parent_differs) = match parent.is_empty() {
+            true => (parent, false),
+            false => self.stored(&parent)?,
+        };
+        let Some(entries) = self.read_dir(&stored_parent) else {
+            return match stored_parent.is_empty() {
+                true => Some((path.to_owned(), false)),
+                false => None,
+            };
+        };
+        let (entry, differs) = match entries.iter().find(|entry| *entry == name) {
+            Some(entry) => (entry, parent_differs),
+            None => {
+                let lowercase = name.to_lowercase();
+                let entry = entries
+                    .iter()
+                    .find(|entry| entry.to_lowercase() == lowercase)?;
+                (entry, true)
+            }
+        };
+        let stored = match stored_parent.is_empty() {
+            true => entry.to_owned(),
+            false => self.path_join(&stored_parent, entry),
+        };
+        Some((stored, differs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn fs() -> FSUtil {
+        FSUtil::in_memory(HashMap::from([
+            ("schema/models/user.teo".to_owned(), "".to_owned()),
+            ("schema/Post.teo".to_owned(), "".to_owned()),
+        ]))
+    }
+
+    #[test]
+    fn finds_the_stored_case_of_each_entry() {
+        assert_eq!(
+            fs().stored_case("schema/Models/User.teo").as_deref(),
+            Some("schema/models/user.teo")
+        );
+        assert_eq!(
+            fs().stored_case("Schema/post.teo").as_deref(),
+            Some("schema/Post.teo")
+        );
+        assert_eq!(fs().stored_case("schema/comment.teo"), None);
+    }
+
+    #[test]
+    fn reports_only_paths_whose_case_differs() {
+        assert_eq!(fs().case_mismatch("schema/models/user.teo"), None);
+        assert_eq!(
+            fs().case_mismatch("schema/MODELS/user.teo").as_deref(),
+            Some("schema/models/user.teo")
+        );
+        assert_eq!(fs().case_mismatch("schema/missing.teo"), None);
+    }
+
+    #[test]
+    fn case_check_is_off_by_default() {
+        assert!(!fs().checks_case());
+        assert!(fs().with_case_check().checks_case());
+    }
+}
@@ -0,0 +1,402 @@
+use super::{FSUtil, FsError};
+use std::sync::Arc;
+
+/// # Chained file system utility
+///
+/// A list of file system utilities tried in order, for example an in-memory
+/// overlay, then a cache, then the real disk. For reads and existence
+/// checks the first backend that has an answer wins: `read_file` returns
+/// the first `Some`, `file_exists` and `file_is_directory` return true as
+/// soon as any backend does. Directory listings of all backends are merged.
+/// Path manipulation doesn't touch the file system, so it is always done by
+/// the first backend. Writes also go to the first backend only. Convert it
+/// into an [`FSUtil`] to resolve imports through it.
+#[derive(Clone)]
+pub struct ChainedFSUtil {
+    /// The backends in the order they are tried.
+    backends: Vec<FSUtil>,
+}
+
+impl ChainedFSUtil {
+    /// Create a chain trying `primary` first and `fallback` second.
+    pub fn new(primary: FSUtil, fallback: FSUtil) -> Self {
+        Self {
+            backends: vec![primary, fallback],
+        }
+    }
+
+    /// Append `fallback` to the end of the chain.
+    pub fn or(mut self, fallback: FSUtil) -> Self {
+        self.backends.push(fallback);
+        self
+    }
+
+    /// Returns the backends in the order they are tried.
+    pub fn backends(&self) -> &[FSUtil] {
+        &self.backends
+    }
+
+    /// Read the file content from `path` using the first backend that can.
+    pub fn read_file(&self, path: &str) -> Option<String> {
+        self.backends
+            .iter()
+            .find_map(|backend| backend.read_file(path))
+    }
+
+    /// Read the file content from `path` using the first backend that can.
+    /// If none can, the first reason other than the file not being found.
+    pub fn try_read_file(&self, path: &str) -> Result<String, FsError> {
+        let mut failure = FsError::NotFound;
+        for backend in &self.backends {
+            match backend.try_read_file(path) {
+                Ok(content) => return Ok(content),
+                Err(FsError::NotFound) => (),
+                Err(error) if failure == FsError::NotFound => failure = error,
+                Err(_) => (),
+            }
+        }
+        Err(failure)
+    }
+
+    /// Read the file content from `path` as bytes using the first backend
+    /// that can.
+    pub fn read_file_bytes(&self, path: &str) -> Option<Vec<u8>> {
+        self.backends
+            .iter()
+            .find_map(|backend| backend.read_file_bytes(path))
+    }
+
+    /// Returns true if any backend has a file at `path`.
+    pub fn file_exists(&self, path: &str) -> bool {
+        self.backends
+            .iter()
+            .any(|backend| backend.file_exists(path))
+    }
+
+    /// Returns true if any backend has a directory at `path`.
+    pub fn file_is_directory(&self, path: &str) -> bool {
+        self.backends
+            .iter()
+            .any(|backend| backend.file_is_directory(path))
+    }
+
+    /// Returns a joined path of `base` and `path`.
+    pub fn path_join(&self, base: &str, path: &str) -> String {
+        self.backends[0].path_join(base, path)
+    }
+
+    /// Returns the parent directory of `path`.
+    pub fn parent_directory(&self, path: &str) -> String {
+        self.backends[0].parent_directory(path)
+    }
+
+    /// Returns true if `path` is an absolute path.
+    pub fn path_is_absolute(&self, path: &str) -> bool {
+        self.backends[0].path_is_absolute(path)
+    }
+
+    /// Returns the extension of the file name of `path` using the first
+    /// backend.
+    pub fn path_extension(&self, path: &str) -> Option<String> {
+        self.backends[0].path_extension(path)
+    }
+
+    /// Returns the file name of `path` without its extension using the
+    /// first backend.
+    pub fn file_stem(&self, path: &str) -> String {
+        self.backends[0].file_stem(path)
+    }
+
+    /// Returns the entry names of the directory at `path` in every backend
+    /// that has it, merged and sorted. None if no backend has it.
+    pub fn read_dir(&self, path: &str) -> Option<Vec<String>> {
+        let mut found = false;
+        let mut names = vec![];
+        for entries in self.backends.iter().filter_map(|b| b.read_dir(path)) {
+            found = true;
+            names.extend(entries);
+        }
+        names.sort();
+        names.dedup();
+        found.then_some(names)
+    }
+
+    /// Write `contents` into the file at `path` using the first backend.
+    pub fn write_file(&self, path: &str, contents: &str) -> Result<(), String> {
+        self.backends[0].write_file(path, contents)
+    }
+
+    /// Get the parent directory of `source_path` and join it with `path`.
+    pub fn import_path(&self, source_path: &str, path: &str) -> String {
+        self.backends[0].import_path(source_path, path)
+    }
+
+    /// Returns the canonical path of `path` from the first backend that has
+    /// a file there and can resolve it, or else from the first backend
+    /// that can resolve it at all.
+    pub fn canonicalize(&self, path: &str) -> Option<String> {
+        self.backends
+            .iter()
+            .filter(|backend| backend.file_exists(path))
+            .chain(&self.backends)
+            .find_map(|backend| backend.canonicalize(path))
+    }
+
+    /// Forget the cached answers of every backend.
+    pub fn clear_cache(&self) {
+        for backend in &self.backends {
+            backend.clear_cache();
+        }
+    }
+}
+
+impl From<ChainedFSUtil> for FSUtil {
+    /// Answers every query the way the chain does. Checks case if the first
+    /// backend does.
+    fn from(chain: ChainedFSUtil) -> Self {
+        let chain = Arc::new(chain);
+        let check_case = chain.backends[0].checks_case();
+        let (read, exists, directory, join) =
+            (chain.clone(), chain.clone(), chain.clone(), chain.clone());
+        let (parent, absolute, write, read_dir) =
+            (chain.clone(), chain.clone(), chain.clone(), chain.clone());
+        let (bytes, try_read, extension, stem) =
+            (chain.clone(), chain.clone(), chain.clone(), chain.clone());
+        let (canonicalize, clear) = (chain.clone(), chain);
+        let mut fs = FSUtil::new(
+            move |path| read.read_file(path),
+            move |path| exists.file_exists(path),
+            move |path| directory.file_is_directory(path),
+            move |base, path| join.path_join(base, path),
+            move |path| parent.parent_directory(path),
+            move |path| absolute.path_is_absolute(path),
+            move |path, contents| write.write_file(path, contents),
+            move |path| read_dir.read_dir(path),
+        )
+        .with_read_file_bytes(move |path| bytes.read_file_bytes(path))
+        .with_try_read_file(move |path| try_read.try_read_file(path))
+        .with_path_extension(move |path| extension.path_extension(path))
+        .with_file_stem(move |path| stem.file_stem(path))
+        .with_canonicalize(move |path| canonicalize.canonicalize(path));
+        fs.clear_cache = Arc::new(move || clear.clear_cache());
+        fs.check_case = check_case;
+        fs
+    }
+}
+
+impl FSUtil {
+    /// Chain this utility with `fallback`, which is tried when this one has
+    /// no answer, like a [`ChainedFSUtil`] of the two.
+    pub fn or(self, fallback: FSUtil) -> FSUtil {
+        ChainedFSUtil::new(self, fallback).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn overlay_read_file(path: &str) -> Option<String> {
+        match path {
+            "src/lib.rs" => Some("overlay lib".to_owned()),
+            "overlay.teo" => Some("overlay only".to_owned()),
+            _ => None,
+        }
+    }
+
+    fn overlay_file_exists(path: &str) -> bool {
+        overlay_read_file(path).is_some()
+    }
+
+    fn overlay_file_is_directory(_path: &str) -> bool {
+        false
+    }
+
+    fn overlay_path_join(base: &str, path: &str) -> String {
+        format!("{}/{}", base, path)
+    }
+
+    fn overlay_parent_directory(_path: &str) -> String {
+        "overlay".to_owned()
+    }
+
+    fn overlay_path_is_absolute(path: &str) -> bool {
+        path.starts_with('/')
+    }
+
+    fn overlay_write_file(_path: &str, _contents: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn overlay_read_dir(path: &str) -> Option<Vec<String>> {
+        match path {
+            "schema" => Some(vec!["b.teo".to_owned(), "a.teo".to_owned()]),
+            _ => None,
+        }
+    }
+
+    fn overlay() -> FSUtil {
+        FSUtil::new(
+            overlay_read_file,
+            overlay_file_exists,
+            overlay_file_is_directory,
+            overlay_path_join,
+            overlay_parent_directory,
+            overlay_path_is_absolute,
+            overlay_write_file,
+            overlay_read_dir,
+        )
+    }
+
+    fn empty_read_file(_path: &str) -> Option<String> {
+        None
+    }
+
+    fn empty_file_exists(_path: &str) -> bool {
+        false
+    }
+
+    fn empty_write_file(_path: &str, _contents: &str) -> Result<(), String> {
+        Err("read-only".to_owned())
+    }
+
+    fn empty_read_dir(_path: &str) -> Option<Vec<String>> {
+        None
+    }
+
+    fn empty() -> FSUtil {
+        FSUtil::new(
+            empty_read_file,
+            empty_file_exists,
+            empty_file_exists,
+            overlay_path_join,
+            overlay_parent_directory,
+            overlay_path_is_absolute,
+            empty_write_file,
+            empty_read_dir,
+        )
+    }
+
+    #[test]
+    fn first_backend_with_an_answer_wins() {
+        let chained = ChainedFSUtil::new(empty(), overlay()).or(empty());
+        assert_eq!(chained.backends().len(), 3);
+        assert_eq!(
+            chained.read_file("overlay.teo"),
+            Some("overlay only".to_owned())
+        );
+        assert!(chained.file_exists("overlay.teo"));
+        assert_eq!(chained.read_file("missing.teo"), None);
+        assert!(!chained.file_exists("missing.teo"));
+    }
+
+    #[test]
+    fn try_read_file_keeps_the_first_failure_that_is_not_not_found() {
+        let denied = empty().with_try_read_file(|_| Err(FsError::PermissionDenied));
+        let chained = empty().or(denied.clone()).or(overlay());
+        assert_eq!(
+            chained.try_read_file("overlay.teo"),
+            Ok("overlay only".to_owned())
+        );
+        assert_eq!(
+            chained.try_read_file("missing.teo"),
+            Err(FsError::PermissionDenied)
+        );
+        assert_eq!(
+            empty().or(overlay()).try_read_file("missing.teo"),
+            Err(FsError::NotFound)
+        );
+    }
+
+    #[test]
+    fn directory_listings_are_merged() {
+        let chained = overlay().or(empty()).or(overlay());
+        assert_eq!(
+            chained.read_dir("schema"),
+            Some(vec!["a.teo".to_owned(), "b.teo".to_owned()])
+        );
+        assert_eq!(chained.read_dir("nowhere"), None);
+    }
+
+    #[test]
+    fn writes_go_to_the_first_backend() {
+        assert_eq!(overlay().or(empty()).write_file("out.rs", ""), Ok(()));
+        assert_eq!(
+            empty().or(overlay()).write_file("out.rs", ""),
+            Err("read-only".to_owned())
+        );
+    }
+
+    #[test]
+    fn path_manipulation_uses_the_first_backend() {
+        let chained = overlay().or(empty());
+        assert_eq!(chained.import_path("a/b.teo", "c.teo"), "overlay/c.teo");
+    }
+
+    #[cfg(feature = "stdfs")]
+    #[test]
+    fn overlay_shadows_the_disk() {
+        let chained = overlay().or(FSUtil::default());
+        assert_eq!(
+            chained.read_file("src/lib.rs"),
+            Some("overlay lib".to_owned())
+        );
+        assert_eq!(
+            chained.read_file("Cargo.toml"),
+            std::fs::read_to_string("Cargo.toml").ok()
+        );
+        assert_eq!(
+            chained.read_file("overlay.teo"),
+            Some("overlay only".to_owned())
+        );
+        assert!(chained.file_is_directory("src"));
+    }
+
+    #[test]
+    fn a_chain_converts_into_a_utility() {
+        let fs = empty().or(overlay()).or(empty());
+        assert_eq!(fs.read_file("overlay.teo"), Some("overlay only".to_owned()));
+        assert_eq!(
+            fs.read_text("overlay.teo"),
+            Some(Ok("overlay only".to_owned()))
+        );
+        assert_eq!(
+            fs.read_file_bytes("overlay.teo"),
+            Some(b"overlay only".to_vec())
+        );
+        let memory = FSUtil::in_memory(HashMap::from([(
+            "schema/main.teo".to_owned(),
+            "".to_owned(),
+        )]));
+        let fs = empty().or(memory);
+        assert_eq!(
+            fs.canonicalize("schema/x/../main.teo").as_deref(),
+            Some("schema/main.teo")
+        );
+        assert_eq!(fs.path_key("schema/x/../main.teo"), "schema/main.teo");
+    }
+
+    #[cfg(feature = "stdfs")]
+    #[test]
+    fn resolves_imports_through_an_overlay_over_the_disk() {
+        use crate::resolve::resolve_imports;
+
+        let directory = std::env::temp_dir().join(format!("teo-chained-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("user.teo"), "model User { id: Int }").unwrap();
+        let directory = directory.to_str().unwrap().replace('\\', "/");
+        let main = format!("{}/main.teo", directory);
+        let overlay = FSUtil::in_memory(HashMap::from([(
+            main.clone(),
+            "import { User } from \"./user.teo\"\nmodel Post { author: User }".to_owned(),
+        )]));
+        let graph = resolve_imports(&main, &overlay.or(FSUtil::default()));
+        std::fs::remove_dir_all(&directory).unwrap();
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(paths.len(), 2, "{:?}", paths);
+        assert!(paths.iter().any(|path| path.ends_with("/user.teo")));
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+        assert_eq!(graph.imports[&graph.entry].len(), 1);
+    }
+}
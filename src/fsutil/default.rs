@@ -1,13 +1,18 @@
-use super::FSUtil;
+use super::{FSUtil, FsError};
 use path_clean::PathClean;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 fn read_file(file_path: &str) -> Option<String> {
-    match fs::read_to_string(Path::new(file_path)) {
-        Ok(s) => Some(s),
-        Err(_) => None,
-    }
+    fs::read_to_string(Path::new(file_path)).ok()
+}
+
+fn try_read_file(file_path: &str) -> Result<String, FsError> {
+    Ok(fs::read_to_string(Path::new(file_path))?)
+}
+
+fn read_file_bytes(file_path: &str) -> Option<Vec<u8>> {
+    fs::read(Path::new(file_path)).ok()
 }
 
 fn file_exists(file_path: &str) -> bool {
@@ -20,6 +25,18 @@ fn parent_directory(path: &str) -> String {
     path.to_str().unwrap().to_string()
 }
 
+fn path_extension(path: &str) -> Option<String> {
+    let extension = Path::new(path).extension()?.to_str()?;
+    (!extension.is_empty()).then(|| extension.to_owned())
+}
+
+fn file_stem(path: &str) -> String {
+    match Path::new(path).file_stem() {
+        Some(stem) => stem.to_str().unwrap().to_string(),
+        None => String::new(),
+    }
+}
+
 fn path_is_absolute(path: &str) -> bool {
     Path::new(path).is_absolute()
 }
@@ -37,6 +54,44 @@ fn file_is_directory(file_path: &str) -> bool {
     Path::new(file_path).is_dir()
 }
 
+fn read_dir(path: &str) -> Option<Vec<String>> {
+    let mut names = fs::read_dir(Path::new(path))
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect::<Vec<String>>();
+    names.sort();
+    Some(names)
+}
+
+fn canonicalize(file_path: &str) -> Option<String> {
+    let path = Path::new(file_path);
+    let real = match fs::canonicalize(path) {
+        Ok(real) => real,
+        // The file doesn't exist yet: resolve the deepest ancestor that does
+        // and append the rest of the path to it.
+        Err(_) => {
+            let mut existing = match path.is_absolute() {
+                true => path.to_path_buf(),
+                false => std::env::current_dir().ok()?.join(path),
+            }
+            .clean();
+            let mut missing = vec![];
+            while fs::symlink_metadata(&existing).is_err() {
+                missing.push(existing.file_name()?.to_owned());
+                existing.pop();
+            }
+            let mut real = fs::canonicalize(&existing).unwrap_or(existing);
+            real.extend(missing.iter().rev());
+            real
+        }
+    };
+    real.to_str().map(|real| real.to_owned())
+}
+
+fn write_file(file_path: &str, contents: &str) -> Result<(), String> {
+    fs::write(Path::new(file_path), contents).map_err(|error| error.to_string())
+}
+
 /// Provide a default implementation for file system utility.
 impl Default for FSUtil {
     fn default() -> Self {
@@ -47,7 +102,14 @@ impl Default for FSUtil {
             path_join,
             parent_directory,
             path_is_absolute,
+            write_file,
+            read_dir,
         )
+        .with_read_file_bytes(read_file_bytes)
+        .with_try_read_file(try_read_file)
+        .with_canonicalize(canonicalize)
+        .with_path_extension(path_extension)
+        .with_file_stem(file_stem)
     }
 }
 
@@ -70,32 +132,128 @@ mod tests {
         assert_eq!(content, None);
     }
 
+    #[test]
+    fn test_try_read_file_tells_why_a_file_cannot_be_read() {
+        assert!(try_read_file("src/fsutil/default.rs").is_ok());
+        assert_eq!(
+            try_read_file("src/fsutil/nonexistent.rs"),
+            Err(FsError::NotFound)
+        );
+        assert!(matches!(
+            try_read_file("src/fsutil"),
+            Err(FsError::Other(_))
+        ));
+        let file_path = std::env::temp_dir().join("teo-language-parser-invalid.teo");
+        fs::write(&file_path, [0xff, 0xfe]).unwrap();
+        let file_path = file_path.to_str().unwrap();
+        assert_eq!(try_read_file(file_path), Err(FsError::InvalidUtf8));
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_read_file_bytes_returns_the_content_of_a_file() {
+        let file_path = "src/fsutil/default.rs";
+        let bytes = read_file_bytes(file_path).unwrap();
+        assert_eq!(bytes, fs::read(Path::new(file_path)).unwrap());
+    }
+
+    #[test]
+    fn test_read_text_reports_a_file_that_is_not_utf8() {
+        let file_path = std::env::temp_dir().join("teo-language-parser-latin1.teo");
+        fs::write(&file_path, b"model Caf\xe9 {}").unwrap();
+        let file_path = file_path.to_str().unwrap();
+        let fs_util = FSUtil::default();
+        assert_eq!(read_file(file_path), None);
+        assert_eq!(fs_util.read_file_bytes(file_path).unwrap().len(), 13);
+        let error = fs_util.read_text(file_path).unwrap().unwrap_err();
+        assert_eq!(error.valid_up_to, 9);
+        assert_eq!(fs_util.read_text("src/fsutil/nonexistent.rs"), None);
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_canonicalize_resolves_equivalent_paths_to_the_same_result() {
+        let direct = canonicalize("src/fsutil/default.rs").unwrap();
+        assert_eq!(
+            canonicalize("src/../src/fsutil/./default.rs").unwrap(),
+            direct
+        );
+        assert!(Path::new(&direct).is_absolute());
+    }
+
+    #[test]
+    fn test_canonicalize_falls_back_for_a_file_that_does_not_exist_yet() {
+        let direct = canonicalize("src/fsutil/new/schema.teo").unwrap();
+        assert_eq!(
+            canonicalize("src/lexer/../fsutil/new/./schema.teo").unwrap(),
+            direct
+        );
+        assert_eq!(
+            direct,
+            Path::new(&canonicalize("src/fsutil").unwrap())
+                .join("new/schema.teo")
+                .to_str()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_file_writes_the_contents() {
+        let file_path = std::env::temp_dir().join("teo-language-parser-write-file.txt");
+        let file_path = file_path.to_str().unwrap();
+        assert_eq!(write_file(file_path, "written"), Ok(()));
+        assert_eq!(read_file(file_path).unwrap(), "written");
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_write_file_returns_an_error_if_the_directory_does_not_exist() {
+        let file_path = "src/fsutil/nonexistent/output.txt";
+        assert!(write_file(file_path, "written").is_err());
+    }
+
+    #[test]
+    fn test_read_dir_returns_sorted_entry_names_of_a_directory() {
+        let directory = std::env::temp_dir().join("teo-language-parser-read-dir");
+        fs::create_dir_all(directory.join("models")).unwrap();
+        fs::write(directory.join("main.teo"), "").unwrap();
+        fs::write(directory.join("enums.teo"), "").unwrap();
+        let names = read_dir(directory.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(names, vec!["enums.teo", "main.teo", "models"]);
+    }
+
+    #[test]
+    fn test_read_dir_returns_none_if_path_is_a_file() {
+        assert_eq!(read_dir("src/fsutil/default.rs"), None);
+    }
+
     #[test]
     fn test_file_exists_returns_true_if_file_exists() {
         let file_path = "src/fsutil/default.rs";
         let exists = file_exists(file_path);
-        assert_eq!(exists, true);
+        assert!(exists);
     }
 
     #[test]
     fn test_file_exists_returns_false_if_file_does_not_exist() {
         let file_path = "src/fsutil/nonexistent.rs";
         let exists = file_exists(file_path);
-        assert_eq!(exists, false);
+        assert!(!exists);
     }
 
     #[test]
     fn test_file_is_directory_returns_true_if_file_is_directory() {
         let file_path = "src/fsutil";
         let is_directory = file_is_directory(file_path);
-        assert_eq!(is_directory, true);
+        assert!(is_directory);
     }
 
     #[test]
     fn test_file_is_directory_returns_false_if_file_is_not_directory() {
         let file_path = "src/fsutil/default.rs";
         let is_directory = file_is_directory(file_path);
-        assert_eq!(is_directory, false);
+        assert!(!is_directory);
     }
 
     #[test]
@@ -105,17 +263,114 @@ mod tests {
         assert_eq!(parent, "src/fsutil");
     }
 
+    #[test]
+    fn test_path_extension_returns_extension_of_file_name() {
+        let path = "src/fsutil/default.rs";
+        let extension = path_extension(path);
+        assert_eq!(extension, Some("rs".to_owned()));
+    }
+
+    #[test]
+    fn test_path_extension_returns_none_for_hidden_file() {
+        let path = "src/.gitignore";
+        let extension = path_extension(path);
+        assert_eq!(extension, None);
+    }
+
+    #[test]
+    fn test_path_extension_returns_none_if_file_name_has_no_extension() {
+        let path = "src.d/Makefile";
+        let extension = path_extension(path);
+        assert_eq!(extension, None);
+    }
+
+    #[test]
+    fn test_path_extension_returns_last_extension_of_file_name_with_multiple_dots() {
+        let path = "schema/a.b.teo";
+        let extension = path_extension(path);
+        assert_eq!(extension, Some("teo".to_owned()));
+    }
+
+    #[test]
+    fn test_file_stem_returns_file_name_without_extension() {
+        let path = "src/user.teo";
+        let stem = file_stem(path);
+        assert_eq!(stem, "user");
+    }
+
+    #[test]
+    fn test_file_stem_returns_file_name_if_path_is_single_component() {
+        let path = "user.teo";
+        let stem = file_stem(path);
+        assert_eq!(stem, "user");
+    }
+
+    #[test]
+    fn test_file_stem_returns_name_if_path_has_no_extension() {
+        let path = "src/fsutil";
+        let stem = file_stem(path);
+        assert_eq!(stem, "fsutil");
+    }
+
+    #[test]
+    fn test_file_stem_returns_name_of_path_with_trailing_slash() {
+        let path = "src/fsutil/";
+        let stem = file_stem(path);
+        assert_eq!(stem, "fsutil");
+    }
+
+    #[test]
+    fn test_file_stem_returns_name_of_path_with_multiple_trailing_slashes() {
+        let path = "src/user.teo///";
+        let stem = file_stem(path);
+        assert_eq!(stem, "user");
+    }
+
+    #[test]
+    fn test_file_stem_returns_name_of_absolute_path() {
+        let path = "/src/fsutil/user.teo";
+        let stem = file_stem(path);
+        assert_eq!(stem, "user");
+    }
+
+    #[test]
+    fn test_file_stem_returns_all_but_last_extension_of_name_with_multiple_dots() {
+        let path = "src/a.b.teo";
+        let stem = file_stem(path);
+        assert_eq!(stem, "a.b");
+    }
+
+    #[test]
+    fn test_file_stem_returns_hidden_file_name() {
+        let path = "src/.gitignore";
+        let stem = file_stem(path);
+        assert_eq!(stem, ".gitignore");
+    }
+
+    #[test]
+    fn test_file_stem_returns_empty_string_if_path_is_root() {
+        let path = "/";
+        let stem = file_stem(path);
+        assert_eq!(stem, "");
+    }
+
+    #[test]
+    fn test_file_stem_returns_empty_string_if_path_is_empty() {
+        let path = "";
+        let stem = file_stem(path);
+        assert_eq!(stem, "");
+    }
+
     #[test]
     fn test_path_is_absolute_returns_true_if_path_is_absolute() {
         if cfg!(windows) {
             let path = "C:\\Users\\user";
             let is_absolute = path_is_absolute(path);
-            assert_eq!(is_absolute, true);
-            return;
+            assert!(is_absolute);
         } else {
             let path = "/home/user";
             let is_absolute = path_is_absolute(path);
-            assert_eq!(is_absolute, true);
+            assert!(is_absolute);
         }
     }
 
@@ -123,7 +378,7 @@ mod tests {
     fn test_path_is_absolute_returns_false_if_path_is_not_absolute() {
         let path = "home/user";
         let is_absolute = path_is_absolute(path);
-        assert_eq!(is_absolute, false);
+        assert!(!is_absolute);
     }
 
     #[test]
@@ -133,7 +388,6 @@ mod tests {
             let path = "fsutil\\default.rs";
             let joined = path_join(base, path);
             assert_eq!(joined, "src\\fsutil\\default.rs");
-            return;
         } else {
             let base = "src";
             let path = "fsutil/default.rs";
@@ -149,7 +403,6 @@ mod tests {
             let path = "fsutil\\..\\fsutil\\default.rs";
             let joined = path_join(base, path);
             assert_eq!(joined, "src\\fsutil\\default.rs");
-            return;
         } else {
             let base = "src";
             let path = "fsutil/../fsutil/default.rs";
@@ -173,7 +426,6 @@ mod tests {
             let path = "fsutil\\default.rs";
             let joined = path_join(base, path);
             assert_eq!(joined, "fsutil\\default.rs");
-            return;
         } else {
             let base = "";
             let path = "fsutil/default.rs";
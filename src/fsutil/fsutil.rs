@@ -1,3 +1,19 @@
+use super::normalize_path;
+use std::fmt;
+use std::sync::Arc;
+
+type ReadFile = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+type ReadFileBytes = Arc<dyn Fn(&str) -> Option<Vec<u8>> + Send + Sync>;
+type TryReadFile = Arc<dyn Fn(&str) -> Result<String, FsError> + Send + Sync>;
+type PathPredicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+type PathJoin = Arc<dyn Fn(&str, &str) -> String + Send + Sync>;
+type ParentDirectory = Arc<dyn Fn(&str) -> String + Send + Sync>;
+type WriteFile = Arc<dyn Fn(&str, &str) -> Result<(), String> + Send + Sync>;
+type ReadDir = Arc<dyn Fn(&str) -> Option<Vec<String>> + Send + Sync>;
+type Canonicalize = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+type PathExtension = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+type FileStem = Arc<dyn Fn(&str) -> String + Send + Sync>;
+type ClearCache = Arc<dyn Fn() + Send + Sync>;
 
 /// # File system utility
 ///
@@ -5,24 +21,54 @@
 /// file system. This is designed with compatibility in mind. Not every
 /// platform that we target supports the standard Rust file system library.
 /// For example, WASM doesn't support Rust file system functions in the
-/// stdlib.
+/// stdlib. The functions may capture state, like the file map of
+/// [`FSUtil::in_memory`], and are shared between clones.
+#[derive(Clone)]
 pub struct FSUtil {
     /// The utility function to read file content.
-    read_file: fn(path: &str) -> Option<String>,
+    read_file: ReadFile,
+    /// The utility function to read file content as bytes. Reads with
+    /// `read_file` unless set with [`FSUtil::with_read_file_bytes`].
+    read_file_bytes: Option<ReadFileBytes>,
+    /// The utility function to read file content, telling why it cannot.
+    /// Reads with `read_text` unless set with [`FSUtil::with_try_read_file`].
+    try_read_file: Option<TryReadFile>,
     /// The utility function to check whether a file exists.
-    file_exists: fn(path: &str) -> bool,
+    file_exists: PathPredicate,
     /// The utility function to check if file is a directory.
-    file_is_directory: fn(path: &str) -> bool,
+    file_is_directory: PathPredicate,
     /// The utility function to join `base` and `path` into a single path.
-    path_join: fn(base: &str, path: &str) -> String,
+    path_join: PathJoin,
     /// The utility function to get the parent directory of the argument.
-    parent_directory: fn(path: &str) -> String,
+    parent_directory: ParentDirectory,
     /// The utility function to check whether a path is absolute.
-    path_is_absolute: fn(path: &str) -> bool,
+    path_is_absolute: PathPredicate,
+    /// The utility function to write `contents` into the file at `path`.
+    write_file: WriteFile,
+    /// The utility function to list the names of the entries of a directory.
+    read_dir: ReadDir,
+    /// The utility function to resolve a path to the absolute real path it
+    /// refers to. Answers `None` unless set with
+    /// [`FSUtil::with_canonicalize`].
+    canonicalize: Canonicalize,
+    /// The utility function to get the extension of the file name of a
+    /// path. Splits the path as text unless set with
+    /// [`FSUtil::with_path_extension`].
+    path_extension: PathExtension,
+    /// The utility function to get the file name of a path without its
+    /// extension. Splits the path as text unless set with
+    /// [`FSUtil::with_file_stem`].
+    file_stem: FileStem,
+    /// The function forgetting cached answers, see [`FSUtil::cached`].
+    pub(super) clear_cache: ClearCache,
+    /// Whether imports are checked for the case of the files they name,
+    /// see [`FSUtil::with_case_check`].
+    pub(super) check_case: bool,
 }
 
 impl FSUtil {
-    /// Create a new instance of file system utility.
+    /// Create a new instance of file system utility. Each argument is a
+    /// function or a closure, which may capture state.
     ///
     /// # Arguments
     ///
@@ -31,6 +77,9 @@ impl FSUtil {
     /// * `file_is_directory` - A function to check if file is a directory.
     /// * `path_join` - A function to join `base` and `path` into a single path.
     /// * `parent_directory` - A function to get the parent directory of the argument.
+    /// * `path_is_absolute` - A function to check whether a path is absolute.
+    /// * `write_file` - A function to write file content.
+    /// * `read_dir` - A function to list the entry names of a directory.
     ///
     /// # Examples
     /// ```
@@ -67,13 +116,24 @@ impl FSUtil {
     ///     Path::new(path).is_dir()
     /// }
     ///
+    /// fn write_file(path: &str, contents: &str) -> Result<(), String> {
+    ///     fs::write(path, contents).map_err(|error| error.to_string())
+    /// }
+    ///
+    /// fn read_dir(path: &str) -> Option<Vec<String>> {
+    ///     let entries = fs::read_dir(path).ok()?;
+    ///     Some(entries.filter_map(|e| e.ok()?.file_name().into_string().ok()).collect())
+    /// }
+    ///
     /// let fs_util = FSUtil::new(
     ///     read_file,
     ///     file_exists,
     ///     file_is_directory,
     ///     path_join,
     ///     parent_directory,
-    ///     path_is_absolute
+    ///     path_is_absolute,
+    ///     write_file,
+    ///     read_dir
     /// );
     /// assert!(fs_util.file_exists("Cargo.toml"));
     /// assert!(!fs_util.file_is_directory("Cargo.toml"));
@@ -81,22 +141,101 @@ impl FSUtil {
     /// assert_eq!(fs_util.parent_directory("src/main.rs"), "src");
     /// assert!(fs_util.path_is_absolute("/home/user"));
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        read_file: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+        file_exists: impl Fn(&str) -> bool + Send + Sync + 'static,
+        file_is_directory: impl Fn(&str) -> bool + Send + Sync + 'static,
+        path_join: impl Fn(&str, &str) -> String + Send + Sync + 'static,
+        parent_directory: impl Fn(&str) -> String + Send + Sync + 'static,
+        path_is_absolute: impl Fn(&str) -> bool + Send + Sync + 'static,
+        write_file: impl Fn(&str, &str) -> Result<(), String> + Send + Sync + 'static,
+        read_dir: impl Fn(&str) -> Option<Vec<String>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            read_file: Arc::new(read_file),
+            read_file_bytes: None,
+            try_read_file: None,
+            file_exists: Arc::new(file_exists),
+            file_is_directory: Arc::new(file_is_directory),
+            path_join: Arc::new(path_join),
+            parent_directory: Arc::new(parent_directory),
+            path_is_absolute: Arc::new(path_is_absolute),
+            write_file: Arc::new(write_file),
+            read_dir: Arc::new(read_dir),
+            canonicalize: Arc::new(|_| None),
+            path_extension: Arc::new(extension),
+            file_stem: Arc::new(stem),
+            clear_cache: Arc::new(|| ()),
+            check_case: false,
+        }
+    }
+
+    /// Create a new instance of file system utility from plain function
+    /// pointers, as [`FSUtil::new`] took before it accepted closures.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_fns(
         read_file: fn(path: &str) -> Option<String>,
         file_exists: fn(path: &str) -> bool,
         file_is_directory: fn(path: &str) -> bool,
         path_join: fn(base: &str, path: &str) -> String,
         parent_directory: fn(path: &str) -> String,
         path_is_absolute: fn(path: &str) -> bool,
+        write_file: fn(path: &str, contents: &str) -> Result<(), String>,
+        read_dir: fn(path: &str) -> Option<Vec<String>>,
     ) -> Self {
-        Self {
+        Self::new(
             read_file,
             file_exists,
             file_is_directory,
             path_join,
             parent_directory,
             path_is_absolute,
-        }
+            write_file,
+            read_dir,
+        )
+    }
+
+    /// Returns the utility with `read_file` replacing its file reading
+    /// function. The byte and fallible readers read with it too.
+    pub fn with_read_file(
+        mut self,
+        read_file: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.read_file = Arc::new(read_file);
+        self.read_file_bytes = None;
+        self.try_read_file = None;
+        self
+    }
+
+    /// Returns the utility with `read_file_bytes` reading the content of
+    /// files as bytes, for files that may not be valid UTF-8.
+    pub fn with_read_file_bytes(
+        mut self,
+        read_file_bytes: impl Fn(&str) -> Option<Vec<u8>> + Send + Sync + 'static,
+    ) -> Self {
+        self.read_file_bytes = Some(Arc::new(read_file_bytes));
+        self
+    }
+
+    /// Returns the utility with `try_read_file` reading the content of
+    /// files, with the reason a file cannot be read.
+    pub fn with_try_read_file(
+        mut self,
+        try_read_file: impl Fn(&str) -> Result<String, FsError> + Send + Sync + 'static,
+    ) -> Self {
+        self.try_read_file = Some(Arc::new(try_read_file));
+        self
+    }
+
+    /// Returns the utility with `write_file` replacing its file writing
+    /// function.
+    pub fn with_write_file(
+        mut self,
+        write_file: impl Fn(&str, &str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.write_file = Arc::new(write_file);
+        self
     }
 
     /// Read the file content from `path` into a String.
@@ -105,6 +244,49 @@ impl FSUtil {
         (self.read_file)(path)
     }
 
+    /// Read the file content from `path` as bytes, whatever its encoding.
+    /// None if file doesn't exist or cannot be read.
+    pub fn read_file_bytes(&self, path: &str) -> Option<Vec<u8>> {
+        match &self.read_file_bytes {
+            Some(read_file_bytes) => read_file_bytes(path),
+            None => self.read_file(path).map(String::into_bytes),
+        }
+    }
+
+    /// Read the file content from `path` as text. None if file doesn't
+    /// exist or cannot be read, an [`EncodingError`] if its content isn't
+    /// valid UTF-8.
+    ///
+    /// Telling the two apart needs the bytes of the file, so a utility
+    /// without [`FSUtil::with_read_file_bytes`] reports every file its
+    /// `read_file` cannot read as missing.
+    pub fn read_text(&self, path: &str) -> Option<Result<String, EncodingError>> {
+        if self.read_file_bytes.is_none() {
+            return self.read_file(path).map(Ok);
+        }
+        let bytes = self.read_file_bytes(path)?;
+        Some(String::from_utf8(bytes).map_err(|error| EncodingError {
+            path: path.to_owned(),
+            valid_up_to: error.utf8_error().valid_up_to(),
+        }))
+    }
+
+    /// Read the file content from `path` into a String, or tell why it
+    /// cannot be read.
+    ///
+    /// A utility without [`FSUtil::with_try_read_file`] only knows what
+    /// [`FSUtil::read_text`] does: a file is either missing or not UTF-8.
+    pub fn try_read_file(&self, path: &str) -> Result<String, FsError> {
+        match &self.try_read_file {
+            Some(try_read_file) => try_read_file(path),
+            None => match self.read_text(path) {
+                Some(Ok(content)) => Ok(content),
+                Some(Err(_)) => Err(FsError::InvalidUtf8),
+                None => Err(FsError::NotFound),
+            },
+        }
+    }
+
     /// Returns true if file exists at `path`.
     pub fn file_exists(&self, path: &str) -> bool {
         (self.file_exists)(path)
@@ -130,15 +312,192 @@ impl FSUtil {
         (self.path_is_absolute)(path)
     }
 
+    /// Returns the utility with `path_extension` getting the extension of
+    /// the file name of a path.
+    pub fn with_path_extension(
+        mut self,
+        path_extension: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.path_extension = Arc::new(path_extension);
+        self
+    }
+
+    /// Returns the extension of the file name of `path` without the dot,
+    /// like `teo` for `schema/a.b.teo`. None if the name has no extension,
+    /// like `Makefile`, or only starts with a dot, like `.gitignore`.
+    pub fn path_extension(&self, path: &str) -> Option<String> {
+        (self.path_extension)(path)
+    }
+
+    /// Returns the utility with `file_stem` getting the file name of a path
+    /// without its extension.
+    pub fn with_file_stem(
+        mut self,
+        file_stem: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.file_stem = Arc::new(file_stem);
+        self
+    }
+
+    /// Returns the file name of `path` without its directory and extension,
+    /// like `user` for `src/user.teo`. A trailing separator is ignored, and
+    /// a name that only starts with a dot, like `.gitignore`, is its own
+    /// stem. Empty if `path` has no file name, like `/`.
+    pub fn file_stem(&self, path: &str) -> String {
+        (self.file_stem)(path)
+    }
+
+    /// Write `contents` into the file at `path`, replacing what it had.
+    /// Returns a description of the failure if it cannot be written.
+    pub fn write_file(&self, path: &str, contents: &str) -> Result<(), String> {
+        (self.write_file)(path, contents)
+    }
+
+    /// Returns the names of the entries of the directory at `path`, not
+    /// their full paths. None if `path` isn't a readable directory.
+    pub fn read_dir(&self, path: &str) -> Option<Vec<String>> {
+        (self.read_dir)(path)
+    }
+
+    /// Returns the utility with `canonicalize` resolving paths to the real
+    /// paths they refer to. Recursive walks use it to notice links that
+    /// lead back to a directory already visited.
+    pub fn with_canonicalize(
+        mut self,
+        canonicalize: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.canonicalize = Arc::new(canonicalize);
+        self
+    }
+
+    /// Returns the absolute, normalized path `path` refers to, following
+    /// links, so that equivalent paths like `a/../a/x.teo` and `a/x.teo`
+    /// give the same answer. None if the utility cannot resolve paths.
+    pub fn canonicalize(&self, path: &str) -> Option<String> {
+        (self.canonicalize)(path)
+    }
+
+    /// Returns the key files are known by at `path`: its
+    /// [canonical](FSUtil::canonicalize) path when the utility can resolve
+    /// it, normalized by [`normalize_path`] so that keys don't depend on
+    /// the separators of the platform.
+    pub fn path_key(&self, path: &str) -> String {
+        normalize_path(&self.canonicalize(path).unwrap_or_else(|| path.to_owned()))
+    }
+
+    /// Forget every cached answer of a utility created by
+    /// [`FSUtil::cached`]. Does nothing for other utilities.
+    pub fn clear_cache(&self) {
+        (self.clear_cache)()
+    }
+
     /// Get the parent directory of `source_path` and join it with `path`.
     pub fn import_path(&self, source_path: &str, path: &str) -> String {
         self.path_join(&self.parent_directory(source_path), path)
     }
 }
 
+/// Returns the last component of `path`, splitting at both `/` and `\`,
+/// or None if it has none, like `/` or `..`.
+fn file_name(path: &str) -> Option<&str> {
+    let name = path
+        .trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()?;
+    (!name.is_empty() && name != "..").then_some(name)
+}
+
+/// Returns the extension of the last component of `path`.
+fn extension(path: &str) -> Option<String> {
+    let (stem, extension) = file_name(path)?.rsplit_once('.')?;
+    (!stem.is_empty() && !extension.is_empty()).then(|| extension.to_owned())
+}
+
+/// Returns the last component of `path` without its extension.
+fn stem(path: &str) -> String {
+    let Some(name) = file_name(path) else {
+        return String::new();
+    };
+    match name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem.to_owned(),
+        _ => name.to_owned(),
+    }
+}
+
+/// # Encoding error
+///
+/// The content of a file read as text by [`FSUtil::read_text`] isn't
+/// valid UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingError {
+    /// The path of the file.
+    pub path: String,
+    /// The number of bytes at the start of the file that are valid UTF-8.
+    pub valid_up_to: usize,
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not valid UTF-8, from byte {}",
+            self.path, self.valid_up_to
+        )
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+/// # File system error
+///
+/// Why [`FSUtil::try_read_file`] cannot read a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FsError {
+    /// There is no file at the path.
+    NotFound,
+    /// The file cannot be read with the permissions of the process.
+    PermissionDenied,
+    /// The content of the file isn't valid UTF-8.
+    InvalidUtf8,
+    /// Any other failure, described.
+    Other(String),
+}
+
+impl From<std::io::Error> for FsError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => Self::NotFound,
+            std::io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            // `read_to_string` reports content that isn't UTF-8 as invalid data.
+            std::io::ErrorKind::InvalidData => Self::InvalidUtf8,
+            _ => Self::Other(error.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for FsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => f.write_str("file not found"),
+            Self::PermissionDenied => f.write_str("permission denied"),
+            Self::InvalidUtf8 => f.write_str("not valid UTF-8"),
+            Self::Other(description) => f.write_str(description),
+        }
+    }
+}
+
+impl std::error::Error for FsError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    thread_local! {
+        static WRITES: RefCell<Vec<(String, String)>> = const { RefCell::new(vec![]) };
+    }
 
     fn read_file(path: &str) -> Option<String> {
         match path {
@@ -170,53 +529,251 @@ mod tests {
         path.starts_with("/")
     }
 
-    static FS_UTIL: FSUtil = FSUtil {
-        read_file,
-        file_exists,
-        file_is_directory,
-        path_join,
-        parent_directory,
-        path_is_absolute,
-    };
+    fn write_file(path: &str, contents: &str) -> Result<(), String> {
+        match path {
+            "readonly" => Err("permission denied".to_owned()),
+            _ => {
+                WRITES.with(|writes| {
+                    writes
+                        .borrow_mut()
+                        .push((path.to_owned(), contents.to_owned()))
+                });
+                Ok(())
+            }
+        }
+    }
+
+    fn read_dir(path: &str) -> Option<Vec<String>> {
+        match path {
+            "directory" => Some(vec!["a".to_owned(), "b".to_owned()]),
+            _ => None,
+        }
+    }
+
+    fn fs_util() -> FSUtil {
+        FSUtil::new(
+            read_file,
+            file_exists,
+            file_is_directory,
+            path_join,
+            parent_directory,
+            path_is_absolute,
+            write_file,
+            read_dir,
+        )
+    }
 
     #[test]
     fn read_file_calls_the_argument_function() {
-        assert_eq!(FS_UTIL.read_file("exists"), Some("content".to_owned()));
-        assert_eq!(FS_UTIL.read_file("not_exists"), None);
+        assert_eq!(fs_util().read_file("exists"), Some("content".to_owned()));
+        assert_eq!(fs_util().read_file("not_exists"), None);
+    }
+
+    #[test]
+    fn read_file_bytes_falls_back_to_read_file() {
+        assert_eq!(
+            fs_util().read_file_bytes("exists"),
+            Some(b"content".to_vec())
+        );
+        assert_eq!(fs_util().read_file_bytes("not_exists"), None);
+        assert_eq!(
+            fs_util().read_text("exists"),
+            Some(Ok("content".to_owned()))
+        );
+    }
+
+    #[test]
+    fn read_text_reports_invalid_utf8() {
+        let fs_util = fs_util().with_read_file_bytes(|path| match path {
+            "latin1" => Some(b"caf\xe9".to_vec()),
+            "exists" => Some(b"content".to_vec()),
+            _ => None,
+        });
+        assert_eq!(fs_util.read_text("exists"), Some(Ok("content".to_owned())));
+        assert_eq!(fs_util.read_text("not_exists"), None);
+        let error = fs_util.read_text("latin1").unwrap().unwrap_err();
+        assert_eq!(error.valid_up_to, 3);
+        assert_eq!(
+            error.to_string(),
+            "`latin1` is not valid UTF-8, from byte 3"
+        );
+    }
+
+    #[test]
+    fn try_read_file_falls_back_to_read_text() {
+        assert_eq!(fs_util().try_read_file("exists"), Ok("content".to_owned()));
+        assert_eq!(
+            fs_util().try_read_file("not_exists"),
+            Err(FsError::NotFound)
+        );
+        let fs_util = fs_util().with_read_file_bytes(|_| Some(vec![0xff]));
+        assert_eq!(fs_util.try_read_file("exists"), Err(FsError::InvalidUtf8));
+    }
+
+    #[test]
+    fn try_read_file_calls_the_argument_function() {
+        let fs_util = fs_util().with_try_read_file(|_| Err(FsError::PermissionDenied));
+        assert_eq!(
+            fs_util.try_read_file("exists"),
+            Err(FsError::PermissionDenied)
+        );
+        let fs_util = fs_util.with_read_file(read_file);
+        assert_eq!(fs_util.try_read_file("exists"), Ok("content".to_owned()));
+    }
+
+    #[test]
+    fn io_errors_map_to_fs_errors() {
+        use std::io::{Error, ErrorKind};
+        let map = |kind| FsError::from(Error::from(kind));
+        assert_eq!(map(ErrorKind::NotFound), FsError::NotFound);
+        assert_eq!(map(ErrorKind::PermissionDenied), FsError::PermissionDenied);
+        assert_eq!(map(ErrorKind::InvalidData), FsError::InvalidUtf8);
+        assert_eq!(
+            FsError::from(Error::other("disk on fire")),
+            FsError::Other("disk on fire".to_owned())
+        );
+        assert_eq!(
+            map(ErrorKind::Interrupted).to_string(),
+            "operation interrupted"
+        );
     }
 
     #[test]
     fn file_exists_calls_the_argument_function() {
-        assert!(FS_UTIL.file_exists("exists"));
-        assert!(!FS_UTIL.file_exists("not_exists"));
+        assert!(fs_util().file_exists("exists"));
+        assert!(!fs_util().file_exists("not_exists"));
     }
 
     #[test]
     fn file_is_directory_calls_the_argument_function() {
-        assert!(FS_UTIL.file_is_directory("directory"));
-        assert!(!FS_UTIL.file_is_directory("file"));
+        assert!(fs_util().file_is_directory("directory"));
+        assert!(!fs_util().file_is_directory("file"));
     }
 
     #[test]
     fn path_join_calls_the_argument_function() {
-        assert_eq!(FS_UTIL.path_join("base", "path"), "base/path");
+        assert_eq!(fs_util().path_join("base", "path"), "base/path");
     }
 
     #[test]
     fn parent_directory_calls_the_argument_function() {
-        assert_eq!(FS_UTIL.parent_directory("hasParent"), "parent");
-        assert_eq!(FS_UTIL.parent_directory("noParent"), "");
+        assert_eq!(fs_util().parent_directory("hasParent"), "parent");
+        assert_eq!(fs_util().parent_directory("noParent"), "");
     }
 
     #[test]
     fn path_is_absolute_calls_the_argument_function() {
-        assert!(FS_UTIL.path_is_absolute("/home/user"));
-        assert!(!FS_UTIL.path_is_absolute("home/user"));
+        assert!(fs_util().path_is_absolute("/home/user"));
+        assert!(!fs_util().path_is_absolute("home/user"));
+    }
+
+    #[test]
+    fn path_extension_splits_the_file_name() {
+        assert_eq!(
+            fs_util().path_extension("schema/user.teo"),
+            Some("teo".to_owned())
+        );
+        assert_eq!(
+            fs_util().path_extension("schema\\a.b.teo"),
+            Some("teo".to_owned())
+        );
+        assert_eq!(fs_util().path_extension("schema/.gitignore"), None);
+        assert_eq!(fs_util().path_extension("a.b/Makefile"), None);
+        assert_eq!(
+            fs_util().path_extension("schema/user.teo/"),
+            Some("teo".to_owned())
+        );
+        let fs_util = fs_util().with_path_extension(|_| Some("rs".to_owned()));
+        assert_eq!(fs_util.path_extension("user.teo"), Some("rs".to_owned()));
+    }
+
+    #[test]
+    fn file_stem_splits_the_file_name() {
+        assert_eq!(fs_util().file_stem("src/user.teo"), "user");
+        assert_eq!(fs_util().file_stem("src\\a.b.teo"), "a.b");
+        assert_eq!(fs_util().file_stem("src/models/"), "models");
+        assert_eq!(fs_util().file_stem(".gitignore"), ".gitignore");
+        assert_eq!(fs_util().file_stem("/"), "");
+        let fs_util = fs_util().with_file_stem(|_| "index".to_owned());
+        assert_eq!(fs_util.file_stem("src/user.teo"), "index");
+    }
+
+    #[test]
+    fn read_dir_calls_the_argument_function() {
+        assert_eq!(
+            fs_util().read_dir("directory"),
+            Some(vec!["a".to_owned(), "b".to_owned()])
+        );
+        assert_eq!(fs_util().read_dir("file"), None);
+    }
+
+    #[test]
+    fn callbacks_can_capture_state() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let counter = reads.clone();
+        let fs_util = fs_util().with_read_file(move |path| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            read_file(path)
+        });
+        assert_eq!(fs_util.read_file("exists"), Some("content".to_owned()));
+        assert_eq!(fs_util.clone().read_file("not_exists"), None);
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn closures_can_record_writes() {
+        let written = Arc::new(Mutex::new(vec![]));
+        let buffer = written.clone();
+        let fs_util = fs_util().with_write_file(move |path, contents| {
+            buffer
+                .lock()
+                .unwrap()
+                .push(format!("{}={}", path, contents));
+            Ok(())
+        });
+        std::thread::spawn(move || fs_util.write_file("out.rs", "a"))
+            .join()
+            .unwrap()
+            .unwrap();
+        assert_eq!(*written.lock().unwrap(), vec!["out.rs=a".to_owned()]);
+    }
+
+    #[test]
+    fn from_fns_accepts_function_pointers() {
+        let fs_util = FSUtil::from_fns(
+            read_file,
+            file_exists,
+            file_is_directory,
+            path_join,
+            parent_directory,
+            path_is_absolute,
+            write_file,
+            read_dir,
+        );
+        assert!(fs_util.file_exists("exists"));
     }
 
     #[test]
     fn import_path_calls_parent_directory_and_path_join() {
-        assert_eq!(FS_UTIL.import_path("hasParent", "path"), "parent/path");
-        assert_eq!(FS_UTIL.import_path("noParent", "path"), "/path");
+        assert_eq!(fs_util().import_path("hasParent", "path"), "parent/path");
+        assert_eq!(fs_util().import_path("noParent", "path"), "/path");
+    }
+
+    #[test]
+    fn write_file_calls_the_argument_function() {
+        assert_eq!(fs_util().write_file("out/a.rs", "a"), Ok(()));
+        assert_eq!(fs_util().write_file("out/b.rs", "b"), Ok(()));
+        assert_eq!(
+            fs_util().write_file("readonly", "c"),
+            Err("permission denied".to_owned())
+        );
+        let writes = WRITES.with(|writes| writes.borrow().clone());
+        assert_eq!(
+            writes,
+            vec![
+                ("out/a.rs".to_owned(), "a".to_owned()),
+                ("out/b.rs".to_owned(), "b".to_owned()),
+            ]
+        );
     }
 }
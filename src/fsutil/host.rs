@@ -0,0 +1,358 @@
+use super::FSUtil;
+
+/// The number of bytes first offered to a host function for its result.
+const INITIAL_CAPACITY: usize = 1024;
+
+/// A host function taking a path and answering yes or no.
+pub type HostPredicate = unsafe extern "C" fn(path: *const u8, path_len: usize) -> i32;
+
+/// A host function taking a path and writing a string result.
+pub type HostQuery = unsafe extern "C" fn(
+    path: *const u8,
+    path_len: usize,
+    out: *mut u8,
+    out_capacity: usize,
+) -> isize;
+
+/// A host function taking two strings and writing a string result.
+pub type HostJoin = unsafe extern "C" fn(
+    base: *const u8,
+    base_len: usize,
+    path: *const u8,
+    path_len: usize,
+    out: *mut u8,
+    out_capacity: usize,
+) -> isize;
+
+/// A host function writing a file.
+pub type HostWrite = unsafe extern "C" fn(
+    path: *const u8,
+    path_len: usize,
+    contents: *const u8,
+    contents_len: usize,
+) -> i32;
+
+/// # Host imports
+///
+/// The functions a host, like a JavaScript runtime loading the parser as
+/// a WASM module, provides to answer file system queries, for
+/// [`FSUtil::from_host`].
+///
+/// Every string crosses the boundary as a pointer to UTF-8 bytes in the
+/// linear memory of the module and a length in bytes, without a
+/// terminator. A function with a string result is given an output buffer
+/// and its capacity. It returns the length of the result, or `-1` if
+/// there is none, and writes the result into the buffer only if it fits.
+/// When it doesn't fit, the function is called again with a buffer of the
+/// returned length, so it must give the same answer twice. Predicates
+/// return non-zero for true. `write_file` returns zero on success.
+/// `read_dir` answers the names of the entries separated by `\n`.
+#[derive(Debug, Clone, Copy)]
+pub struct HostImports {
+    pub read_file: HostQuery,
+    pub file_exists: HostPredicate,
+    pub file_is_directory: HostPredicate,
+    pub path_join: HostJoin,
+    pub parent_directory: HostQuery,
+    pub path_is_absolute: HostPredicate,
+    pub write_file: HostWrite,
+    pub read_dir: HostQuery,
+    /// Resolves a path to the real path it refers to, `-1` if it cannot.
+    pub canonicalize: HostQuery,
+}
+
+#[cfg(target_arch = "wasm32")]
+#[link(wasm_import_module = "teo_fs")]
+extern "C" {
+    fn read_file(path: *const u8, path_len: usize, out: *mut u8, out_capacity: usize) -> isize;
+    fn file_exists(path: *const u8, path_len: usize) -> i32;
+    fn file_is_directory(path: *const u8, path_len: usize) -> i32;
+    fn path_join(
+        base: *const u8,
+        base_len: usize,
+        path: *const u8,
+        path_len: usize,
+        out: *mut u8,
+        out_capacity: usize,
+    ) -> isize;
+    fn parent_directory(
+        path: *const u8,
+        path_len: usize,
+        out: *mut u8,
+        out_capacity: usize,
+    ) -> isize;
+    fn path_is_absolute(path: *const u8, path_len: usize) -> i32;
+    fn write_file(
+        path: *const u8,
+        path_len: usize,
+        contents: *const u8,
+        contents_len: usize,
+    ) -> i32;
+    fn read_dir(path: *const u8, path_len: usize, out: *mut u8, out_capacity: usize) -> isize;
+    fn canonicalize(path: *const u8, path_len: usize, out: *mut u8, out_capacity: usize) -> isize;
+}
+
+impl HostImports {
+    /// Returns the functions imported from the `teo_fs` module of the
+    /// host, each under the name of its field.
+    #[cfg(target_arch = "wasm32")]
+    pub fn linked() -> Self {
+        Self {
+            read_file,
+            file_exists,
+            file_is_directory,
+            path_join,
+            parent_directory,
+            path_is_absolute,
+            write_file,
+            read_dir,
+            canonicalize,
+        }
+    }
+}
+
+impl FSUtil {
+    /// Create a file system utility answering every query by calling the
+    /// host functions of `imports`, following the ABI described at
+    /// [`HostImports`].
+    ///
+    /// A result that isn't valid UTF-8 reads as no result. A path join or
+    /// parent directory without a result is the empty path.
+    ///
+    /// # Safety
+    ///
+    /// The functions must follow the ABI: read only the given input
+    /// lengths, and write at most the given capacity into the output
+    /// buffer.
+    pub unsafe fn from_host(imports: HostImports) -> Self {
+        let HostImports {
+            read_file,
+            file_exists,
+            file_is_directory,
+            path_join,
+            parent_directory,
+            path_is_absolute,
+            write_file,
+            read_dir,
+            canonicalize,
+        } = imports;
+        let query = move |query: HostQuery| {
+            move |path: &str| {
+                string_result(|out, capacity| unsafe {
+                    query(path.as_ptr(), path.len(), out, capacity)
+                })
+            }
+        };
+        let predicate = |predicate: HostPredicate| {
+            move |path: &str| unsafe { predicate(path.as_ptr(), path.len()) != 0 }
+        };
+        let parent = query(parent_directory);
+        let entries = query(read_dir);
+        Self::new(
+            query(read_file),
+            predicate(file_exists),
+            predicate(file_is_directory),
+            move |base, path| {
+                string_result(|out, capacity| unsafe {
+                    path_join(
+                        base.as_ptr(),
+                        base.len(),
+                        path.as_ptr(),
+                        path.len(),
+                        out,
+                        capacity,
+                    )
+                })
+                .unwrap_or_default()
+            },
+            move |path| parent(path).unwrap_or_default(),
+            predicate(path_is_absolute),
+            move |path, contents| {
+                let status = unsafe {
+                    write_file(path.as_ptr(), path.len(), contents.as_ptr(), contents.len())
+                };
+                match status {
+                    0 => Ok(()),
+                    status => Err(format!("the host could not write `{}`: {}", path, status)),
+                }
+            },
+            move |path| {
+                let entries = entries(path)?;
+                Some(match entries.is_empty() {
+                    true => vec![],
+                    false => entries.split('\n').map(str::to_owned).collect(),
+                })
+            },
+        )
+        .with_canonicalize(query(canonicalize))
+    }
+}
+
+/// Call `call` with an output buffer and its capacity until the result
+/// fits, and return the result. None if there is none or it isn't UTF-8.
+fn string_result(mut call: impl FnMut(*mut u8, usize) -> isize) -> Option<String> {
+    let mut buffer = vec![0; INITIAL_CAPACITY];
+    loop {
+        let length = usize::try_from(call(buffer.as_mut_ptr(), buffer.len())).ok()?;
+        if length <= buffer.len() {
+            buffer.truncate(length);
+            return String::from_utf8(buffer).ok();
+        }
+        buffer.resize(length, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::resolve_imports;
+
+    /// The files of the host, the last one longer than the first buffer.
+    fn files() -> Vec<(&'static str, String)> {
+        vec![
+            ("schema/main.teo", "include \"./user.teo\"".to_owned()),
+            (
+                "schema/user.teo",
+                format!("model User {{\n{}}}", "    name: String\n".repeat(100)),
+            ),
+        ]
+    }
+
+    unsafe fn text<'a>(pointer: *const u8, length: usize) -> &'a str {
+        std::str::from_utf8(std::slice::from_raw_parts(pointer, length)).unwrap()
+    }
+
+    unsafe fn answer(result: Option<&str>, out: *mut u8, capacity: usize) -> isize {
+        let Some(result) = result else {
+            return -1;
+        };
+        if result.len() <= capacity {
+            std::ptr::copy_nonoverlapping(result.as_ptr(), out, result.len());
+        }
+        result.len() as isize
+    }
+
+    unsafe extern "C" fn read_file(path: *const u8, len: usize, out: *mut u8, cap: usize) -> isize {
+        let path = text(path, len);
+        let files = files();
+        let content = files
+            .iter()
+            .find(|(p, _)| *p == path)
+            .map(|(_, c)| c.as_str());
+        answer(content, out, cap)
+    }
+
+    unsafe extern "C" fn file_exists(path: *const u8, len: usize) -> i32 {
+        let path = text(path, len);
+        (path == "schema" || files().iter().any(|(p, _)| *p == path)) as i32
+    }
+
+    unsafe extern "C" fn file_is_directory(path: *const u8, len: usize) -> i32 {
+        (text(path, len) == "schema") as i32
+    }
+
+    unsafe extern "C" fn path_join(
+        base: *const u8,
+        base_len: usize,
+        path: *const u8,
+        path_len: usize,
+        out: *mut u8,
+        cap: usize,
+    ) -> isize {
+        let path = text(path, path_len).trim_start_matches("./");
+        let joined = format!("{}/{}", text(base, base_len), path);
+        answer(Some(&joined), out, cap)
+    }
+
+    unsafe extern "C" fn parent_directory(
+        path: *const u8,
+        len: usize,
+        out: *mut u8,
+        cap: usize,
+    ) -> isize {
+        let parent = text(path, len).rsplit_once('/').map(|(parent, _)| parent);
+        answer(Some(parent.unwrap_or("")), out, cap)
+    }
+
+    unsafe extern "C" fn path_is_absolute(path: *const u8, len: usize) -> i32 {
+        text(path, len).starts_with('/') as i32
+    }
+
+    unsafe extern "C" fn write_file(
+        _path: *const u8,
+        _path_len: usize,
+        _contents: *const u8,
+        _contents_len: usize,
+    ) -> i32 {
+        13
+    }
+
+    unsafe extern "C" fn read_dir(path: *const u8, len: usize, out: *mut u8, cap: usize) -> isize {
+        let entries = (text(path, len) == "schema").then_some("main.teo\nuser.teo");
+        answer(entries, out, cap)
+    }
+
+    unsafe extern "C" fn canonicalize(
+        _path: *const u8,
+        _len: usize,
+        _out: *mut u8,
+        _cap: usize,
+    ) -> isize {
+        -1
+    }
+
+    fn fs() -> FSUtil {
+        unsafe {
+            FSUtil::from_host(HostImports {
+                read_file,
+                file_exists,
+                file_is_directory,
+                path_join,
+                parent_directory,
+                path_is_absolute,
+                write_file,
+                read_dir,
+                canonicalize,
+            })
+        }
+    }
+
+    #[test]
+    fn answers_queries_through_the_host() {
+        let fs = fs();
+        assert_eq!(
+            fs.read_file("schema/main.teo").as_deref(),
+            Some("include \"./user.teo\"")
+        );
+        assert_eq!(fs.read_file("schema/missing.teo"), None);
+        assert!(fs.file_exists("schema/user.teo"));
+        assert!(fs.file_is_directory("schema"));
+        assert_eq!(fs.path_join("schema", "./user.teo"), "schema/user.teo");
+        assert_eq!(fs.parent_directory("schema/user.teo"), "schema");
+        assert!(!fs.path_is_absolute("schema"));
+        assert_eq!(
+            fs.read_dir("schema"),
+            Some(vec!["main.teo".to_owned(), "user.teo".to_owned()])
+        );
+        assert_eq!(fs.read_dir("schema/user.teo"), None);
+        assert_eq!(
+            fs.write_file("schema/out.teo", ""),
+            Err("the host could not write `schema/out.teo`: 13".to_owned())
+        );
+    }
+
+    #[test]
+    fn grows_the_buffer_for_a_long_result() {
+        let content = fs().read_file("schema/user.teo").unwrap();
+        assert!(content.len() > INITIAL_CAPACITY);
+        assert_eq!(content, files()[1].1);
+    }
+
+    #[test]
+    fn resolves_imports_from_the_host() {
+        let graph = resolve_imports("schema/main.teo", &fs());
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["schema/main.teo", "schema/user.teo"]);
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+    }
+}
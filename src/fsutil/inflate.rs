@@ -0,0 +1,329 @@
+/// The base lengths of the length codes 257 to 285.
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+
+/// The number of extra bits after each of the length codes 257 to 285.
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// The base distances of the distance codes 0 to 29.
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+
+/// The number of extra bits after each of the distance codes 0 to 29.
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// The order in which the code lengths of the code length alphabet are
+/// written in a dynamic block.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompress `bytes`, a raw deflate stream as stored in zip archives,
+/// into at most `limit` bytes. A stream inflating to more is an error.
+pub(super) fn inflate(bytes: &[u8], limit: usize) -> Result<Vec<u8>, String> {
+    let mut input = Bits {
+        bytes,
+        position: 0,
+        buffer: 0,
+        count: 0,
+    };
+    let mut output = Output {
+        bytes: vec![],
+        limit,
+    };
+    loop {
+        let last = input.take(1)? == 1;
+        match input.take(2)? {
+            0 => stored(&mut input, &mut output)?,
+            1 => {
+                let (lengths, distances) = fixed_codes();
+                compressed(&mut input, &mut output, &lengths, &distances)?
+            }
+            2 => {
+                let (lengths, distances) = dynamic_codes(&mut input)?;
+                compressed(&mut input, &mut output, &lengths, &distances)?
+            }
+            _ => return Err("invalid deflate block type".to_owned()),
+        }
+        if last {
+            return Ok(output.bytes);
+        }
+    }
+}
+
+/// Copy a stored block to `output`.
+fn stored(input: &mut Bits, output: &mut Output) -> Result<(), String> {
+    input.align();
+    let header = input.bytes(4)?;
+    let length = u16::from_le_bytes([header[0], header[1]]);
+    let complement = u16::from_le_bytes([header[2], header[3]]);
+    if length != !complement {
+        return Err("corrupt stored deflate block".to_owned());
+    }
+    output.extend(input.bytes(length as usize)?)
+}
+
+/// Decode a block compressed with the `lengths` code for literals and
+/// lengths and the `distances` code, into `output`.
+fn compressed(
+    input: &mut Bits,
+    output: &mut Output,
+    lengths: &Huffman,
+    distances: &Huffman,
+) -> Result<(), String> {
+    loop {
+        let symbol = lengths.decode(input)? as usize;
+        match symbol {
+            0..=255 => output.extend(&[symbol as u8])?,
+            256 => return Ok(()),
+            257..=285 => {
+                let index = symbol - 257;
+                let length =
+                    LENGTH_BASE[index] as usize + input.take(LENGTH_EXTRA[index])? as usize;
+                let index = distances.decode(input)? as usize;
+                if index >= DISTANCE_BASE.len() {
+                    return Err("invalid deflate distance code".to_owned());
+                }
+                let distance =
+                    DISTANCE_BASE[index] as usize + input.take(DISTANCE_EXTRA[index])? as usize;
+                output.repeat(distance, length)?;
+            }
+            _ => return Err("invalid deflate length code".to_owned()),
+        }
+    }
+}
+
+/// The decompressed bytes, which may not grow past `limit`.
+struct Output {
+    bytes: Vec<u8>,
+    limit: usize,
+}
+
+impl Output {
+    /// Check that `count` more bytes fit under the limit.
+    fn reserve(&self, count: usize) -> Result<(), String> {
+        match self.bytes.len().checked_add(count) {
+            Some(length) if length <= self.limit => Ok(()),
+            _ => Err(format!("deflate stream inflates past {} bytes", self.limit)),
+        }
+    }
+
+    fn extend(&mut self, bytes: &[u8]) -> Result<(), String> {
+        self.reserve(bytes.len())?;
+        self.bytes.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Copy `length` bytes starting `distance` bytes back, which may
+    /// overlap the bytes being written.
+    fn repeat(&mut self, distance: usize, length: usize) -> Result<(), String> {
+        if distance > self.bytes.len() {
+            return Err("deflate distance reaches before the start".to_owned());
+        }
+        self.reserve(length)?;
+        let start = self.bytes.len() - distance;
+        for offset in 0..length {
+            self.bytes.push(self.bytes[start + offset]);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the codes of a block with fixed Huffman codes.
+fn fixed_codes() -> (Huffman, Huffman) {
+    let mut lengths = [8; 288];
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    (Huffman::new(&lengths), Huffman::new(&[5; 30]))
+}
+
+/// Read the codes of a block with dynamic Huffman codes from its header.
+fn dynamic_codes(input: &mut Bits) -> Result<(Huffman, Huffman), String> {
+    let literals = input.take(5)? as usize + 257;
+    let distances = input.take(5)? as usize + 1;
+    let code_lengths = input.take(4)? as usize + 4;
+    let mut lengths = [0; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_lengths] {
+        lengths[index] = input.take(3)? as u8;
+    }
+    let code = Huffman::new(&lengths);
+    let mut lengths = vec![];
+    while lengths.len() < literals + distances {
+        let (length, repeat) = match code.decode(input)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => match lengths.last() {
+                Some(&previous) => (previous, 3 + input.take(2)?),
+                None => return Err("deflate code length repeats nothing".to_owned()),
+            },
+            17 => (0, 3 + input.take(3)?),
+            _ => (0, 11 + input.take(7)?),
+        };
+        lengths.extend(std::iter::repeat_n(length, repeat as usize));
+    }
+    if lengths.len() > literals + distances {
+        return Err("too many deflate code lengths".to_owned());
+    }
+    Ok((
+        Huffman::new(&lengths[..literals]),
+        Huffman::new(&lengths[literals..]),
+    ))
+}
+
+/// A canonical Huffman code.
+struct Huffman {
+    /// The number of codes of each bit length.
+    counts: [u16; 16],
+    /// The symbols ordered by their codes.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// Build the code giving symbol `n` a code of `lengths[n]` bits, or no
+    /// code if that is zero.
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0; 16];
+        for &length in lengths {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols: Vec<u16> = (0..lengths.len() as u16)
+            .filter(|&symbol| lengths[symbol as usize] != 0)
+            .collect();
+        symbols.sort_by_key(|&symbol| lengths[symbol as usize]);
+        Self { counts, symbols }
+    }
+
+    /// Read one code from `input` and return its symbol.
+    fn decode(&self, input: &mut Bits) -> Result<u16, String> {
+        let (mut code, mut first, mut index) = (0, 0, 0);
+        for &count in &self.counts[1..] {
+            code |= input.take(1)? as usize;
+            let count = count as usize;
+            if code < first + count {
+                return Ok(self.symbols[index + code - first]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("invalid deflate code".to_owned())
+    }
+}
+
+/// Reads a byte slice bit by bit, least significant bit first.
+struct Bits<'a> {
+    bytes: &'a [u8],
+    /// The index of the next byte to load into `buffer`.
+    position: usize,
+    buffer: u32,
+    /// The number of bits in `buffer`.
+    count: u8,
+}
+
+impl<'a> Bits<'a> {
+    /// Take the next `count` bits, at most 16, as a number.
+    fn take(&mut self, count: u8) -> Result<u32, String> {
+        while self.count < count {
+            let byte = self
+                .bytes
+                .get(self.position)
+                .ok_or("truncated deflate stream")?;
+            self.buffer |= (*byte as u32) << self.count;
+            self.position += 1;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1 << count) - 1);
+        self.buffer >>= count;
+        self.count -= count;
+        Ok(value)
+    }
+
+    /// Drop the bits left of the current byte.
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+
+    /// Take the next `count` whole bytes.
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + count)
+            .ok_or("truncated deflate stream")?;
+        self.position += count;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The raw deflate stream of twenty small models, as compressed by zlib.
+    const DYNAMIC: &str = concat!(
+        "8dd1b10dc2300005d1de53fc11f8014270435a0a2a2688640b59c24642ee5076a7c800b9feaa7bf5",
+        "93f25b8f837e2a29eadebae69282d4969aa39efd5bdaeb16d650b7d0341c6878a4e18986671a8e34",
+        "bcd070a2e1150fe734d8c618c758c798c7d8c718c858c898c8fb467f",
+    );
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&text[index..index + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn inflates_a_stored_block() {
+        let bytes = hex("010500faff68656c6c6f");
+        assert_eq!(inflate(&bytes, usize::MAX).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn inflates_a_block_with_fixed_codes() {
+        let bytes = hex("cbcd4f49cd51082d4e2d52a856c84cb152f0cc2b51a80500");
+        assert_eq!(
+            inflate(&bytes, usize::MAX).unwrap(),
+            b"model User { id: Int }"
+        );
+    }
+
+    #[test]
+    fn inflates_a_block_with_dynamic_codes() {
+        let expected: String = (0..20)
+            .map(|index| format!("model M{} {{ id: Int @id\n  name: String?\n}}\n", index))
+            .collect();
+        let bytes = hex(DYNAMIC);
+        assert_eq!(bytes[0] & 0b111, 0b101);
+        assert_eq!(inflate(&bytes, usize::MAX).unwrap(), expected.as_bytes());
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let bytes = hex("cbcd4f49cd51082d4e2d52a8");
+        assert_eq!(
+            inflate(&bytes, usize::MAX),
+            Err("truncated deflate stream".to_owned())
+        );
+    }
+
+    #[test]
+    fn rejects_output_past_the_limit() {
+        let bytes = hex("cbcd4f49cd51082d4e2d52a856c84cb152f0cc2b51a80500");
+        assert_eq!(inflate(&bytes, 22).unwrap().len(), 22);
+        assert_eq!(
+            inflate(&bytes, 21),
+            Err("deflate stream inflates past 21 bytes".to_owned())
+        );
+        assert!(inflate(&hex("010500faff68656c6c6f"), 4).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+use super::FSUtil;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+type Files = Arc<RwLock<BTreeMap<String, String>>>;
+
+impl FSUtil {
+    /// Create a file system utility backed by `files`, a map from file
+    /// paths to their content, for tests and for tools that never touch the
+    /// disk.
+    ///
+    /// Paths use `/` separators and are normalized, so `a/./b/../c` and
+    /// `a/c` name the same file. Directories exist implicitly for every
+    /// prefix of a file path. Writes add or replace files in the map.
+    pub fn in_memory(files: HashMap<String, String>) -> Self {
+        let files: Files = Arc::new(RwLock::new(
+            files
+                .into_iter()
+                .map(|(path, content)| (normalize(&path), content))
+                .collect(),
+        ));
+        let read = files.clone();
+        let exists = files.clone();
+        let directory = files.clone();
+        let write = files.clone();
+        Self::new(
+            move |path| read.read().unwrap().get(&normalize(path)).cloned(),
+            move |path| {
+                let path = normalize(path);
+                let is_file = exists.read().unwrap().contains_key(&path);
+                is_file || is_directory(&exists, &path)
+            },
+            move |path| is_directory(&directory, &normalize(path)),
+            join,
+            parent,
+            |path| path.starts_with('/'),
+            move |path, contents| {
+                let path = normalize(path);
+                if is_directory(&write, &path) {
+                    return Err(format!("`{}` is a directory", path));
+                }
+                write.write().unwrap().insert(path, contents.to_owned());
+                Ok(())
+            },
+            move |path| {
+                let path = normalize(path);
+                if !is_directory(&files, &path) {
+                    return None;
+                }
+                let mut names: Vec<String> = files
+                    .read()
+                    .unwrap()
+                    .keys()
+                    .filter_map(|file| child_of(&path, file))
+                    .map(|name| name.to_owned())
+                    .collect();
+                names.sort();
+                names.dedup();
+                Some(names)
+            },
+        )
+        .with_canonicalize(|path| Some(normalize(path)))
+    }
+}
+
+/// Join `base` and the relative or absolute `path`, normalized. An empty
+/// `base` is the root of the relative paths.
+pub(super) fn join(base: &str, path: &str) -> String {
+    match path.starts_with('/') || base.is_empty() {
+        true => normalize(path),
+        false => normalize(&format!("{}/{}", base, path)),
+    }
+}
+
+/// Returns the directory containing `path`, normalized.
+pub(super) fn parent(path: &str) -> String {
+    let path = normalize(path);
+    match path.rsplit_once('/') {
+        Some(("", _)) if path != "/" => "/".to_owned(),
+        Some((parent, _)) => parent.to_owned(),
+        None if path == "/" => path,
+        None => "".to_owned(),
+    }
+}
+
+/// Resolve `.` and `..` segments and repeated or trailing slashes.
+pub(super) fn normalize(path: &str) -> String {
+    let mut segments: Vec<&str> = vec![];
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => (),
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            ".." if path.starts_with('/') => (),
+            segment => segments.push(segment),
+        }
+    }
+    let joined = segments.join("/");
+    match path.starts_with('/') {
+        true => format!("/{}", joined),
+        false => joined,
+    }
+}
+
+/// Returns the name of the entry of the directory `directory` that leads
+/// to `file`, if `file` is inside it.
+pub(super) fn child_of<'a>(directory: &str, file: &'a str) -> Option<&'a str> {
+    let rest = match directory {
+        "" => file,
+        "/" => file.strip_prefix('/')?,
+        _ => file.strip_prefix(directory)?.strip_prefix('/')?,
+    };
+    rest.split('/').next()
+}
+
+fn is_directory(files: &Files, path: &str) -> bool {
+    files
+        .read()
+        .unwrap()
+        .keys()
+        .any(|file| file != path && child_of(path, file).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::resolve_imports;
+
+    fn fs() -> FSUtil {
+        FSUtil::in_memory(HashMap::from([
+            (
+                "schema/main.teo".to_owned(),
+                "import { User } from \"./models/user.teo\"".to_owned(),
+            ),
+            (
+                "schema/models/user.teo".to_owned(),
+                "include \"../shared/./role.teo\"\nmodel User { id: Int }".to_owned(),
+            ),
+            (
+                "schema/shared/role.teo".to_owned(),
+                "enum Role { ADMIN }".to_owned(),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn answers_file_queries_from_the_map() {
+        let fs = fs();
+        assert_eq!(
+            fs.read_file("schema/shared/role.teo").as_deref(),
+            Some("enum Role { ADMIN }")
+        );
+        assert_eq!(fs.read_file("schema/missing.teo"), None);
+        assert!(fs.file_exists("schema/models/../main.teo"));
+        assert!(fs.file_exists("schema/models"));
+        assert!(!fs.file_exists("schema/main"));
+        assert!(fs.file_is_directory("schema/"));
+        assert!(!fs.file_is_directory("schema/main.teo"));
+        assert_eq!(
+            fs.read_dir("schema"),
+            Some(vec![
+                "main.teo".to_owned(),
+                "models".to_owned(),
+                "shared".to_owned()
+            ])
+        );
+        assert_eq!(fs.read_dir("schema/main.teo"), None);
+    }
+
+    #[test]
+    fn manipulates_paths_without_a_disk() {
+        let fs = fs();
+        assert_eq!(
+            fs.path_join("schema/models", "../shared/./role.teo"),
+            "schema/shared/role.teo"
+        );
+        assert_eq!(fs.path_join("schema", "/etc/schema.teo"), "/etc/schema.teo");
+        assert_eq!(fs.path_join("", "./schema.teo"), "schema.teo");
+        assert_eq!(
+            fs.parent_directory("schema/models/user.teo"),
+            "schema/models"
+        );
+        assert_eq!(fs.parent_directory("/schema.teo"), "/");
+        assert_eq!(fs.parent_directory("schema.teo"), "");
+        assert!(fs.path_is_absolute("/schema.teo"));
+        assert!(!fs.path_is_absolute("schema.teo"));
+    }
+
+    #[test]
+    fn writes_are_visible_to_reads() {
+        let fs = fs();
+        assert_eq!(fs.write_file("out/user.rs", "struct User;"), Ok(()));
+        assert_eq!(fs.read_file("out/user.rs").as_deref(), Some("struct User;"));
+        assert!(fs.file_is_directory("out"));
+        assert!(fs.write_file("schema", "").is_err());
+    }
+
+    #[test]
+    fn resolves_imports_entirely_in_memory() {
+        let graph = resolve_imports("schema/main.teo", &fs());
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "schema/main.teo",
+                "schema/models/user.teo",
+                "schema/shared/role.teo"
+            ]
+        );
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+    }
+
+    #[test]
+    fn resolves_imports_of_a_file_at_the_root() {
+        let fs = FSUtil::in_memory(HashMap::from([
+            ("main.teo".to_owned(), "import \"./a.teo\"".to_owned()),
+            ("a.teo".to_owned(), "model A { id: Int }".to_owned()),
+        ]));
+        let graph = resolve_imports("main.teo", &fs);
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["a.teo", "main.teo"]);
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+    }
+}
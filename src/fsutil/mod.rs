@@ -1,5 +1,25 @@
+pub mod cached;
+pub mod case;
+pub mod chained;
+#[cfg(feature = "zip")]
+mod inflate;
+#[allow(clippy::module_inception)]
 pub mod fsutil;
+#[cfg(feature = "wasm")]
+pub mod host;
+pub mod memory;
+pub mod relative;
+pub mod walk;
 #[cfg(feature = "stdfs")]
 pub mod default;
+#[cfg(feature = "zip")]
+pub mod zip;
 
-pub use fsutil::FSUtil;
+pub use chained::ChainedFSUtil;
+pub use fsutil::{EncodingError, FSUtil, FsError};
+#[cfg(feature = "wasm")]
+pub use host::HostImports;
+pub use relative::{normalize_path, relative_path};
+pub use walk::FileWalk;
+#[cfg(feature = "zip")]
+pub use zip::ZipFSUtil;
@@ -0,0 +1,132 @@
+/// Returns the path of `target` relative to the directory `base`, with
+/// `..` segments where `target` is outside of `base`.
+///
+/// The computation is lexical: both paths are split on `/` and `\`, and
+/// `.` and `..` segments are resolved before they are compared. Returns
+/// `.` when the paths are the same, and `target` unchanged when the paths
+/// have different roots, like a relative and an absolute path or two
+/// Windows drives. The result uses `/` separators.
+pub fn relative_path(base: &str, target: &str) -> String {
+    let (base_root, base) = split(base);
+    let (target_root, target_segments) = split(target);
+    if base_root != target_root {
+        return target.to_owned();
+    }
+    let common = base
+        .iter()
+        .zip(&target_segments)
+        .take_while(|(base, target)| base == target)
+        .count();
+    let mut segments = vec![".."; base.len() - common];
+    segments.extend(&target_segments[common..]);
+    match segments.is_empty() {
+        true => ".".to_owned(),
+        false => segments.join("/"),
+    }
+}
+
+/// Returns `path` in a form independent of the platform it was written
+/// on, for keying files: `/` separators, `.` and `..` segments resolved
+/// and repeated separators collapsed, with an uppercase drive letter.
+/// `C:\\schema\\main.teo` and `c:/schema/./main.teo` give the same answer.
+///
+/// The computation is lexical, like [`relative_path`]. An empty relative
+/// path gives `.`.
+pub fn normalize_path(path: &str) -> String {
+    let (root, segments) = split(path);
+    let joined = segments.join("/");
+    match root.as_str() {
+        "" if joined.is_empty() => ".".to_owned(),
+        "" => joined,
+        "/" => format!("/{}", joined),
+        drive if drive.ends_with('/') => format!("{}{}", drive, joined),
+        drive => format!("{}/{}", drive, joined),
+    }
+}
+
+/// Split `path` into its root, which is empty for relative paths, and its
+/// normalized segments.
+fn split(path: &str) -> (String, Vec<&str>) {
+    let mut parts = path.split(['/', '\\']).peekable();
+    let mut root = String::new();
+    if let Some(drive) = parts.next_if(|part| part.len() == 2 && part.ends_with(':')) {
+        root.push_str(&drive.to_ascii_uppercase());
+    }
+    if parts.next_if_eq(&"").is_some() && parts.peek().is_some() {
+        root.push('/');
+    }
+    let mut segments: Vec<&str> = vec![];
+    for part in parts {
+        match part {
+            "" | "." => (),
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            ".." if !root.is_empty() => (),
+            part => segments.push(part),
+        }
+    }
+    (root, segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walks_up_to_a_sibling_directory() {
+        assert_eq!(
+            relative_path("/project/schema", "/project/out/user.rs"),
+            "../out/user.rs"
+        );
+        assert_eq!(relative_path("schema/models", "schema/enums"), "../enums");
+    }
+
+    #[test]
+    fn handles_ancestors_and_descendants() {
+        assert_eq!(
+            relative_path("/project", "/project/schema/main.teo"),
+            "schema/main.teo"
+        );
+        assert_eq!(relative_path("/project/schema/models", "/project"), "../..");
+        assert_eq!(relative_path("/project/./schema/", "/project/schema"), ".");
+    }
+
+    #[test]
+    fn normalizes_windows_and_posix_paths_to_the_same_key() {
+        assert_eq!(
+            normalize_path("schema\\models\\user.teo"),
+            "schema/models/user.teo"
+        );
+        assert_eq!(
+            normalize_path("schema/./models//user.teo"),
+            "schema/models/user.teo"
+        );
+        assert_eq!(
+            normalize_path("c:\\project\\schema\\..\\schema\\main.teo"),
+            normalize_path("C:/project/schema/main.teo")
+        );
+        assert_eq!(
+            normalize_path("C:/project/schema/main.teo"),
+            "C:/project/schema/main.teo"
+        );
+        assert_eq!(normalize_path("/project/schema/"), "/project/schema");
+        assert_eq!(normalize_path("./"), ".");
+    }
+
+    #[test]
+    fn returns_the_target_when_the_roots_differ() {
+        assert_eq!(
+            relative_path("/project", "schema/main.teo"),
+            "schema/main.teo"
+        );
+        assert_eq!(
+            relative_path("C:\\project", "D:\\schema\\main.teo"),
+            "D:\\schema\\main.teo"
+        );
+        assert_eq!(
+            relative_path("c:\\project\\schema", "C:\\project\\out"),
+            "../out"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use super::FSUtil;
+use std::collections::HashSet;
+
+/// # File walk
+///
+/// The files found under a directory by [`FSUtil::find_files`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileWalk {
+    /// The paths of the files found, in the order they were visited.
+    /// Entries of each directory are visited sorted by name.
+    pub files: Vec<String>,
+    /// The paths of the directories that were not entered because they
+    /// lead back to a directory already visited, like a symlink to an
+    /// ancestor.
+    pub loops: Vec<String>,
+}
+
+impl FSUtil {
+    /// Find every file under the directory `root`, recursively.
+    ///
+    /// Directories are tracked by their real paths, as given by the
+    /// canonicalize function of the utility, so a link back to a directory
+    /// already visited is recorded in [`FileWalk::loops`] instead of being
+    /// walked forever. Without a canonicalize function directories are
+    /// tracked by their paths as written.
+    pub fn find_files(&self, root: &str) -> FileWalk {
+        let mut walk = FileWalk::default();
+        let mut visited = HashSet::new();
+        self.walk(root, &mut visited, &mut walk);
+        walk
+    }
+
+    fn walk(&self, directory: &str, visited: &mut HashSet<String>, walk: &mut FileWalk) {
+        let real = self
+            .canonicalize(directory)
+            .unwrap_or_else(|| directory.to_owned());
+        if !visited.insert(real) {
+            walk.loops.push(directory.to_owned());
+            return;
+        }
+        let Some(mut names) = self.read_dir(directory) else {
+            return;
+        };
+        names.sort();
+        for name in names {
+            let path = self.path_join(directory, &name);
+            if self.file_is_directory(&path) {
+                self.walk(&path, visited, walk);
+            } else {
+                walk.files.push(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn finds_files_recursively_in_order() {
+        let fs = FSUtil::in_memory(HashMap::from([
+            ("schema/b.teo".to_owned(), "".to_owned()),
+            ("schema/a/c.teo".to_owned(), "".to_owned()),
+            ("schema/a/d/e.teo".to_owned(), "".to_owned()),
+            ("other/f.teo".to_owned(), "".to_owned()),
+        ]));
+        let walk = fs.find_files("schema");
+        assert_eq!(
+            walk.files,
+            vec!["schema/a/c.teo", "schema/a/d/e.teo", "schema/b.teo"]
+        );
+        assert!(walk.loops.is_empty());
+    }
+
+    #[cfg(all(unix, feature = "stdfs"))]
+    #[test]
+    fn stops_at_a_symlink_to_an_ancestor() {
+        let root = std::env::temp_dir().join(format!("teo-walk-{}", std::process::id()));
+        let nested = root.join("models");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("user.teo"), "model User { }").unwrap();
+        std::os::unix::fs::symlink(&root, nested.join("loop")).unwrap();
+
+        let walk = FSUtil::default().find_files(root.to_str().unwrap());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let loop_path = nested.join("loop");
+        assert_eq!(
+            walk.files,
+            vec![nested.join("user.teo").to_str().unwrap().to_owned()]
+        );
+        assert_eq!(walk.loops, vec![loop_path.to_str().unwrap().to_owned()]);
+    }
+}
@@ -0,0 +1,394 @@
+use super::inflate::inflate;
+use super::memory::{child_of, join, normalize, parent};
+use super::FSUtil;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+/// The signature of the end of central directory record.
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+/// The signature of a central directory file header.
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x0201_4b50;
+/// The signature of a local file header.
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+/// The largest uncompressed size of an entry, which keeps an archive from
+/// inflating into more memory than any schema needs.
+const MAX_ENTRY_SIZE: usize = 64 * 1024 * 1024;
+
+/// # Zip file system utility
+///
+/// A zip archive read as a read-only file system whose root is the root of
+/// the archive, for schemas shipped inside a package. Entry names are used
+/// as paths, with `/` separators, and normalized like the paths of
+/// [`FSUtil::in_memory`]. Directories exist for every prefix of an entry
+/// name and for every directory entry. Stored and deflated entries are
+/// supported, and every entry is decompressed when the archive is
+/// opened, up to 64 MiB an entry. Convert it into an [`FSUtil`] to
+/// resolve imports from it.
+#[derive(Clone)]
+pub struct ZipFSUtil {
+    archive: Arc<Archive>,
+}
+
+struct Archive {
+    /// The content of each file entry by its normalized name.
+    files: BTreeMap<String, Vec<u8>>,
+    /// The normalized paths of the directories, including the root `""`.
+    directories: BTreeSet<String>,
+}
+
+impl ZipFSUtil {
+    /// Read the archive in `bytes`.
+    ///
+    /// Encrypted entries and compression methods other than stored and
+    /// deflate are errors, as are an entry larger than 64 MiB, an entry
+    /// whose content doesn't have the size its header records, and an
+    /// archive that isn't well formed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut files = BTreeMap::new();
+        let mut directories = BTreeSet::from(["".to_owned()]);
+        for entry in entries(bytes)? {
+            let path = normalize(&entry.name);
+            if entry.name.ends_with('/') {
+                directories.insert(path);
+                continue;
+            }
+            let mut directory = path.as_str();
+            while let Some((prefix, _)) = directory.rsplit_once('/') {
+                directories.insert(prefix.to_owned());
+                directory = prefix;
+            }
+            if entry.size > MAX_ENTRY_SIZE {
+                return Err(format!(
+                    "`{}` is larger than {} bytes",
+                    entry.name, MAX_ENTRY_SIZE
+                ));
+            }
+            let content = match entry.method {
+                0 => entry.data.to_vec(),
+                8 => inflate(entry.data, entry.size)
+                    .map_err(|error| format!("cannot read `{}`: {}", entry.name, error))?,
+                method => {
+                    return Err(format!(
+                        "`{}` uses the unsupported compression method {}",
+                        entry.name, method
+                    ))
+                }
+            };
+            if content.len() != entry.size {
+                return Err(format!(
+                    "`{}` isn't the {} bytes its header records",
+                    entry.name, entry.size
+                ));
+            }
+            files.insert(path, content);
+        }
+        Ok(Self {
+            archive: Arc::new(Archive { files, directories }),
+        })
+    }
+
+    /// Read the archive in the file at `path` on disk.
+    #[cfg(feature = "stdfs")]
+    pub fn open(path: &str) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|error| error.to_string())?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Read the file content of the entry at `path`. None if there is no
+    /// such entry or it isn't valid UTF-8.
+    pub fn read_file(&self, path: &str) -> Option<String> {
+        let content = self.archive.files.get(&normalize(path))?;
+        String::from_utf8(content.clone()).ok()
+    }
+
+    /// Read the content of the entry at `path` as bytes. None if there is
+    /// no such entry.
+    pub fn read_file_bytes(&self, path: &str) -> Option<Vec<u8>> {
+        self.archive.files.get(&normalize(path)).cloned()
+    }
+
+    /// Returns true if there is a file or directory at `path`.
+    pub fn file_exists(&self, path: &str) -> bool {
+        let path = normalize(path);
+        self.archive.files.contains_key(&path) || self.archive.directories.contains(&path)
+    }
+
+    /// Returns true if there is a directory at `path`.
+    pub fn file_is_directory(&self, path: &str) -> bool {
+        self.archive.directories.contains(&normalize(path))
+    }
+
+    /// Returns the sorted entry names of the directory at `path`. None if
+    /// there is no directory at `path`.
+    pub fn read_dir(&self, path: &str) -> Option<Vec<String>> {
+        let path = normalize(path);
+        if !self.archive.directories.contains(&path) {
+            return None;
+        }
+        let files = self.archive.files.keys();
+        let directories = self.archive.directories.iter().filter(|d| **d != path);
+        let names: BTreeSet<&str> = files
+            .chain(directories)
+            .filter_map(|entry| child_of(&path, entry))
+            .collect();
+        Some(names.into_iter().map(str::to_owned).collect())
+    }
+}
+
+impl From<ZipFSUtil> for FSUtil {
+    /// Answers file queries from the archive. Writes are errors.
+    fn from(zip: ZipFSUtil) -> Self {
+        let (read, bytes, exists, directory) = (zip.clone(), zip.clone(), zip.clone(), zip.clone());
+        FSUtil::new(
+            move |path| read.read_file(path),
+            move |path| exists.file_exists(path),
+            move |path| directory.file_is_directory(path),
+            join,
+            parent,
+            |path| path.starts_with('/'),
+            |path, _| Err(format!("`{}` is inside a read-only archive", path)),
+            move |path| zip.read_dir(path),
+        )
+        .with_read_file_bytes(move |path| bytes.read_file_bytes(path))
+        .with_canonicalize(|path| Some(normalize(path)))
+    }
+}
+
+/// An entry of the central directory.
+struct Entry<'a> {
+    name: String,
+    method: u16,
+    /// The uncompressed size the header records.
+    size: usize,
+    /// The compressed data of the entry.
+    data: &'a [u8],
+}
+
+/// Read the entries listed in the central directory of the archive in
+/// `bytes`.
+fn entries(bytes: &[u8]) -> Result<Vec<Entry<'_>>, String> {
+    let end = (0..=bytes.len().saturating_sub(22))
+        .rev()
+        .find(|&offset| u32_at(bytes, offset) == Some(END_OF_CENTRAL_DIRECTORY))
+        .ok_or("not a zip archive")?;
+    let count = u16_at(bytes, end + 10).ok_or("truncated zip archive")?;
+    let mut offset = u32_at(bytes, end + 16).ok_or("truncated zip archive")? as usize;
+    let mut entries = vec![];
+    for _ in 0..count {
+        entries.push(entry(bytes, &mut offset).ok_or("corrupt zip archive")??);
+    }
+    Ok(entries)
+}
+
+/// Read the central directory file header at `offset` and the entry it
+/// describes, and move `offset` past the header. None if the archive is
+/// truncated or corrupt there.
+fn entry<'a>(bytes: &'a [u8], offset: &mut usize) -> Option<Result<Entry<'a>, String>> {
+    let header = *offset;
+    if u32_at(bytes, header)? != CENTRAL_DIRECTORY_HEADER {
+        return None;
+    }
+    let flags = u16_at(bytes, header + 8)?;
+    let method = u16_at(bytes, header + 10)?;
+    let compressed_size = u32_at(bytes, header + 20)? as usize;
+    let size = u32_at(bytes, header + 24)? as usize;
+    let name_length = u16_at(bytes, header + 28)? as usize;
+    let extra_length = u16_at(bytes, header + 30)? as usize;
+    let comment_length = u16_at(bytes, header + 32)? as usize;
+    let local = u32_at(bytes, header + 42)? as usize;
+    let name_start = header.checked_add(46)?;
+    let name = bytes.get(name_start..name_start.checked_add(name_length)?)?;
+    let name = String::from_utf8_lossy(name).into_owned();
+    *offset = name_start + name_length + extra_length + comment_length;
+    if flags & 1 != 0 {
+        return Some(Err(format!("`{}` is encrypted", name)));
+    }
+    if u32_at(bytes, local)? != LOCAL_FILE_HEADER {
+        return None;
+    }
+    let start = local
+        .checked_add(30)?
+        .checked_add(u16_at(bytes, local + 26)? as usize)?
+        .checked_add(u16_at(bytes, local + 28)? as usize)?;
+    let data = bytes.get(start..start.checked_add(compressed_size)?)?;
+    Some(Ok(Entry {
+        name,
+        method,
+        size,
+        data,
+    }))
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolve::resolve_imports;
+
+    /// Build an archive of `entries`, each a name, a compression method,
+    /// the data as compressed with it and the uncompressed size.
+    fn archive(entries: &[(&str, u16, &[u8], u32)]) -> Vec<u8> {
+        let (mut bytes, mut directory) = (vec![], vec![]);
+        for (name, method, data, size) in entries {
+            let offset = bytes.len() as u32;
+            let mut fields = vec![];
+            fields.extend(20u16.to_le_bytes());
+            fields.extend(0u16.to_le_bytes());
+            fields.extend(method.to_le_bytes());
+            fields.extend([0; 8]);
+            fields.extend((data.len() as u32).to_le_bytes());
+            fields.extend(size.to_le_bytes());
+            fields.extend((name.len() as u16).to_le_bytes());
+            fields.extend(0u16.to_le_bytes());
+            bytes.extend(LOCAL_FILE_HEADER.to_le_bytes());
+            bytes.extend(&fields);
+            bytes.extend(name.as_bytes());
+            bytes.extend(*data);
+            directory.extend(CENTRAL_DIRECTORY_HEADER.to_le_bytes());
+            directory.extend(20u16.to_le_bytes());
+            directory.extend(&fields);
+            directory.extend([0; 10]);
+            directory.extend(offset.to_le_bytes());
+            directory.extend(name.as_bytes());
+        }
+        let offset = bytes.len() as u32;
+        let size = directory.len() as u32;
+        bytes.extend(directory);
+        bytes.extend(END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+        bytes.extend([0; 4]);
+        bytes.extend((entries.len() as u16).to_le_bytes());
+        bytes.extend((entries.len() as u16).to_le_bytes());
+        bytes.extend(size.to_le_bytes());
+        bytes.extend(offset.to_le_bytes());
+        bytes.extend([0; 2]);
+        bytes
+    }
+
+    /// `model User { id: Int }` compressed with fixed codes.
+    const USER: &[u8] = &[
+        0xcb, 0xcd, 0x4f, 0x49, 0xcd, 0x51, 0x08, 0x2d, 0x4e, 0x2d, 0x52, 0xa8, 0x56, 0xc8, 0x4c,
+        0xb1, 0x52, 0xf0, 0xcc, 0x2b, 0x51, 0xa8, 0x05, 0x00,
+    ];
+
+    fn zip() -> ZipFSUtil {
+        ZipFSUtil::from_bytes(&archive(&[
+            ("schema/", 0, b"", 0),
+            (
+                "schema/main.teo",
+                0,
+                b"import { User } from \"./models/user.teo\"",
+                40,
+            ),
+            ("schema/models/user.teo", 8, USER, 22),
+            ("schema/empty/", 0, b"", 0),
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_entries_as_files() {
+        let zip = zip();
+        assert_eq!(
+            zip.read_file("schema/models/../models/user.teo").as_deref(),
+            Some("model User { id: Int }")
+        );
+        assert_eq!(zip.read_file("schema/missing.teo"), None);
+        assert!(zip.file_exists("schema/main.teo"));
+        assert!(!zip.file_is_directory("schema/main.teo"));
+    }
+
+    #[test]
+    fn detects_directories_from_entry_names() {
+        let zip = zip();
+        assert!(zip.file_is_directory(""));
+        assert!(zip.file_is_directory("schema/models"));
+        assert!(zip.file_is_directory("schema/empty"));
+        assert_eq!(
+            zip.read_dir("schema"),
+            Some(vec![
+                "empty".to_owned(),
+                "main.teo".to_owned(),
+                "models".to_owned()
+            ])
+        );
+        assert_eq!(zip.read_dir("schema/empty"), Some(vec![]));
+        assert_eq!(zip.read_dir("schema/main.teo"), None);
+    }
+
+    #[test]
+    fn resolves_imports_inside_the_archive() {
+        let fs = FSUtil::from(zip());
+        let graph = resolve_imports("schema/main.teo", &fs);
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["schema/main.teo", "schema/models/user.teo"]);
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+        assert!(fs.write_file("schema/out.teo", "").is_err());
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_read() {
+        assert_eq!(
+            ZipFSUtil::from_bytes(b"model User {}").err().as_deref(),
+            Some("not a zip archive")
+        );
+        let bzip2 = archive(&[("schema.teo", 12, b"", 0)]);
+        assert_eq!(
+            ZipFSUtil::from_bytes(&bzip2).err().as_deref(),
+            Some("`schema.teo` uses the unsupported compression method 12")
+        );
+    }
+
+    #[test]
+    fn rejects_entries_of_the_wrong_size() {
+        let large = archive(&[("schema.teo", 8, USER, u32::MAX)]);
+        assert_eq!(
+            ZipFSUtil::from_bytes(&large).err().as_deref(),
+            Some("`schema.teo` is larger than 67108864 bytes")
+        );
+        let short = archive(&[("schema.teo", 8, USER, 21)]);
+        assert_eq!(
+            ZipFSUtil::from_bytes(&short).err().as_deref(),
+            Some("cannot read `schema.teo`: deflate stream inflates past 21 bytes")
+        );
+        let long = archive(&[("schema.teo", 8, USER, 23)]);
+        assert_eq!(
+            ZipFSUtil::from_bytes(&long).err().as_deref(),
+            Some("`schema.teo` isn't the 23 bytes its header records")
+        );
+        let stored = archive(&[("schema.teo", 0, b"model", 4)]);
+        assert!(ZipFSUtil::from_bytes(&stored).is_err());
+    }
+
+    #[test]
+    fn rejects_offsets_past_the_end() {
+        let mut bytes = archive(&[("schema.teo", 0, b"model", 5)]);
+        let header = bytes.len() - 22 - 56;
+        bytes[header + 42..header + 46].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            ZipFSUtil::from_bytes(&bytes).err().as_deref(),
+            Some("corrupt zip archive")
+        );
+    }
+
+    #[test]
+    fn resolves_imports_of_an_entry_at_the_root() {
+        let zip = ZipFSUtil::from_bytes(&archive(&[
+            ("main.teo", 0, b"import \"./user.teo\"", 19),
+            ("user.teo", 8, USER, 22),
+        ]))
+        .unwrap();
+        let graph = resolve_imports("main.teo", &FSUtil::from(zip));
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["main.teo", "user.teo"]);
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+    }
+}
@@ -0,0 +1,1268 @@
+use super::{JsonError, JsonValue};
+use crate::ast::{
+    Argument, BinaryOperator, ConfigDeclaration, ConfigItem, ConfigSection, Declaration, Decorator,
+    DictEntry, EnumDeclaration, EnumMember, ExportStatement, Expr, FieldDeclaration,
+    FunctionDeclaration, FunctionParameter, HandlerDeclaration, Identifier, ImportKind,
+    ImportStatement, LetDeclaration, Literal, LiteralValue, MiddlewareDeclaration,
+    ModelDeclaration, NamespaceDeclaration, SchemaFile, StringPart, StructDeclaration,
+    TypeAliasDeclaration, TypeExpr, TypeParam, UnaryOperator, UnknownDeclaration,
+};
+use std::ops::Range;
+
+/// Write `schema` as a JSON document.
+///
+/// Struct nodes are objects with an entry per field, named like the field
+/// and in the order the fields are declared. Variants of enum nodes, like
+/// [`Declaration`], [`Expr`] and [`TypeExpr`], are objects whose `"node"`
+/// entry names the variant, followed by the entries of its fields. Spans
+/// are `[start, end]` arrays of byte offsets, missing optional values are
+/// `null` and operators and import kinds are their variant names. The
+/// output is the same for the same tree.
+pub fn schema_to_json(schema: &SchemaFile) -> String {
+    ast_to_json(schema)
+}
+
+/// Write any node of the syntax tree, like a single model or expression,
+/// as a JSON document of the shape described at [`schema_to_json`].
+pub fn ast_to_json(node: &impl ToJson) -> String {
+    ast_to_json_with(node, SpanFormat::Array)
+}
+
+/// How [`ast_to_json_with`] writes spans.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpanFormat {
+    /// `[start, end]` arrays of byte offsets.
+    #[default]
+    Array,
+    /// `{ "start": start, "end": end }` objects of byte offsets.
+    Object,
+}
+
+/// Write a node like [`ast_to_json`], with its spans written in `spans`
+/// format. [`FromJson`] reads spans of either format.
+pub fn ast_to_json_with(node: &impl ToJson, spans: SpanFormat) -> String {
+    match spans {
+        SpanFormat::Array => node.to_json().to_string(),
+        SpanFormat::Object => span_objects(node.to_json()).to_string(),
+    }
+}
+
+/// Returns `json` with the `[start, end]` arrays of its span entries, the
+/// entries named `span` or ending in `_span`, written as objects.
+fn span_objects(json: JsonValue) -> JsonValue {
+    match json {
+        JsonValue::Object(entries) => JsonValue::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| match value {
+                    JsonValue::Array(bounds)
+                        if (key == "span" || key.ends_with("_span")) && bounds.len() == 2 =>
+                    {
+                        let [start, end] = <[JsonValue; 2]>::try_from(bounds).unwrap();
+                        let span = JsonValue::object([("start", start), ("end", end)]);
+                        (key, span)
+                    }
+                    value => (key, span_objects(value)),
+                })
+                .collect(),
+        ),
+        JsonValue::Array(elements) => {
+            JsonValue::Array(elements.into_iter().map(span_objects).collect())
+        }
+        json => json,
+    }
+}
+
+/// Read a schema written by [`schema_to_json`] back.
+pub fn schema_from_json(source: &str) -> Result<SchemaFile, JsonError> {
+    SchemaFile::from_json(&JsonValue::parse(source)?)
+}
+
+/// A value that can be written as JSON.
+pub trait ToJson {
+    /// Returns the JSON form of the value.
+    fn to_json(&self) -> JsonValue;
+}
+
+/// A value that can be read from the JSON written by its [`ToJson`]
+/// implementation.
+pub trait FromJson: Sized {
+    /// Read the value from `json`.
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError>;
+}
+
+/// Read the entry `key` of the object `json`.
+fn field<T: FromJson>(json: &JsonValue, key: &str) -> Result<T, JsonError> {
+    let value = json
+        .get(key)
+        .ok_or_else(|| JsonError::new(format!("missing `{}`", key)))?;
+    T::from_json(value).map_err(|error| JsonError::new(format!("{} in `{}`", error, key)))
+}
+
+/// Read the `"node"` entry that names the variant of an enum node.
+fn node(json: &JsonValue) -> Result<String, JsonError> {
+    field(json, "node")
+}
+
+fn unknown(kind: &str, name: &str) -> JsonError {
+    JsonError::new(format!("unknown {} `{}`", kind, name))
+}
+
+/// Prepend the `"node"` entry naming a variant to the object `json`.
+fn tagged(name: &str, json: JsonValue) -> JsonValue {
+    let mut entries = vec![("node".to_owned(), JsonValue::String(name.to_owned()))];
+    if let JsonValue::Object(fields) = json {
+        entries.extend(fields);
+    }
+    JsonValue::Object(entries)
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+}
+
+impl FromJson for String {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        json.as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| JsonError::new("expected a string"))
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Bool(*self)
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        match json {
+            JsonValue::Bool(value) => Ok(*value),
+            _ => Err(JsonError::new("expected a boolean")),
+        }
+    }
+}
+
+impl ToJson for usize {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Number(*self as f64)
+    }
+}
+
+impl FromJson for usize {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        json.as_usize()
+            .ok_or_else(|| JsonError::new("expected an offset"))
+    }
+}
+
+impl ToJson for Range<usize> {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(vec![self.start.to_json(), self.end.to_json()])
+    }
+}
+
+impl FromJson for Range<usize> {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        if let Some([start, end]) = json.as_array() {
+            return Ok(usize::from_json(start)?..usize::from_json(end)?);
+        }
+        match (json.get("start"), json.get("end")) {
+            (Some(start), Some(end)) => Ok(usize::from_json(start)?..usize::from_json(end)?),
+            _ => Err(JsonError::new("expected a `[start, end]` span")),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Some(value) => value.to_json(),
+            None => JsonValue::Null,
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        match json {
+            JsonValue::Null => Ok(None),
+            json => T::from_json(json).map(Some),
+        }
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(self.iter().map(ToJson::to_json).collect())
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        json.as_array()
+            .ok_or_else(|| JsonError::new("expected an array"))?
+            .iter()
+            .map(T::from_json)
+            .collect()
+    }
+}
+
+impl<T: ToJson> ToJson for Box<T> {
+    fn to_json(&self) -> JsonValue {
+        self.as_ref().to_json()
+    }
+}
+
+impl<T: FromJson> FromJson for Box<T> {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        T::from_json(json).map(Box::new)
+    }
+}
+
+impl ToJson for SchemaFile {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([("declarations", self.declarations.to_json())])
+    }
+}
+
+impl FromJson for SchemaFile {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(SchemaFile::new(field(json, "declarations")?))
+    }
+}
+
+impl ToJson for Declaration {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Declaration::Import(import) => tagged("Import", import.to_json()),
+            Declaration::Export(export) => tagged("Export", export.to_json()),
+            Declaration::Model(model) => tagged("Model", model.to_json()),
+            Declaration::Enum(r#enum) => tagged("Enum", r#enum.to_json()),
+            Declaration::Config(config) => tagged("Config", config.to_json()),
+            Declaration::ConfigSection(section) => tagged("ConfigSection", section.to_json()),
+            Declaration::TypeAlias(alias) => tagged("TypeAlias", alias.to_json()),
+            Declaration::Let(binding) => tagged("Let", binding.to_json()),
+            Declaration::Struct(r#struct) => tagged("Struct", r#struct.to_json()),
+            Declaration::Function(function) => tagged("Function", function.to_json()),
+            Declaration::Middleware(middleware) => tagged("Middleware", middleware.to_json()),
+            Declaration::Handler(handler) => tagged("Handler", handler.to_json()),
+            Declaration::Namespace(namespace) => tagged("Namespace", namespace.to_json()),
+            Declaration::Unknown(unknown) => tagged("Unknown", unknown.to_json()),
+        }
+    }
+}
+
+impl FromJson for Declaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(match node(json)?.as_str() {
+            "Import" => Declaration::Import(FromJson::from_json(json)?),
+            "Export" => Declaration::Export(FromJson::from_json(json)?),
+            "Model" => Declaration::Model(FromJson::from_json(json)?),
+            "Enum" => Declaration::Enum(FromJson::from_json(json)?),
+            "Config" => Declaration::Config(FromJson::from_json(json)?),
+            "ConfigSection" => Declaration::ConfigSection(FromJson::from_json(json)?),
+            "TypeAlias" => Declaration::TypeAlias(FromJson::from_json(json)?),
+            "Let" => Declaration::Let(FromJson::from_json(json)?),
+            "Struct" => Declaration::Struct(FromJson::from_json(json)?),
+            "Function" => Declaration::Function(FromJson::from_json(json)?),
+            "Middleware" => Declaration::Middleware(FromJson::from_json(json)?),
+            "Handler" => Declaration::Handler(FromJson::from_json(json)?),
+            "Namespace" => Declaration::Namespace(FromJson::from_json(json)?),
+            "Unknown" => Declaration::Unknown(FromJson::from_json(json)?),
+            name => return Err(unknown("declaration", name)),
+        })
+    }
+}
+
+impl ToJson for Identifier {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([("name", self.name.to_json()), ("span", self.span.to_json())])
+    }
+}
+
+impl FromJson for Identifier {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(Identifier {
+            name: field(json, "name")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for ImportKind {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(
+            match self {
+                ImportKind::Import => "Import",
+                ImportKind::Include => "Include",
+            }
+            .to_owned(),
+        )
+    }
+}
+
+impl FromJson for ImportKind {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        match String::from_json(json)?.as_str() {
+            "Import" => Ok(ImportKind::Import),
+            "Include" => Ok(ImportKind::Include),
+            name => Err(unknown("import kind", name)),
+        }
+    }
+}
+
+impl ToJson for ImportStatement {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("kind", self.kind.to_json()),
+            ("identifiers", self.identifiers.to_json()),
+            ("source", self.source.to_json()),
+            ("source_span", self.source_span.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for ImportStatement {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(ImportStatement {
+            kind: field(json, "kind")?,
+            identifiers: field(json, "identifiers")?,
+            source: field(json, "source")?,
+            source_span: field(json, "source_span")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for ExportStatement {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("names", self.names.to_json()),
+            ("source", self.source.to_json()),
+            ("source_span", self.source_span.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for ExportStatement {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(ExportStatement {
+            names: field(json, "names")?,
+            source: field(json, "source")?,
+            source_span: field(json, "source_span")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for ModelDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("doc", self.doc.to_json()),
+            ("decorators", self.decorators.to_json()),
+            ("name", self.name.to_json()),
+            ("type_parameters", self.type_parameters.to_json()),
+            ("fields", self.fields.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for ModelDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(ModelDeclaration {
+            doc: field(json, "doc")?,
+            decorators: field(json, "decorators")?,
+            name: field(json, "name")?,
+            type_parameters: field(json, "type_parameters")?,
+            fields: field(json, "fields")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for FieldDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("doc", self.doc.to_json()),
+            ("decorators", self.decorators.to_json()),
+            ("name", self.name.to_json()),
+            ("type_expr", self.type_expr.to_json()),
+            ("default", self.default.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for FieldDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(FieldDeclaration {
+            doc: field(json, "doc")?,
+            decorators: field(json, "decorators")?,
+            name: field(json, "name")?,
+            type_expr: field(json, "type_expr")?,
+            default: field(json, "default")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for EnumDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("doc", self.doc.to_json()),
+            ("decorators", self.decorators.to_json()),
+            ("name", self.name.to_json()),
+            ("backing", self.backing.to_json()),
+            ("members", self.members.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for EnumDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(EnumDeclaration {
+            doc: field(json, "doc")?,
+            decorators: field(json, "decorators")?,
+            name: field(json, "name")?,
+            backing: field(json, "backing")?,
+            members: field(json, "members")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for EnumMember {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("decorators", self.decorators.to_json()),
+            ("name", self.name.to_json()),
+            ("value", self.value.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for EnumMember {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(EnumMember {
+            decorators: field(json, "decorators")?,
+            name: field(json, "name")?,
+            value: field(json, "value")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for ConfigDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("kind", self.kind.to_json()),
+            ("name", self.name.to_json()),
+            ("items", self.items.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for ConfigDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(ConfigDeclaration {
+            kind: field(json, "kind")?,
+            name: field(json, "name")?,
+            items: field(json, "items")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for ConfigItem {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("key", self.key.to_json()),
+            ("value", self.value.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for ConfigItem {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(ConfigItem {
+            key: field(json, "key")?,
+            value: field(json, "value")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for ConfigSection {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("entries", self.entries.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for ConfigSection {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(ConfigSection {
+            name: field(json, "name")?,
+            entries: field(json, "entries")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for LetDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("type_expr", self.type_expr.to_json()),
+            ("value", self.value.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for LetDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(LetDeclaration {
+            name: field(json, "name")?,
+            type_expr: field(json, "type_expr")?,
+            value: field(json, "value")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for StructDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("type_parameters", self.type_parameters.to_json()),
+            ("fields", self.fields.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for StructDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(StructDeclaration {
+            name: field(json, "name")?,
+            type_parameters: field(json, "type_parameters")?,
+            fields: field(json, "fields")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for NamespaceDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("declarations", self.declarations.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for NamespaceDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(NamespaceDeclaration {
+            name: field(json, "name")?,
+            declarations: field(json, "declarations")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for UnknownDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("keyword", self.keyword.to_json()),
+            ("name", self.name.to_json()),
+            ("body_span", self.body_span.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for UnknownDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(UnknownDeclaration {
+            keyword: field(json, "keyword")?,
+            name: field(json, "name")?,
+            body_span: field(json, "body_span")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for FunctionDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("declare", self.declare.to_json()),
+            ("native", self.native.to_json()),
+            ("name", self.name.to_json()),
+            ("type_parameters", self.type_parameters.to_json()),
+            ("parameters", self.parameters.to_json()),
+            ("return_type", self.return_type.to_json()),
+            ("body", self.body.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for FunctionDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(FunctionDeclaration {
+            declare: field(json, "declare")?,
+            native: field(json, "native")?,
+            name: field(json, "name")?,
+            type_parameters: field(json, "type_parameters")?,
+            parameters: field(json, "parameters")?,
+            return_type: field(json, "return_type")?,
+            body: field(json, "body")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for FunctionParameter {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("type_expr", self.type_expr.to_json()),
+            ("default", self.default.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for FunctionParameter {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(FunctionParameter {
+            name: field(json, "name")?,
+            type_expr: field(json, "type_expr")?,
+            default: field(json, "default")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for TypeParam {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("bound", self.bound.to_json()),
+        ])
+    }
+}
+
+impl FromJson for TypeParam {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(TypeParam {
+            name: field(json, "name")?,
+            bound: field(json, "bound")?,
+        })
+    }
+}
+
+impl ToJson for TypeAliasDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("type_parameters", self.type_parameters.to_json()),
+            ("target", self.target.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for TypeAliasDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(TypeAliasDeclaration {
+            name: field(json, "name")?,
+            type_parameters: field(json, "type_parameters")?,
+            target: field(json, "target")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for MiddlewareDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("decorators", self.decorators.to_json()),
+            ("name", self.name.to_json()),
+            ("body", self.body.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for MiddlewareDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(MiddlewareDeclaration {
+            decorators: field(json, "decorators")?,
+            name: field(json, "name")?,
+            body: field(json, "body")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for HandlerDeclaration {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("decorators", self.decorators.to_json()),
+            ("name", self.name.to_json()),
+            ("body", self.body.to_json()),
+            ("span", self.span.to_json()),
+            ("source_order", self.source_order.to_json()),
+        ])
+    }
+}
+
+impl FromJson for HandlerDeclaration {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(HandlerDeclaration {
+            decorators: field(json, "decorators")?,
+            name: field(json, "name")?,
+            body: field(json, "body")?,
+            span: field(json, "span")?,
+            source_order: field(json, "source_order")?,
+        })
+    }
+}
+
+impl ToJson for Decorator {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("path", self.path.to_json()),
+            ("arguments", self.arguments.to_json()),
+            ("block", self.block.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for Decorator {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(Decorator {
+            path: field(json, "path")?,
+            arguments: field(json, "arguments")?,
+            block: field(json, "block")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for Argument {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("name", self.name.to_json()),
+            ("value", self.value.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for Argument {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(Argument {
+            name: field(json, "name")?,
+            value: field(json, "value")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for TypeExpr {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            TypeExpr::Named {
+                path,
+                arguments,
+                span,
+            } => JsonValue::object([
+                ("node", JsonValue::String("Named".to_owned())),
+                ("path", path.to_json()),
+                ("arguments", arguments.to_json()),
+                ("span", span.to_json()),
+            ]),
+            TypeExpr::Optional { inner, span } => JsonValue::object([
+                ("node", JsonValue::String("Optional".to_owned())),
+                ("inner", inner.to_json()),
+                ("span", span.to_json()),
+            ]),
+            TypeExpr::Array { inner, span } => JsonValue::object([
+                ("node", JsonValue::String("Array".to_owned())),
+                ("inner", inner.to_json()),
+                ("span", span.to_json()),
+            ]),
+            TypeExpr::Union { members, span } => JsonValue::object([
+                ("node", JsonValue::String("Union".to_owned())),
+                ("members", members.to_json()),
+                ("span", span.to_json()),
+            ]),
+            TypeExpr::Tuple { elements, span } => JsonValue::object([
+                ("node", JsonValue::String("Tuple".to_owned())),
+                ("elements", elements.to_json()),
+                ("span", span.to_json()),
+            ]),
+        }
+    }
+}
+
+impl FromJson for TypeExpr {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(match node(json)?.as_str() {
+            "Named" => TypeExpr::Named {
+                path: field(json, "path")?,
+                arguments: field(json, "arguments")?,
+                span: field(json, "span")?,
+            },
+            "Optional" => TypeExpr::Optional {
+                inner: field(json, "inner")?,
+                span: field(json, "span")?,
+            },
+            "Array" => TypeExpr::Array {
+                inner: field(json, "inner")?,
+                span: field(json, "span")?,
+            },
+            "Union" => TypeExpr::Union {
+                members: field(json, "members")?,
+                span: field(json, "span")?,
+            },
+            "Tuple" => TypeExpr::Tuple {
+                elements: field(json, "elements")?,
+                span: field(json, "span")?,
+            },
+            name => return Err(unknown("type", name)),
+        })
+    }
+}
+
+impl ToJson for Literal {
+    fn to_json(&self) -> JsonValue {
+        let (name, value) = match &self.value {
+            LiteralValue::String(value) => ("String", value),
+            LiteralValue::Number(value) => ("Number", value),
+            LiteralValue::Regex(value) => ("Regex", value),
+        };
+        JsonValue::object([
+            ("node", JsonValue::String(name.to_owned())),
+            ("value", value.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for Literal {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        let value = field(json, "value")?;
+        Ok(Literal {
+            value: match node(json)?.as_str() {
+                "String" => LiteralValue::String(value),
+                "Number" => LiteralValue::Number(value),
+                "Regex" => LiteralValue::Regex(value),
+                name => return Err(unknown("literal", name)),
+            },
+            span: field(json, "span")?,
+        })
+    }
+}
+
+impl ToJson for Expr {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Expr::Literal(literal) => JsonValue::object([
+                ("node", JsonValue::String("Literal".to_owned())),
+                ("literal", literal.to_json()),
+            ]),
+            Expr::Identifier(identifier) => JsonValue::object([
+                ("node", JsonValue::String("Identifier".to_owned())),
+                ("identifier", identifier.to_json()),
+            ]),
+            Expr::Array { elements, span } => JsonValue::object([
+                ("node", JsonValue::String("Array".to_owned())),
+                ("elements", elements.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::Tuple { elements, span } => JsonValue::object([
+                ("node", JsonValue::String("Tuple".to_owned())),
+                ("elements", elements.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::Dict { entries, span } => JsonValue::object([
+                ("node", JsonValue::String("Dict".to_owned())),
+                ("entries", entries.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::Unary {
+                operator,
+                operand,
+                span,
+            } => JsonValue::object([
+                ("node", JsonValue::String("Unary".to_owned())),
+                ("operator", operator.to_json()),
+                ("operand", operand.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::Binary {
+                operator,
+                lhs,
+                rhs,
+                span,
+            } => JsonValue::object([
+                ("node", JsonValue::String("Binary".to_owned())),
+                ("operator", operator.to_json()),
+                ("lhs", lhs.to_json()),
+                ("rhs", rhs.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::Member {
+                object,
+                property,
+                span,
+            } => JsonValue::object([
+                ("node", JsonValue::String("Member".to_owned())),
+                ("object", object.to_json()),
+                ("property", property.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::OptionalMember {
+                object,
+                property,
+                span,
+            } => JsonValue::object([
+                ("node", JsonValue::String("OptionalMember".to_owned())),
+                ("object", object.to_json()),
+                ("property", property.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::Index {
+                object,
+                index,
+                span,
+            } => JsonValue::object([
+                ("node", JsonValue::String("Index".to_owned())),
+                ("object", object.to_json()),
+                ("index", index.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::Call {
+                callee,
+                arguments,
+                span,
+            } => JsonValue::object([
+                ("node", JsonValue::String("Call".to_owned())),
+                ("callee", callee.to_json()),
+                ("arguments", arguments.to_json()),
+                ("span", span.to_json()),
+            ]),
+            Expr::InterpolatedString { parts, span } => JsonValue::object([
+                ("node", JsonValue::String("InterpolatedString".to_owned())),
+                ("parts", parts.to_json()),
+                ("span", span.to_json()),
+            ]),
+        }
+    }
+}
+
+impl FromJson for Expr {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(match node(json)?.as_str() {
+            "Literal" => Expr::Literal(field(json, "literal")?),
+            "Identifier" => Expr::Identifier(field(json, "identifier")?),
+            "Array" => Expr::Array {
+                elements: field(json, "elements")?,
+                span: field(json, "span")?,
+            },
+            "Tuple" => Expr::Tuple {
+                elements: field(json, "elements")?,
+                span: field(json, "span")?,
+            },
+            "Dict" => Expr::Dict {
+                entries: field(json, "entries")?,
+                span: field(json, "span")?,
+            },
+            "Unary" => Expr::Unary {
+                operator: field(json, "operator")?,
+                operand: field(json, "operand")?,
+                span: field(json, "span")?,
+            },
+            "Binary" => Expr::Binary {
+                operator: field(json, "operator")?,
+                lhs: field(json, "lhs")?,
+                rhs: field(json, "rhs")?,
+                span: field(json, "span")?,
+            },
+            "Member" => Expr::Member {
+                object: field(json, "object")?,
+                property: field(json, "property")?,
+                span: field(json, "span")?,
+            },
+            "OptionalMember" => Expr::OptionalMember {
+                object: field(json, "object")?,
+                property: field(json, "property")?,
+                span: field(json, "span")?,
+            },
+            "Index" => Expr::Index {
+                object: field(json, "object")?,
+                index: field(json, "index")?,
+                span: field(json, "span")?,
+            },
+            "Call" => Expr::Call {
+                callee: field(json, "callee")?,
+                arguments: field(json, "arguments")?,
+                span: field(json, "span")?,
+            },
+            "InterpolatedString" => Expr::InterpolatedString {
+                parts: field(json, "parts")?,
+                span: field(json, "span")?,
+            },
+            name => return Err(unknown("expression", name)),
+        })
+    }
+}
+
+impl ToJson for StringPart {
+    fn to_json(&self) -> JsonValue {
+        match self {
+            StringPart::Text(text) => JsonValue::object([
+                ("node", JsonValue::String("Text".to_owned())),
+                ("text", text.to_json()),
+            ]),
+            StringPart::Expr(expr) => JsonValue::object([
+                ("node", JsonValue::String("Expr".to_owned())),
+                ("expr", expr.to_json()),
+            ]),
+        }
+    }
+}
+
+impl FromJson for StringPart {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(match node(json)?.as_str() {
+            "Text" => StringPart::Text(field(json, "text")?),
+            "Expr" => StringPart::Expr(field(json, "expr")?),
+            name => return Err(unknown("string part", name)),
+        })
+    }
+}
+
+impl ToJson for DictEntry {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object([
+            ("key", self.key.to_json()),
+            ("quoted", self.quoted.to_json()),
+            ("value", self.value.to_json()),
+            ("span", self.span.to_json()),
+        ])
+    }
+}
+
+impl FromJson for DictEntry {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        Ok(DictEntry {
+            key: field(json, "key")?,
+            quoted: field(json, "quoted")?,
+            value: field(json, "value")?,
+            span: field(json, "span")?,
+        })
+    }
+}
+
+const UNARY_OPERATORS: [(UnaryOperator, &str); 2] = [
+    (UnaryOperator::Not, "Not"),
+    (UnaryOperator::Negate, "Negate"),
+];
+
+const BINARY_OPERATORS: [(BinaryOperator, &str); 12] = [
+    (BinaryOperator::Or, "Or"),
+    (BinaryOperator::And, "And"),
+    (BinaryOperator::Equal, "Equal"),
+    (BinaryOperator::NotEqual, "NotEqual"),
+    (BinaryOperator::BitOr, "BitOr"),
+    (BinaryOperator::BitXor, "BitXor"),
+    (BinaryOperator::BitAnd, "BitAnd"),
+    (BinaryOperator::Add, "Add"),
+    (BinaryOperator::Subtract, "Subtract"),
+    (BinaryOperator::Multiply, "Multiply"),
+    (BinaryOperator::Divide, "Divide"),
+    (BinaryOperator::Modulo, "Modulo"),
+];
+
+impl ToJson for UnaryOperator {
+    fn to_json(&self) -> JsonValue {
+        let (_, name) = UNARY_OPERATORS.iter().find(|(o, _)| o == self).unwrap();
+        JsonValue::String(name.to_string())
+    }
+}
+
+impl FromJson for UnaryOperator {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        let name = String::from_json(json)?;
+        UNARY_OPERATORS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(operator, _)| *operator)
+            .ok_or_else(|| unknown("operator", &name))
+    }
+}
+
+impl ToJson for BinaryOperator {
+    fn to_json(&self) -> JsonValue {
+        let (_, name) = BINARY_OPERATORS.iter().find(|(o, _)| o == self).unwrap();
+        JsonValue::String(name.to_string())
+    }
+}
+
+impl FromJson for BinaryOperator {
+    fn from_json(json: &JsonValue) -> Result<Self, JsonError> {
+        let name = String::from_json(json)?;
+        BINARY_OPERATORS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(operator, _)| *operator)
+            .ok_or_else(|| unknown("operator", &name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    #[test]
+    fn writes_a_stable_document() {
+        let schema = parse_schema("model User {\n  @id id: Int?\n}", "schema.teo").schema;
+        assert_eq!(
+            schema_to_json(&schema),
+            concat!(
+                r#"{"declarations":[{"node":"Model","doc":null,"decorators":[],"#,
+                r#""name":{"name":"User","span":[6,10]},"type_parameters":[],"#,
+                r#""fields":[{"doc":null,"#,
+                r#""decorators":[{"path":[{"name":"id","span":[16,18]}],"#,
+                r#""arguments":null,"block":false,"span":[15,18]}],"#,
+                r#""name":{"name":"id","span":[19,21]},"#,
+                r#""type_expr":{"node":"Optional","inner":{"node":"Named","#,
+                r#""path":[{"name":"Int","span":[23,26]}],"arguments":[],"#,
+                r#""span":[23,26]},"#,
+                r#""span":[23,27]},"default":null,"span":[19,27]}],"#,
+                r#""span":[0,29],"source_order":0}]}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let source = r#"
+import { Role } from "./role"
+export { User }
+config server { bind: ("0.0.0.0", 5050) }
+[database]
+url = "postgres://localhost"
+type Id = Int | String
+type Pair<A, B> = (A, B?)
+/// A user.
+@map("users")
+model User {
+  @default(-1 + 2 * 3) id: Id[]?
+  tags: String[] = ["a\n", obj.items[0], !flag, obj?.tags]
+  label: String = "user ${id + 1}\t"
+}
+enum Role { ADMIN = "admin" }
+middleware log { log(level: 1) }
+handler create { /^a+$/ }
+"#;
+        let result = parse_schema(source, "schema.teo");
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        let json = schema_to_json(&result.schema);
+        assert_eq!(schema_from_json(&json), Ok(result.schema));
+    }
+
+    #[test]
+    fn writes_a_single_node() {
+        let schema = parse_schema("enum Role { ADMIN = \"admin\" }", "schema.teo").schema;
+        let Declaration::Enum(r#enum) = &schema.declarations[0] else {
+            panic!("expected an enum");
+        };
+        let json = JsonValue::parse(&ast_to_json(r#enum)).unwrap();
+        assert_eq!(
+            json.get("name").unwrap().get("name").unwrap().as_str(),
+            Some("Role")
+        );
+        let member = &json.get("members").unwrap().as_array().unwrap()[0];
+        let value = member.get("value").unwrap();
+        assert_eq!(value.get("node").unwrap().as_str(), Some("String"));
+        assert_eq!(value.get("value").unwrap().as_str(), Some("admin"));
+        assert_eq!(FromJson::from_json(value.get("span").unwrap()), Ok(20..27));
+        let declaration = ast_to_json(&schema.declarations[0]);
+        assert!(declaration.starts_with(r#"{"node":"Enum","#));
+    }
+
+    #[test]
+    fn writes_spans_as_objects() {
+        let schema = parse_schema("model User { id: Int }", "schema.teo").schema;
+        let json = ast_to_json_with(&schema, SpanFormat::Object);
+        let value = JsonValue::parse(&json).unwrap();
+        let model = &value.get("declarations").unwrap().as_array().unwrap()[0];
+        let span = model.get("span").unwrap();
+        assert_eq!(span.get("start").unwrap().as_usize(), Some(0));
+        assert_eq!(span.get("end").unwrap().as_usize(), Some(22));
+        assert!(json.contains(r#""span":{"start":6,"end":10}"#));
+        assert!(!json.contains(r#""span":["#));
+        assert_eq!(SchemaFile::from_json(&value), Ok(schema));
+    }
+
+    #[test]
+    fn reports_a_document_of_the_wrong_shape() {
+        let error = schema_from_json(r#"{"declarations":[{"node":"Table"}]}"#).unwrap_err();
+        assert_eq!(
+            error.message,
+            "unknown declaration `Table` in `declarations`"
+        );
+        let error = schema_from_json(r#"{"declarations":[{"node":"Model"}]}"#).unwrap_err();
+        assert_eq!(error.message, "missing `doc` in `declarations`");
+    }
+}
@@ -0,0 +1,28 @@
+use std::fmt::{Display, Formatter};
+
+/// # JSON error
+///
+/// A JSON document that could not be read, or that does not have the
+/// shape of the value it was read as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    /// A human readable description of the problem.
+    pub message: String,
+}
+
+impl JsonError {
+    /// Create a new JSON error.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for JsonError {}
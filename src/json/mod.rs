@@ -0,0 +1,9 @@
+pub mod ast;
+pub mod error;
+pub mod value;
+
+pub use ast::{
+    ast_to_json, ast_to_json_with, schema_from_json, schema_to_json, FromJson, SpanFormat, ToJson,
+};
+pub use error::JsonError;
+pub use value::JsonValue;
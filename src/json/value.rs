@@ -0,0 +1,386 @@
+use super::JsonError;
+use std::fmt::{Display, Formatter, Write};
+
+/// # JSON value
+///
+/// A JSON document. Object keys keep the order they were inserted or
+/// read in, so a value is always written the same way.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// How deep arrays and objects may be nested in a document that is
+    /// read, like the [nesting limit](crate::parser::Parser::with_max_depth)
+    /// of schemas.
+    pub const MAX_DEPTH: usize = 256;
+
+    /// Create an object from `entries`, keeping their order.
+    pub fn object<'a>(entries: impl IntoIterator<Item = (&'a str, JsonValue)>) -> Self {
+        JsonValue::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        )
+    }
+
+    /// Returns the value of the entry `key` if this is an object that has
+    /// one.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries
+                .iter()
+                .find(|(entry, _)| entry == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if this is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if this is an array.
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if this is a number that fits in a `usize`
+    /// without losing anything.
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            JsonValue::Number(value)
+                if *value >= 0.0 && value.fract() == 0.0 && *value <= usize::MAX as f64 =>
+            {
+                Some(*value as usize)
+            }
+            _ => None,
+        }
+    }
+
+    /// Read a JSON document from `source`. Arrays and objects nested more
+    /// than [`JsonValue::MAX_DEPTH`] levels deep are an error.
+    pub fn parse(source: &str) -> Result<Self, JsonError> {
+        let mut reader = Reader {
+            source,
+            position: 0,
+            depth: 0,
+        };
+        let value = reader.value()?;
+        reader.whitespace();
+        match reader.position == source.len() {
+            true => Ok(value),
+            false => Err(reader.error("expected the end of the document")),
+        }
+    }
+}
+
+impl Display for JsonValue {
+    /// Writes the value as compact JSON, without any whitespace.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Bool(value) => write!(f, "{}", value),
+            JsonValue::Number(value) if value.is_finite() => write!(f, "{}", value),
+            JsonValue::Number(_) => f.write_str("null"),
+            JsonValue::String(value) => write_string(f, value),
+            JsonValue::Array(elements) => {
+                f.write_char('[')?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                f.write_char(']')
+            }
+            JsonValue::Object(entries) => {
+                f.write_char('{')?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut Formatter<'_>, value: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+struct Reader<'a> {
+    source: &'a str,
+    position: usize,
+    /// The number of arrays and objects being read.
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, message: &str) -> JsonError {
+        JsonError::new(format!("{} at offset {}", message, self.position))
+    }
+
+    /// Run `read` one nesting level deeper, or fail with `nesting too
+    /// deep` if that is past the limit.
+    fn nested(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<JsonValue, JsonError>,
+    ) -> Result<JsonValue, JsonError> {
+        if self.depth >= JsonValue::MAX_DEPTH {
+            return Err(self.error("nesting too deep"));
+        }
+        self.depth += 1;
+        let result = read(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        match self.source[self.position..].starts_with(keyword) {
+            true => {
+                self.position += keyword.len();
+                Ok(value)
+            }
+            false => Err(self.error("expected a value")),
+        }
+    }
+
+    fn value(&mut self) -> Result<JsonValue, JsonError> {
+        self.whitespace();
+        match self.peek() {
+            Some('n') => self.keyword("null", JsonValue::Null),
+            Some('t') => self.keyword("true", JsonValue::Bool(true)),
+            Some('f') => self.keyword("false", JsonValue::Bool(false)),
+            Some('"') => Ok(JsonValue::String(self.string()?)),
+            Some('[') => self.nested(Self::array),
+            Some('{') => self.nested(Self::object),
+            Some('-' | '0'..='9') => self.number(),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.position;
+        while matches!(self.peek(), Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+            self.position += 1;
+        }
+        match self.source[start..self.position].parse() {
+            Ok(value) => Ok(JsonValue::Number(value)),
+            Err(_) => {
+                self.position = start;
+                Err(self.error("invalid number"))
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.next();
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => {
+                    let c = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    value.push(c);
+                }
+                Some(c) if c >= ' ' => value.push(c),
+                _ => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    /// Read the digits of a `\u` escape, and of the low surrogate that
+    /// follows a high one.
+    fn unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.hex4()?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if !self.source[self.position..].starts_with("\\u") {
+                    return Err(self.error("unpaired surrogate"));
+                }
+                self.position += 2;
+                let low = self.hex4()?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return Err(self.error("unpaired surrogate"));
+                }
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            }
+            code => code,
+        };
+        char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let digits = self
+            .source
+            .get(self.position..self.position + 4)
+            .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        self.position += 4;
+        Ok(u32::from_str_radix(digits, 16).unwrap())
+    }
+
+    fn array(&mut self) -> Result<JsonValue, JsonError> {
+        self.next();
+        let mut elements = vec![];
+        self.whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(JsonValue::Array(elements));
+        }
+        loop {
+            elements.push(self.value()?);
+            self.whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(elements));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue, JsonError> {
+        self.next();
+        let mut entries = vec![];
+        self.whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(JsonValue::Object(entries));
+        }
+        loop {
+            self.whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a key"));
+            }
+            let key = self.string()?;
+            self.whitespace();
+            if self.peek() != Some(':') {
+                return Err(self.error("expected `:`"));
+            }
+            self.position += 1;
+            entries.push((key, self.value()?));
+            self.whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(entries));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_compact_json_in_insertion_order() {
+        let value = JsonValue::object([
+            ("name", JsonValue::String("a \"b\"\n".to_owned())),
+            ("span", JsonValue::Array(vec![JsonValue::Number(3.0)])),
+            ("value", JsonValue::Null),
+            ("flag", JsonValue::Bool(true)),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"name":"a \"b\"\n","span":[3],"value":null,"flag":true}"#
+        );
+    }
+
+    #[test]
+    fn reads_what_it_writes() {
+        let source = r#" { "b": [1, -2.5e1, "é😀\t"], "a": {} , "c": false } "#;
+        let value = JsonValue::parse(source).unwrap();
+        assert_eq!(
+            value.get("b").unwrap().as_array().unwrap()[0].as_usize(),
+            Some(1)
+        );
+        assert_eq!(
+            value.get("b").unwrap().as_array().unwrap()[2].as_str(),
+            Some("é😀\t")
+        );
+        assert_eq!(JsonValue::parse(&value.to_string()), Ok(value));
+    }
+
+    #[test]
+    fn reports_where_a_document_is_invalid() {
+        let error = JsonValue::parse(r#"{"a": [1, 2}"#).unwrap_err();
+        assert_eq!(error.message, "expected `,` or `]` at offset 11");
+        assert!(JsonValue::parse("[1] 2").is_err());
+        assert!(JsonValue::parse(r#""\ud800""#).is_err());
+    }
+
+    #[test]
+    fn rejects_nesting_past_the_limit() {
+        let error = JsonValue::parse(&"[".repeat(200_000)).unwrap_err();
+        assert_eq!(error.message, "nesting too deep at offset 256");
+        let deepest = format!("{}{}", "[".repeat(256), "]".repeat(256));
+        assert!(JsonValue::parse(&deepest).is_ok());
+        let object = format!("{}1{}", r#"{"a":"#.repeat(257), "}".repeat(257));
+        assert!(JsonValue::parse(&object).is_err());
+    }
+}
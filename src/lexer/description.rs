@@ -0,0 +1,106 @@
+use super::Token;
+
+impl Token {
+    /// Returns a short description of the token for messages, like
+    /// "closing brace `}`" or "identifier". Tokens of a fixed text include
+    /// the text, the others only name their kind.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Token::Import => "keyword `import`",
+            Token::Include => "keyword `include`",
+            Token::From => "keyword `from`",
+            Token::Export => "keyword `export`",
+            Token::Let => "keyword `let`",
+            Token::Var => "keyword `var`",
+            Token::Enum => "keyword `enum`",
+            Token::Model => "keyword `model`",
+            Token::Type => "keyword `type`",
+            Token::Function => "keyword `function`",
+            Token::Struct => "keyword `struct`",
+            Token::Config => "keyword `config`",
+            Token::Namespace => "keyword `namespace`",
+            Token::Native => "keyword `native`",
+            Token::Declare => "keyword `declare`",
+            Token::Where => "keyword `where`",
+            Token::Middleware => "keyword `middleware`",
+            Token::Handler => "keyword `handler`",
+            Token::Plus => "plus sign `+`",
+            Token::Minus => "minus sign `-`",
+            Token::Star => "asterisk `*`",
+            Token::Slash => "slash `/`",
+            Token::Percent => "percent sign `%`",
+            Token::Caret => "caret `^`",
+            Token::Ampersand => "ampersand `&`",
+            Token::AmpAmp => "logical and `&&`",
+            Token::AmpAmpEqual => "and assignment `&&=`",
+            Token::Pipe => "vertical bar `|`",
+            Token::PipePipe => "logical or `||`",
+            Token::PipePipeEqual => "or assignment `||=`",
+            Token::Bang => "exclamation mark `!`",
+            Token::Question => "question mark `?`",
+            Token::At => "at sign `@`",
+            Token::AtAt => "double at sign `@@`",
+            Token::Period => "period `.`",
+            Token::Colon => "colon `:`",
+            Token::ColonColon => "double colon `::`",
+            Token::Comma => "comma `,`",
+            Token::BraceOpen => "opening brace `{`",
+            Token::BraceClose => "closing brace `}`",
+            Token::ParenOpen => "opening parenthesis `(`",
+            Token::ParenClose => "closing parenthesis `)`",
+            Token::BracketOpen => "opening bracket `[`",
+            Token::BracketClose => "closing bracket `]`",
+            Token::AngleOpen => "less-than sign `<`",
+            Token::AngleClose => "greater-than sign `>`",
+            Token::Equal => "equals sign `=`",
+            Token::EqualEqual => "double equals sign `==`",
+            Token::BangEqual => "not-equal sign `!=`",
+            Token::Identifier => "identifier",
+            Token::Number => "number",
+            Token::Regex => "regex literal",
+            Token::String => "string literal",
+            Token::LineComment => "comment",
+            Token::DocComment => "doc comment",
+            Token::Whitespace => "whitespace",
+            Token::UnterminatedString => "unterminated string literal",
+            Token::Error => "unrecognized input",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize_lossless;
+
+    #[test]
+    fn describes_tokens() {
+        assert_eq!(Token::BraceClose.description(), "closing brace `}`");
+        assert_eq!(Token::Identifier.description(), "identifier");
+        assert_eq!(Token::String.description(), "string literal");
+        assert_eq!(Token::Model.description(), "keyword `model`");
+        assert_eq!(Token::Error.description(), "unrecognized input");
+    }
+
+    #[test]
+    fn every_token_has_its_own_description() {
+        // The match of `description` has no wildcard arm, so a new token
+        // doesn't compile without one. This checks the descriptions of
+        // the tokens of a source using each of them differ.
+        let source = "import include from export let var enum model type function \
+                      struct config namespace native declare where middleware handler \
+                      + - * / % ^ & && &&= | || ||= ! ? @ @@ . : :: , { } ( ) [ ] < > = == != \
+                      /a/ name 1 \"s\" // c\n/// d\n# \"t";
+        let mut kinds: Vec<Token> = vec![];
+        for (token, _) in tokenize_lossless(source) {
+            if !kinds.contains(&token) {
+                kinds.push(token);
+            }
+        }
+        let mut descriptions: Vec<&str> = kinds.iter().map(Token::description).collect();
+        descriptions.sort_unstable();
+        descriptions.dedup();
+        assert_eq!(kinds.len(), 58);
+        assert_eq!(descriptions.len(), kinds.len());
+    }
+}
@@ -0,0 +1,54 @@
+use super::{Token, TokenKind};
+use logos::Logos;
+
+/// Returns true if `text` is a reserved keyword, like `model` or `import`,
+/// which cannot be used as a name.
+pub fn is_keyword(text: &str) -> bool {
+    keyword_token(text).is_some()
+}
+
+/// Returns the token of the keyword `text`, or None if it isn't one.
+///
+/// The lexer itself decides: `text` is a keyword if it lexes as a single
+/// token other than an identifier and is spelled like an identifier. So
+/// every keyword added to [`Token`] is recognized without being listed
+/// here too.
+pub fn keyword_token(text: &str) -> Option<TokenKind> {
+    let first = text.chars().next()?;
+    if !first.is_ascii_alphabetic() || !text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    let mut lexer = Token::lexer(text);
+    let token = lexer.next()?.ok()?;
+    if lexer.span() != (0..text.len()) || lexer.next().is_some() {
+        return None;
+    }
+    (token != Token::Identifier).then_some(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_keywords() {
+        assert_eq!(keyword_token("import"), Some(Token::Import));
+        assert_eq!(keyword_token("model"), Some(Token::Model));
+        assert_eq!(keyword_token("enum"), Some(Token::Enum));
+        assert_eq!(keyword_token("struct"), Some(Token::Struct));
+        assert_eq!(keyword_token("declare"), Some(Token::Declare));
+        assert_eq!(keyword_token("where"), Some(Token::Where));
+        assert!(is_keyword("handler"));
+    }
+
+    #[test]
+    fn rejects_names_and_near_misses() {
+        for text in [
+            "User", "Model", "models", "imports", "declared", "model_", "enum1", "", "model ",
+            "@id", "&&",
+        ] {
+            assert!(!is_keyword(text), "{:?}", text);
+        }
+    }
+}
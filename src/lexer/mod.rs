@@ -1 +1,17 @@
+pub mod description;
+pub mod keyword;
+#[allow(clippy::module_inception)]
 pub mod lexer;
+pub mod number;
+pub mod semantic;
+pub mod stream;
+pub mod string;
+pub mod token_stream;
+
+pub use keyword::{is_keyword, keyword_token};
+pub use lexer::{tokenize, tokenize_lossless, LexErrorKind, Token};
+pub use number::invalid_number_digit;
+pub use semantic::{semantic_tokens, SemanticCategory};
+pub use stream::{lex, lex_range, lex_to_vec, lexemes, LexError, Lexeme, TokenKind};
+pub use string::{string_value, unescape};
+pub use token_stream::TokenStream;
@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+/// Returns the span, relative to the start of `text`, and a description
+/// of the first problem of the number literal `text`, if it has one.
+///
+/// Only literals with a `0x`, `0b` or `0o` radix prefix can be invalid:
+/// the lexer reads the prefix and every letter and digit after it as one
+/// number, so that a digit of the wrong radix, like the `G` of `0xG1`, is
+/// reported instead of being read as a separate identifier.
+pub fn invalid_number_digit(text: &str) -> Option<(Range<usize>, String)> {
+    let (radix, name) = match text.get(..2) {
+        Some("0x") => (16, "hexadecimal"),
+        Some("0b") => (2, "binary"),
+        Some("0o") => (8, "octal"),
+        _ => return None,
+    };
+    let digits = &text[2..];
+    if digits.is_empty() {
+        return Some((0..2, format!("missing digits after `{}`", text)));
+    }
+    let (offset, digit) = digits.char_indices().find(|(_, c)| !c.is_digit(radix))?;
+    Some((
+        2 + offset..2 + offset + digit.len_utf8(),
+        format!("invalid digit `{}` in {} number", digit, name),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_at_the_first_digit_of_the_wrong_radix() {
+        assert_eq!(
+            invalid_number_digit("0xG1"),
+            Some((2..3, "invalid digit `G` in hexadecimal number".to_owned()))
+        );
+        assert_eq!(
+            invalid_number_digit("0b12"),
+            Some((3..4, "invalid digit `2` in binary number".to_owned()))
+        );
+        assert_eq!(
+            invalid_number_digit("0o8"),
+            Some((2..3, "invalid digit `8` in octal number".to_owned()))
+        );
+        assert_eq!(
+            invalid_number_digit("0x"),
+            Some((0..2, "missing digits after `0x`".to_owned()))
+        );
+    }
+
+    #[test]
+    fn accepts_valid_numbers() {
+        for text in ["0xfF09", "0b101", "0o17", "0", "12.5"] {
+            assert_eq!(invalid_number_digit(text), None, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use super::{lex, Token};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// # Semantic category
+///
+/// The kind of a token for highlighting, one of the token types of the
+/// semantic tokens of the Language Server Protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemanticCategory {
+    Keyword,
+    Variable,
+    String,
+    Number,
+    Regexp,
+    Comment,
+    Operator,
+    Decorator,
+}
+
+impl SemanticCategory {
+    /// Every category, in the order of the legend a server announces, so
+    /// the position of a category here is its index in the protocol.
+    pub const ALL: [SemanticCategory; 8] = [
+        SemanticCategory::Keyword,
+        SemanticCategory::Variable,
+        SemanticCategory::String,
+        SemanticCategory::Number,
+        SemanticCategory::Regexp,
+        SemanticCategory::Comment,
+        SemanticCategory::Operator,
+        SemanticCategory::Decorator,
+    ];
+
+    /// Returns the name of the token type in the protocol, like `keyword`.
+    pub fn name(self) -> &'static str {
+        match self {
+            SemanticCategory::Keyword => "keyword",
+            SemanticCategory::Variable => "variable",
+            SemanticCategory::String => "string",
+            SemanticCategory::Number => "number",
+            SemanticCategory::Regexp => "regexp",
+            SemanticCategory::Comment => "comment",
+            SemanticCategory::Operator => "operator",
+            SemanticCategory::Decorator => "decorator",
+        }
+    }
+}
+
+impl Display for SemanticCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Token {
+    /// Returns the category the token is highlighted as, or None for
+    /// punctuation, whitespace and errors, which aren't highlighted.
+    ///
+    /// The lexer can't tell what a name refers to, so every identifier is
+    /// a [`SemanticCategory::Variable`], even the name of a type.
+    pub fn semantic_category(&self) -> Option<SemanticCategory> {
+        Some(match self {
+            Token::Import
+            | Token::Include
+            | Token::From
+            | Token::Export
+            | Token::Let
+            | Token::Var
+            | Token::Enum
+            | Token::Model
+            | Token::Type
+            | Token::Function
+            | Token::Struct
+            | Token::Config
+            | Token::Namespace
+            | Token::Native
+            | Token::Declare
+            | Token::Where
+            | Token::Middleware
+            | Token::Handler => SemanticCategory::Keyword,
+            Token::Plus
+            | Token::Minus
+            | Token::Star
+            | Token::Slash
+            | Token::Percent
+            | Token::Caret
+            | Token::Ampersand
+            | Token::AmpAmp
+            | Token::AmpAmpEqual
+            | Token::Pipe
+            | Token::PipePipe
+            | Token::PipePipeEqual
+            | Token::Bang
+            | Token::Question
+            | Token::Equal
+            | Token::EqualEqual
+            | Token::BangEqual => SemanticCategory::Operator,
+            Token::At | Token::AtAt => SemanticCategory::Decorator,
+            Token::Identifier => SemanticCategory::Variable,
+            Token::Number => SemanticCategory::Number,
+            Token::Regex => SemanticCategory::Regexp,
+            Token::String => SemanticCategory::String,
+            Token::LineComment | Token::DocComment => SemanticCategory::Comment,
+            Token::Period
+            | Token::Colon
+            | Token::ColonColon
+            | Token::Comma
+            | Token::BraceOpen
+            | Token::BraceClose
+            | Token::ParenOpen
+            | Token::ParenClose
+            | Token::BracketOpen
+            | Token::BracketClose
+            | Token::AngleOpen
+            | Token::AngleClose
+            | Token::Whitespace
+            | Token::UnterminatedString
+            | Token::Error => return None,
+        })
+    }
+}
+
+/// Returns the highlighted tokens of `source` in order, each with its
+/// category and byte span. Tokens without a category and input that
+/// doesn't form a token are left out.
+pub fn semantic_tokens(source: &str) -> Vec<(SemanticCategory, Range<usize>)> {
+    lex(source)
+        .filter_map(Result::ok)
+        .filter_map(|(token, span)| Some((token.semantic_category()?, span)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categories(source: &str) -> Vec<(SemanticCategory, &str)> {
+        semantic_tokens(source)
+            .into_iter()
+            .map(|(category, span)| (category, &source[span]))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_keywords_strings_numbers_and_comments() {
+        assert_eq!(
+            categories("/// A user.\nmodel User { @default(\"a\") age: Int = 18 } // end"),
+            vec![
+                (SemanticCategory::Comment, "/// A user."),
+                (SemanticCategory::Keyword, "model"),
+                (SemanticCategory::Variable, "User"),
+                (SemanticCategory::Decorator, "@"),
+                (SemanticCategory::Variable, "default"),
+                (SemanticCategory::String, "\"a\""),
+                (SemanticCategory::Variable, "age"),
+                (SemanticCategory::Variable, "Int"),
+                (SemanticCategory::Operator, "="),
+                (SemanticCategory::Number, "18"),
+                (SemanticCategory::Comment, "// end"),
+            ]
+        );
+    }
+
+    #[test]
+    fn names_follow_the_protocol() {
+        let names: Vec<&str> = SemanticCategory::ALL
+            .into_iter()
+            .map(SemanticCategory::name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "keyword",
+                "variable",
+                "string",
+                "number",
+                "regexp",
+                "comment",
+                "operator",
+                "decorator"
+            ]
+        );
+        assert_eq!(SemanticCategory::Regexp.to_string(), "regexp");
+    }
+}
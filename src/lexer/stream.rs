@@ -0,0 +1,274 @@
+use super::lexer::spanned;
+use super::{string_value, LexErrorKind, Token};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// The kind of a token yielded by [`lex`], without its text.
+pub type TokenKind = Token;
+
+/// # Lexeme
+///
+/// A token yielded by [`lexemes`], with the source text it was lexed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexeme {
+    /// The kind of the token.
+    pub kind: TokenKind,
+    /// The source text of the token exactly as written, so a string
+    /// literal keeps its quotes and escapes. See [`Lexeme::value`].
+    pub text: String,
+    /// The byte span of the token.
+    pub span: Range<usize>,
+}
+
+impl Lexeme {
+    /// Returns the value the token stands for: the decoded content of a
+    /// string literal, the text of any other token.
+    pub fn value(&self) -> String {
+        match self.kind {
+            Token::String => string_value(&self.text),
+            _ => self.text.clone(),
+        }
+    }
+}
+
+/// # Lex error
+///
+/// A piece of input that doesn't form any valid token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexError {
+    /// What is wrong with the input.
+    pub kind: LexErrorKind,
+    /// The byte span of the input. An unterminated string literal spans
+    /// from its opening quote to the end of the line.
+    pub span: Range<usize>,
+}
+
+impl Display for LexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let problem = match self.kind {
+            LexErrorKind::Unrecognized => "unrecognized input",
+            LexErrorKind::UnterminatedString => "unterminated string literal",
+        };
+        write!(f, "{} at {}..{}", problem, self.span.start, self.span.end)
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// Returns an iterator over the tokens of `source` with their byte spans,
+/// lexed one at a time as the iterator advances.
+///
+/// Comments are yielded, whitespace is skipped. Input that doesn't form a
+/// token is yielded as a [`LexError`] and lexing continues after it, so a
+/// string literal left open is reported once and lexing resumes on the
+/// next line.
+pub fn lex(source: &str) -> impl Iterator<Item = Result<(TokenKind, Range<usize>), LexError>> + '_ {
+    spanned(source).map(|(token, span)| match token {
+        Ok(token) => Ok((token, span)),
+        Err(kind) => Err(LexError { kind, span }),
+    })
+}
+
+/// Returns an iterator over the tokens of `source` like [`lex`], starting
+/// at the byte offset `start` instead of the beginning, with spans still
+/// relative to the whole source.
+///
+/// This lets an editor re-lex only the region around a change. `start`
+/// must be a safe boundary, the start of a token or of a line of the
+/// source lexed before, or the tokens may differ from those of lexing the
+/// whole source: lexing from the middle of `model` yields an identifier.
+/// No token spans more than one line, so the start of the line holding
+/// the change is always safe, and so is the start of every line after
+/// the change: once the iterator reaches one, the tokens are the old ones
+/// shifted by the change in length, and the caller can stop there.
+///
+/// # Panics
+///
+/// Panics if `start` is past the end of `source` or not on a character
+/// boundary.
+pub fn lex_range(
+    source: &str,
+    start: usize,
+) -> impl Iterator<Item = Result<(TokenKind, Range<usize>), LexError>> + '_ {
+    lex(&source[start..]).map(move |token| match token {
+        Ok((kind, span)) => Ok((kind, span.start + start..span.end + start)),
+        Err(LexError { kind, span }) => Err(LexError {
+            kind,
+            span: span.start + start..span.end + start,
+        }),
+    })
+}
+
+/// Returns an iterator over the tokens of `source` like [`lex`], each with
+/// its source text.
+pub fn lexemes(source: &str) -> impl Iterator<Item = Result<Lexeme, LexError>> + '_ {
+    lex(source).map(|token| {
+        let (kind, span) = token?;
+        Ok(Lexeme {
+            kind,
+            text: source[span.clone()].to_owned(),
+            span,
+        })
+    })
+}
+
+/// Collect the tokens of `source` like [`lex`], stopping at the first
+/// input that doesn't form a token.
+pub fn lex_to_vec(source: &str) -> Result<Vec<(TokenKind, Range<usize>)>, LexError> {
+    lex(source).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lexes_the_sample_schema() {
+        let source =
+            "/// A user.\n@map(\"users\")\nmodel User {\n    @id id: Int\n    name: String?\n}";
+        let kinds: Vec<TokenKind> = lex_to_vec(source)
+            .unwrap()
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Token::DocComment,
+                Token::At,
+                Token::Identifier,
+                Token::ParenOpen,
+                Token::String,
+                Token::ParenClose,
+                Token::Model,
+                Token::Identifier,
+                Token::BraceOpen,
+                Token::At,
+                Token::Identifier,
+                Token::Identifier,
+                Token::Colon,
+                Token::Identifier,
+                Token::Identifier,
+                Token::Colon,
+                Token::Identifier,
+                Token::Question,
+                Token::BraceClose,
+            ]
+        );
+    }
+
+    #[test]
+    fn yields_spans_that_outlive_the_lexer() {
+        let tokens = {
+            let source = String::from("model User");
+            lex(&source).map(Result::unwrap).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            tokens,
+            vec![(Token::Model, 0..5), (Token::Identifier, 6..10)]
+        );
+    }
+
+    #[test]
+    fn reports_unrecognized_input_and_continues() {
+        let tokens: Vec<_> = lex("a # b").collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Ok((Token::Identifier, 0..1)),
+                Err(LexError {
+                    kind: LexErrorKind::Unrecognized,
+                    span: 2..3
+                }),
+                Ok((Token::Identifier, 4..5)),
+            ]
+        );
+        assert_eq!(
+            lex_to_vec("a # b").unwrap_err().to_string(),
+            "unrecognized input at 2..3"
+        );
+    }
+
+    #[test]
+    fn reports_a_string_unterminated_at_the_end_of_the_input() {
+        let tokens: Vec<_> = lex("let x = \"abc").collect();
+        assert_eq!(
+            tokens.last(),
+            Some(&Err(LexError {
+                kind: LexErrorKind::UnterminatedString,
+                span: 8..12
+            }))
+        );
+        assert_eq!(
+            lex_to_vec("let x = \"abc\\").unwrap_err().to_string(),
+            "unterminated string literal at 8..13"
+        );
+    }
+
+    #[test]
+    fn resumes_after_a_string_unterminated_before_a_new_line() {
+        let tokens: Vec<_> = lex("let x = \"abc\nlet y = \"d\" #").collect();
+        assert_eq!(
+            tokens[3..],
+            [
+                Err(LexError {
+                    kind: LexErrorKind::UnterminatedString,
+                    span: 8..12
+                }),
+                Ok((Token::Let, 13..16)),
+                Ok((Token::Identifier, 17..18)),
+                Ok((Token::Equal, 19..20)),
+                Ok((Token::String, 21..24)),
+                Err(LexError {
+                    kind: LexErrorKind::Unrecognized,
+                    span: 25..26
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn captures_the_text_of_each_token() {
+        let source = "name: String @default(\"a \\\"b\\\"\")";
+        let tokens: Vec<Lexeme> = lexemes(source).map(Result::unwrap).collect();
+        assert_eq!(tokens[0].kind, Token::Identifier);
+        assert_eq!(tokens[0].text, "name");
+        assert_eq!(tokens[0].value(), "name");
+        let string = &tokens[6];
+        assert_eq!(string.kind, Token::String);
+        assert_eq!(string.text, "\"a \\\"b\\\"\"");
+        assert_eq!(string.value(), "a \"b\"");
+        assert_eq!(string.span, 22..31);
+    }
+
+    #[test]
+    fn lexing_from_a_boundary_matches_lexing_the_whole_source() {
+        let source = "/// A user.\nmodel User {\n  @id id: Int\n  name: String? = \"a b\"\n}\nlet r = /a+/ # x";
+        let tokens: Vec<_> = lex(source).collect();
+        let token_starts = tokens.iter().map(|token| match token {
+            Ok((_, span)) => span.start,
+            Err(error) => error.span.start,
+        });
+        let line_starts = source.match_indices('\n').map(|(at, _)| at + 1);
+        for start in token_starts.chain(line_starts) {
+            let expected: Vec<_> = tokens
+                .iter()
+                .filter(|token| match token {
+                    Ok((_, span)) => span.start >= start,
+                    Err(error) => error.span.start >= start,
+                })
+                .cloned()
+                .collect();
+            assert_eq!(lex_range(source, start).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn lexing_from_inside_a_token_differs() {
+        let tokens: Vec<_> = lex_range("model User", 2).map(Result::unwrap).collect();
+        assert_eq!(
+            tokens,
+            vec![(Token::Identifier, 2..5), (Token::Identifier, 6..10)]
+        );
+    }
+}
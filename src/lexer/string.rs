@@ -0,0 +1,46 @@
+/// Returns the value of the string literal `text`, quotes included, with
+/// its escapes decoded.
+///
+/// `\n`, `\t`, `\r` and `\0` stand for the control characters, any other
+/// escaped character stands for itself, so `\"` is a quote and `\\` a
+/// backslash. A trailing lone backslash is kept.
+pub fn string_value(text: &str) -> String {
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text);
+    unescape(inner)
+}
+
+/// Returns `inner`, the content of a string literal without its quotes,
+/// with its escapes decoded like [`string_value`] does.
+pub fn unescape(inner: &str) -> String {
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some('0') => value.push('\0'),
+            Some(other) => value.push(other),
+            None => value.push('\\'),
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_escapes() {
+        assert_eq!(string_value(r#""a\"b\\c\nd""#), "a\"b\\c\nd");
+        assert_eq!(string_value(r#""""#), "");
+    }
+}
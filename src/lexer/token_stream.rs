@@ -0,0 +1,109 @@
+use super::Token;
+use std::ops::Range;
+
+/// # Token stream
+///
+/// A stream of tokens with their byte spans that yields every token,
+/// trivia included, as an [`Iterator`], and skips the trivia with
+/// [`TokenStream::next_significant`], so that a lossless consumer and
+/// the parser can read the same tokens.
+#[derive(Debug, Clone)]
+pub struct TokenStream<I> {
+    tokens: I,
+}
+
+impl<I: Iterator<Item = (Token, Range<usize>)>> TokenStream<I> {
+    /// Create a stream of `tokens`, like the ones of
+    /// [`tokenize_lossless`](super::tokenize_lossless).
+    pub fn new(tokens: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            tokens: tokens.into_iter(),
+        }
+    }
+
+    /// Returns the next token that isn't [trivia](Token::is_trivia),
+    /// skipping over the trivia before it.
+    pub fn next_significant(&mut self) -> Option<(Token, Range<usize>)> {
+        self.tokens.find(|(token, _)| !token.is_trivia())
+    }
+
+    /// Returns the rest of the tokens without the trivia.
+    pub fn significant(self) -> impl Iterator<Item = (Token, Range<usize>)> {
+        self.tokens.filter(|(token, _)| !token.is_trivia())
+    }
+}
+
+impl<I: Iterator<Item = (Token, Range<usize>)>> Iterator for TokenStream<I> {
+    type Item = (Token, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tokens.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize_lossless;
+
+    const SOURCE: &str = "/// A user.\nmodel User { // none\n}";
+
+    #[test]
+    fn next_significant_skips_trivia() {
+        let mut stream = TokenStream::new(tokenize_lossless(SOURCE));
+        let mut tokens = vec![];
+        while let Some((token, span)) = stream.next_significant() {
+            tokens.push((token, &SOURCE[span]));
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                (Token::Model, "model"),
+                (Token::Identifier, "User"),
+                (Token::BraceOpen, "{"),
+                (Token::BraceClose, "}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn the_iterator_still_yields_trivia() {
+        let tokens: Vec<Token> = TokenStream::new(tokenize_lossless(SOURCE))
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::DocComment,
+                Token::Whitespace,
+                Token::Model,
+                Token::Whitespace,
+                Token::Identifier,
+                Token::Whitespace,
+                Token::BraceOpen,
+                Token::Whitespace,
+                Token::LineComment,
+                Token::Whitespace,
+                Token::BraceClose,
+            ]
+        );
+    }
+
+    #[test]
+    fn reading_both_ways_from_one_stream() {
+        let mut stream = TokenStream::new(tokenize_lossless(SOURCE));
+        assert_eq!(
+            stream.next().map(|(token, _)| token),
+            Some(Token::DocComment)
+        );
+        assert_eq!(
+            stream.next_significant().map(|(token, _)| token),
+            Some(Token::Model)
+        );
+        assert_eq!(
+            stream.next().map(|(token, _)| token),
+            Some(Token::Whitespace)
+        );
+        assert_eq!(stream.significant().count(), 3);
+    }
+}
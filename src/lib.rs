@@ -1,3 +1,17 @@
+pub mod ast;
+pub mod check;
+pub mod cst;
+pub mod diagnostic;
+pub mod diff;
+pub mod format;
 pub mod fsutil;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod lexer;
+pub mod outline;
 pub mod parser;
+pub mod resolve;
+pub mod source_map;
+pub mod stats;
+pub mod symbol;
+pub mod visit;
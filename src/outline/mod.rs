@@ -0,0 +1,6 @@
+pub mod fast;
+#[allow(clippy::module_inception)]
+pub mod outline;
+
+pub use fast::fast_outline;
+pub use outline::{outline, OutlineItem, OutlineKind};
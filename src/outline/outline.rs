@@ -0,0 +1,349 @@
+use crate::lexer::{tokenize, Token, TokenStream};
+use std::ops::Range;
+
+/// The kind of a declaration in an outline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutlineKind {
+    Namespace,
+    Model,
+    Enum,
+    Field,
+    EnumMember,
+}
+
+/// # Outline item
+///
+/// A declaration found by [`outline`], with the members declared inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineItem {
+    /// The name of the declaration.
+    pub name: String,
+    /// What kind of declaration this is.
+    pub kind: OutlineKind,
+    /// The byte span of the declaration.
+    pub span: Range<usize>,
+    /// The declarations nested inside this one.
+    pub children: Vec<OutlineItem>,
+}
+
+/// Returns a structural summary of the declarations in `source`.
+///
+/// This only recognizes declaration headers and the names of their members,
+/// skipping over types, decorators and values without parsing them. Errors
+/// inside a body don't prevent the rest of the file from being outlined.
+pub fn outline(source: &str) -> Vec<OutlineItem> {
+    let tokens: Vec<(Token, Range<usize>)> =
+        TokenStream::new(tokenize(source)).significant().collect();
+    let mut scanner = Scanner {
+        source,
+        tokens: Buffered {
+            tokens: &tokens,
+            position: 0,
+        },
+    };
+    scanner.declarations(false)
+}
+
+/// The comment-free tokens read by a [`Scanner`], one at a time.
+pub(super) trait Tokens {
+    /// Returns the current token and its span.
+    fn current(&self) -> Option<&(Token, Range<usize>)>;
+    /// Returns the token after the current one.
+    fn second(&self) -> Option<Token>;
+    /// Returns the end of the token before the current one.
+    fn previous_end(&self) -> Option<usize>;
+    /// Move to the next token.
+    fn advance(&mut self);
+}
+
+/// Tokens lexed ahead of time.
+struct Buffered<'a> {
+    tokens: &'a [(Token, Range<usize>)],
+    position: usize,
+}
+
+impl Tokens for Buffered<'_> {
+    fn current(&self) -> Option<&(Token, Range<usize>)> {
+        self.tokens.get(self.position)
+    }
+
+    fn second(&self) -> Option<Token> {
+        self.tokens.get(self.position + 1).map(|(token, _)| *token)
+    }
+
+    fn previous_end(&self) -> Option<usize> {
+        let previous = self.position.checked_sub(1)?;
+        Some(self.tokens[previous].1.end)
+    }
+
+    fn advance(&mut self) {
+        self.position += 1;
+    }
+}
+
+pub(super) struct Scanner<'a, T> {
+    pub(super) source: &'a str,
+    pub(super) tokens: T,
+}
+
+impl<T: Tokens> Scanner<'_, T> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.current().map(|(token, _)| *token)
+    }
+
+    /// Returns the span of the current token.
+    fn span(&self) -> Range<usize> {
+        self.tokens.current().unwrap().1.clone()
+    }
+
+    fn previous_end(&self) -> usize {
+        self.tokens.previous_end().unwrap()
+    }
+
+    fn advance(&mut self) {
+        self.tokens.advance();
+    }
+
+    fn at_line_start(&self) -> bool {
+        match (self.tokens.previous_end(), self.tokens.current()) {
+            (Some(previous_end), Some((_, span))) => {
+                self.source[previous_end..span.start].contains('\n')
+            }
+            _ => true,
+        }
+    }
+
+    /// Scan declarations up to the end of the tokens. In the body of a
+    /// namespace, the scan stops at the `}` closing it, which is left
+    /// unconsumed, and at the top level a stray `}` is skipped.
+    pub(super) fn declarations(&mut self, namespace: bool) -> Vec<OutlineItem> {
+        let mut items = vec![];
+        while let Some(token) = self.peek() {
+            let kind = match token {
+                Token::Namespace => OutlineKind::Namespace,
+                Token::Model => OutlineKind::Model,
+                Token::Enum => OutlineKind::Enum,
+                Token::BraceClose if namespace => return items,
+                Token::BraceOpen | Token::BracketOpen | Token::ParenOpen => {
+                    self.skip_balanced();
+                    continue;
+                }
+                _ => {
+                    self.advance();
+                    continue;
+                }
+            };
+            if let Some(item) = self.declaration(kind) {
+                items.push(item);
+            }
+        }
+        items
+    }
+
+    /// Scan a declaration starting at its keyword.
+    fn declaration(&mut self, kind: OutlineKind) -> Option<OutlineItem> {
+        let start = self.span().start;
+        self.advance();
+        if self.peek() != Some(Token::Identifier) {
+            return None;
+        }
+        let name = self.source[self.span()].to_owned();
+        self.advance();
+        while !matches!(self.peek(), Some(Token::BraceOpen) | None) {
+            if self.at_line_start() {
+                return None;
+            }
+            self.advance();
+        }
+        self.peek()?;
+        self.advance();
+        let children = match kind {
+            OutlineKind::Namespace => self.declarations(true),
+            OutlineKind::Model => self.members(OutlineKind::Field),
+            _ => self.members(OutlineKind::EnumMember),
+        };
+        let end = match self.peek() {
+            Some(Token::BraceClose) => {
+                self.advance();
+                self.previous_end()
+            }
+            _ => self.previous_end(),
+        };
+        Some(OutlineItem {
+            name,
+            kind,
+            span: start..end,
+            children,
+        })
+    }
+
+    /// Scan the member names of a body up to its closing `}`, which is left
+    /// unconsumed. A member starts at its first token after `{`, `,` or a
+    /// line break. Fields are recognized by the `:` after their name.
+    fn members(&mut self, kind: OutlineKind) -> Vec<OutlineItem> {
+        let mut members: Vec<OutlineItem> = vec![];
+        let mut at_member_start = true;
+        while let Some(token) = self.peek() {
+            if self.at_line_start() {
+                at_member_start = true;
+            }
+            match token {
+                Token::BraceClose => return members,
+                Token::Comma => {
+                    self.advance();
+                    at_member_start = true;
+                }
+                Token::At | Token::AtAt => self.skip_decorator(),
+                Token::BraceOpen | Token::BracketOpen | Token::ParenOpen => {
+                    self.skip_balanced();
+                    at_member_start = false;
+                }
+                Token::Identifier if at_member_start => {
+                    let next = self.tokens.second();
+                    if kind == OutlineKind::EnumMember || next == Some(Token::Colon) {
+                        let span = self.span();
+                        members.push(OutlineItem {
+                            name: self.source[span.clone()].to_owned(),
+                            kind,
+                            span,
+                            children: vec![],
+                        });
+                    }
+                    self.advance();
+                    at_member_start = false;
+                }
+                _ => {
+                    if let Some(member) = members.last_mut() {
+                        member.span.end = self.span().end;
+                    }
+                    self.advance();
+                    at_member_start = false;
+                }
+            }
+        }
+        members
+    }
+
+    /// Skip an `@name(arguments)` decorator.
+    fn skip_decorator(&mut self) {
+        self.advance();
+        while self.peek() == Some(Token::Identifier) {
+            self.advance();
+            if self.peek() != Some(Token::ColonColon) {
+                break;
+            }
+            self.advance();
+        }
+        if self.peek() == Some(Token::ParenOpen) {
+            self.skip_balanced();
+        }
+    }
+
+    /// Skip a bracketed group starting at its opening token, through the
+    /// matching closing token. Unmatched closing tokens inside the group are
+    /// ignored, except for a `}` which closes the enclosing body and is left
+    /// unconsumed.
+    fn skip_balanced(&mut self) {
+        let mut expected: Vec<Token> = vec![];
+        while let Some(token) = self.peek() {
+            match token {
+                Token::BraceOpen => expected.push(Token::BraceClose),
+                Token::BracketOpen => expected.push(Token::BracketClose),
+                Token::ParenOpen => expected.push(Token::ParenClose),
+                Token::BraceClose | Token::BracketClose | Token::ParenClose => {
+                    if expected.contains(&token) {
+                        while expected.pop() != Some(token) {}
+                    } else if token == Token::BraceClose {
+                        return;
+                    }
+                }
+                _ => (),
+            }
+            self.advance();
+            if expected.is_empty() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renders an outline as indented `kind name` lines.
+    fn render(items: &[OutlineItem], indent: usize, output: &mut String) {
+        for item in items {
+            output.push_str(&format!(
+                "{}{:?} {}\n",
+                " ".repeat(indent),
+                item.kind,
+                item.name
+            ));
+            render(&item.children, indent + 2, output);
+        }
+    }
+
+    fn rendered(source: &str) -> String {
+        let mut output = String::new();
+        render(&outline(source), 0, &mut output);
+        output
+    }
+
+    #[test]
+    fn outlines_models_enums_and_namespaces() {
+        let source = r#"
+        import { a } from "./a"
+        @map("users")
+        model User {
+            @id id: String,
+            @relation(fields: [id], references: [id])
+            posts: Post[]
+        }
+        namespace blog {
+            enum Status { DRAFT, PUBLISHED }
+            model Post {
+                title: String
+            }
+        }
+        "#;
+        assert_eq!(
+            rendered(source),
+            "Model User\n  Field id\n  Field posts\nNamespace blog\n  Enum Status\n    EnumMember DRAFT\n    EnumMember PUBLISHED\n  Model Post\n    Field title\n"
+        );
+    }
+
+    #[test]
+    fn outlines_past_a_broken_field_body() {
+        let source =
+            "model User {\n id: String,\n name: : = ] ,\n email: String\n}\nenum Role { ADMIN }";
+        assert_eq!(
+            rendered(source),
+            "Model User\n  Field id\n  Field name\n  Field email\nEnum Role\n  EnumMember ADMIN\n"
+        );
+    }
+
+    #[test]
+    fn outlines_past_an_unclosed_bracket_in_a_body() {
+        let source = "model User {\n id: String @default([\n email: String\n}\nenum Role { ADMIN }";
+        assert_eq!(
+            rendered(source),
+            "Model User\n  Field id\nEnum Role\n  EnumMember ADMIN\n"
+        );
+    }
+
+    #[test]
+    fn outlines_past_a_stray_closing_brace() {
+        assert_eq!(rendered("} model A { id: Int }"), "Model A\n  Field id\n");
+        let source = "model A { b: { c: [1, 2) } }\n}} model B { d: Int }";
+        assert_eq!(rendered(source), "Model A\n  Field b\nModel B\n  Field d\n");
+    }
+
+    #[test]
+    fn spans_cover_the_declaration() {
+        let source = "model User { id: String }";
+        let items = outline(source);
+        assert_eq!(items[0].span, 0..source.len());
+        assert_eq!(&source[items[0].children[0].span.clone()], "id: String");
+    }
+}
@@ -0,0 +1,88 @@
+use super::{ParseError, Parser};
+use crate::ast::LetDeclaration;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a `let name = value` or `let name: Type = value` declaration.
+    ///
+    /// `var` is reserved for mutable bindings, so a `var` declaration is an
+    /// error, as is a binding without a value.
+    pub fn parse_let(&mut self) -> Result<LetDeclaration, ParseError> {
+        let first = self.position();
+        if let Some(span) = self.eat(Token::Var) {
+            return Err(ParseError::new(
+                "`var` is reserved, constants are declared with `let`",
+                span,
+            ));
+        }
+        let start = self.expect(Token::Let, "`let`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a constant name")?;
+        let type_expr = match self.eat(Token::Colon) {
+            Some(_) => Some(self.parse_type()?),
+            None => None,
+        };
+        if self.eat(Token::Equal).is_none() {
+            return Err(ParseError::new(
+                format!("constant `{}` has no value, expected `=`", name.name),
+                name.span,
+            ));
+        }
+        let value = self.parse_expr()?;
+        self.trace_node("let", first);
+        Ok(LetDeclaration {
+            span: start..value.span().end,
+            name,
+            type_expr,
+            value,
+            source_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Expr;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<LetDeclaration, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_let()
+    }
+
+    #[test]
+    fn parses_a_binding() {
+        let binding = parse("let x = 1").unwrap();
+        assert_eq!(binding.name.name, "x");
+        assert!(binding.type_expr.is_none());
+        assert!(matches!(binding.value, Expr::Literal(_)));
+        assert_eq!(binding.span, 0..9);
+    }
+
+    #[test]
+    fn parses_a_binding_with_a_type() {
+        let binding = parse("let x: Int = 1").unwrap();
+        assert_eq!(
+            binding.type_expr.as_ref().and_then(|t| t.name()).as_deref(),
+            Some("Int")
+        );
+        assert_eq!(binding.span, 0..14);
+    }
+
+    #[test]
+    fn rejects_a_binding_without_a_value() {
+        let error = parse("let x: Int").unwrap_err();
+        assert_eq!(error.message, "constant `x` has no value, expected `=`");
+        assert_eq!(error.span, 4..5);
+    }
+
+    #[test]
+    fn rejects_var() {
+        let error = parse("var x = 1").unwrap_err();
+        assert_eq!(
+            error.message,
+            "`var` is reserved, constants are declared with `let`"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use super::{ParseError, Parser};
+use crate::ast::{ConfigDeclaration, ConfigItem, ConfigSection};
+use crate::diagnostic::merge_spans;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a `config kind Name { key: value, ... }` declaration, where
+    /// the name, or both the kind and the name, may be left out.
+    ///
+    /// Errors inside the body are recorded and the parser continues with
+    /// the next item. Only a broken header is returned as an error.
+    pub fn parse_config(&mut self) -> Result<ConfigDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Config, "`config`")?.start;
+        let source_order = self.next_source_order();
+        let kind = match self.at(Token::Identifier) {
+            true => Some(self.parse_identifier("a config kind")?),
+            false => None,
+        };
+        let name = match kind.is_some() && self.at(Token::Identifier) {
+            true => Some(self.parse_identifier("a config name")?),
+            false => None,
+        };
+        if !self.at(Token::BraceOpen) {
+            return Err(self.error_expected(match (&kind, &name) {
+                (None, _) => "a config kind or `{`",
+                (Some(_), None) => "a config name or `{`",
+                (Some(_), Some(_)) => "`{`",
+            }));
+        }
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let (items, end) = self.parse_members(open, "config", Self::parse_config_item);
+        self.trace_node("config", first);
+        Ok(ConfigDeclaration {
+            kind,
+            name,
+            items,
+            span: start..end,
+            source_order,
+        })
+    }
+
+    /// Parse a `key: value` config item.
+    pub fn parse_config_item(&mut self) -> Result<ConfigItem, ParseError> {
+        let first = self.position();
+        let key = self.parse_identifier("a config key")?;
+        self.expect(Token::Colon, "`:`")?;
+        let value = self.parse_expr()?;
+        self.trace_node("config item", first);
+        Ok(ConfigItem {
+            span: merge_spans(&key.span, &value.span()),
+            key,
+            value,
+        })
+    }
+}
+
+impl Parser<'_> {
+    /// Parse a `[name]` section header and the `key = value` entries after
+    /// it.
+    ///
+    /// The section ends at the first line that doesn't start an entry. A
+    /// broken entry is recorded and the parser continues on the next line.
+    pub fn parse_config_section(&mut self) -> Result<ConfigSection, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::BracketOpen, "`[`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a section name")?;
+        let close = self.expect(Token::BracketClose, "`]`")?;
+        let mut entries = vec![];
+        while self.at_line_start() && self.at(Token::Identifier) {
+            let entry_start = self.position();
+            match self.parse_config_entry() {
+                Ok(entry) => entries.push(entry),
+                Err(error) => {
+                    self.report(error);
+                    self.recover_member(entry_start);
+                }
+            }
+        }
+        let end = entries.last().map_or(close.end, |entry| entry.span.end);
+        self.trace_node("config section", first);
+        Ok(ConfigSection {
+            name,
+            entries,
+            span: start..end,
+            source_order,
+        })
+    }
+
+    fn parse_config_entry(&mut self) -> Result<ConfigItem, ParseError> {
+        let key = self.parse_identifier("a config key")?;
+        self.expect(Token::Equal, "`=`")?;
+        let value = self.parse_expr()?;
+        Ok(ConfigItem {
+            span: merge_spans(&key.span, &value.span()),
+            key,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Expr, LiteralValue};
+    use crate::diagnostic::Diagnostic;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> (Result<ConfigDeclaration, ParseError>, Vec<Diagnostic>) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let config = parser.parse_config();
+        (config, parser.take_diagnostics())
+    }
+
+    #[test]
+    fn parses_server_config_with_a_tuple_value() {
+        let (config, diagnostics) = parse(r#"config server { bind: ("0.0.0.0", 5000) }"#);
+        let config = config.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(config.kind.unwrap().name, "server");
+        assert!(config.name.is_none());
+        assert_eq!(config.items[0].key.name, "bind");
+        let Expr::Tuple { elements, span } = &config.items[0].value else {
+            panic!("expected a tuple");
+        };
+        assert_eq!(*span, 22..39);
+        assert!(matches!(
+            &elements[0],
+            Expr::Literal(literal) if literal.value == LiteralValue::String("0.0.0.0".to_owned())
+        ));
+        assert!(matches!(
+            &elements[1],
+            Expr::Literal(literal) if literal.value == LiteralValue::Number("5000".to_owned())
+        ));
+    }
+
+    #[test]
+    fn parses_nested_config_item() {
+        let source = "config {\n path: \"/api\"\n database: { url: \"db\", pool: [1, 2] }\n}";
+        let (config, diagnostics) = parse(source);
+        let config = config.unwrap();
+        assert!(diagnostics.is_empty());
+        assert!(config.kind.is_none());
+        assert_eq!(config.items.len(), 2);
+        assert!(matches!(&config.items[1].value, Expr::Dict { entries, .. } if entries.len() == 2));
+    }
+
+    #[test]
+    fn parses_named_config() {
+        let (config, diagnostics) = parse("config client Web { provider: \"ts\" }");
+        assert!(diagnostics.is_empty());
+        let config = config.unwrap();
+        assert_eq!(config.kind.unwrap().name, "client");
+        let name = config.name.unwrap();
+        assert_eq!(name.name, "Web");
+        assert_eq!(name.span, 14..17);
+        assert_eq!(config.items[0].key.name, "provider");
+    }
+
+    #[test]
+    fn an_anonymous_config_has_no_name() {
+        let (config, _) = parse("config { port: 5000 }");
+        let config = config.unwrap();
+        assert!(config.kind.is_none());
+        assert!(config.name.is_none());
+    }
+
+    #[test]
+    fn reports_an_unexpected_token_after_the_keyword() {
+        let (config, _) = parse("config 5000 { }");
+        assert_eq!(
+            config.unwrap_err().message,
+            "expected a config kind or `{`, found number `5000`"
+        );
+        let (config, _) = parse("config server: { }");
+        assert_eq!(
+            config.unwrap_err().message,
+            "expected a config name or `{`, found colon `:`"
+        );
+        let (config, _) = parse("config server Main Other { }");
+        assert_eq!(
+            config.unwrap_err().message,
+            "expected `{`, found identifier `Other`"
+        );
+    }
+
+    #[test]
+    fn recovers_from_an_item_missing_its_colon() {
+        let (config, diagnostics) = parse("config server {\n bind \"0.0.0.0\"\n port: 5000\n}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "expected `:`, found string literal");
+        let config = config.unwrap();
+        assert_eq!(config.items.len(), 1);
+        assert_eq!(config.items[0].key.name, "port");
+    }
+
+    fn parse_sections(source: &str) -> (Vec<ConfigSection>, Vec<Diagnostic>) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let mut sections = vec![];
+        while !parser.is_at_end() {
+            sections.push(parser.parse_config_section().unwrap());
+        }
+        (sections, parser.take_diagnostics())
+    }
+
+    #[test]
+    fn parses_two_sections_with_entries() {
+        let source = "[server]\nbind = \"0.0.0.0\"\nport = 5000\n[database]\nurl = \"db\"\n";
+        let (sections, diagnostics) = parse_sections(source);
+        assert!(diagnostics.is_empty());
+        let names: Vec<&str> = sections.iter().map(|s| s.name.name.as_str()).collect();
+        assert_eq!(names, vec!["server", "database"]);
+        let keys: Vec<&str> = sections[0]
+            .entries
+            .iter()
+            .map(|e| e.key.name.as_str())
+            .collect();
+        assert_eq!(keys, vec!["bind", "port"]);
+        assert_eq!(sections[0].span, 0..37);
+        assert_eq!(sections[1].entries.len(), 1);
+    }
+
+    #[test]
+    fn an_array_value_is_not_a_section() {
+        let (sections, diagnostics) =
+            parse_sections("[server]\nports = [5000, 5001]\nhosts = a [0]");
+        assert!(diagnostics.is_empty());
+        assert_eq!(sections.len(), 1);
+        assert!(
+            matches!(&sections[0].entries[0].value, Expr::Array { elements, .. } if elements.len() == 2)
+        );
+        assert!(matches!(&sections[0].entries[1].value, Expr::Index { .. }));
+    }
+}
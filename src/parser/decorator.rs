@@ -0,0 +1,212 @@
+use super::{ParseError, Parser};
+use crate::ast::{Argument, Decorator, Expr, Literal, LiteralValue};
+use crate::diagnostic::Diagnostic;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse the decorators written before a declaration, if any.
+    pub fn parse_decorators(&mut self) -> Result<Vec<Decorator>, ParseError> {
+        let mut decorators = vec![];
+        while self.at(Token::At) {
+            decorators.push(self.parse_decorator()?);
+        }
+        Ok(decorators)
+    }
+
+    /// Parse an `@name`, `@name()` or `@name(arguments)` decorator, or a
+    /// block decorator written with `@@`.
+    pub fn parse_decorator(&mut self) -> Result<Decorator, ParseError> {
+        let first = self.position();
+        let block = self.at(Token::AtAt);
+        let start = match self.eat(Token::AtAt) {
+            Some(span) => span.start,
+            None => self.expect(Token::At, "`@`")?.start,
+        };
+        let mut path = vec![self.parse_identifier("a decorator name")?];
+        while self.eat(Token::ColonColon).is_some() {
+            path.push(self.parse_identifier("a decorator name after `::`")?);
+        }
+        let arguments = match self.eat(Token::ParenOpen) {
+            Some(_) => Some(self.parse_arguments()?),
+            None => None,
+        };
+        self.trace_node("decorator", first);
+        Ok(Decorator {
+            path,
+            arguments,
+            block,
+            span: start..self.previous_span().end,
+        })
+    }
+
+    /// Returns the documentation given by the `@@doc("...")` block
+    /// decorators among `decorators`, one line per decorator.
+    ///
+    /// A `@@doc` decorator without a single string argument is recorded as
+    /// an `"invalid-doc"` error and ignored.
+    pub(crate) fn block_doc(&mut self, decorators: &[Decorator]) -> Option<String> {
+        let mut lines = vec![];
+        for decorator in decorators {
+            if !decorator.block || decorator.name() != "doc" {
+                continue;
+            }
+            match decorator.arguments.as_deref() {
+                Some(
+                    [Argument {
+                        name: None,
+                        value:
+                            Expr::Literal(Literal {
+                                value: LiteralValue::String(doc),
+                                ..
+                            }),
+                        ..
+                    }],
+                ) => lines.push(doc.clone()),
+                _ => self.report_diagnostic(
+                    Diagnostic::error(
+                        "`@@doc` takes a single string argument",
+                        decorator.span.clone(),
+                    )
+                    .with_code("invalid-doc"),
+                ),
+            }
+        }
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Parse an argument list after its opening parenthesis, through the
+    /// closing parenthesis.
+    ///
+    /// Positional arguments are expected before named ones. A positional
+    /// argument after a named one is kept and reported with a warning.
+    pub(crate) fn parse_arguments(&mut self) -> Result<Vec<Argument>, ParseError> {
+        let arguments =
+            self.parse_comma_separated(Token::ParenClose, "`)`", Self::parse_argument)?;
+        if let Some(first_named) = arguments.iter().position(|a| a.name.is_some()) {
+            for argument in &arguments[first_named..] {
+                if argument.name.is_none() {
+                    self.report_diagnostic(
+                        Diagnostic::warning(
+                            "positional argument after a named argument",
+                            argument.span.clone(),
+                        )
+                        .with_code("positional-after-named"),
+                    );
+                }
+            }
+        }
+        Ok(arguments)
+    }
+
+    /// Parse a positional `value` or a named `name: value` argument.
+    pub fn parse_argument(&mut self) -> Result<Argument, ParseError> {
+        let start = self.peek_span().start;
+        let name = if self.at(Token::Identifier) && self.peek_nth(1) == Some(Token::Colon) {
+            let name = self.parse_identifier("an argument name")?;
+            self.expect(Token::Colon, "`:`")?;
+            Some(name)
+        } else {
+            None
+        };
+        let value = self.parse_expr()?;
+        Ok(Argument {
+            span: start..value.span().end,
+            name,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Expr, LiteralValue};
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<Decorator, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_decorator()
+    }
+
+    fn parse_with_diagnostics(source: &str) -> (Decorator, Vec<Diagnostic>) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let decorator = parser.parse_decorator().unwrap();
+        (decorator, parser.take_diagnostics())
+    }
+
+    fn labels(decorator: &Decorator) -> Vec<Option<&str>> {
+        let arguments = decorator.arguments.as_deref().unwrap_or_default();
+        arguments
+            .iter()
+            .map(|argument| argument.name.as_ref().map(|name| name.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_decorator_without_parentheses() {
+        let decorator = parse("@id").unwrap();
+        assert_eq!(decorator.name(), "id");
+        assert_eq!(decorator.arguments, None);
+        assert_eq!(decorator.span, 0..3);
+    }
+
+    #[test]
+    fn parses_decorator_with_empty_parentheses() {
+        let decorator = parse("@id()").unwrap();
+        assert_eq!(decorator.arguments, Some(vec![]));
+        assert_eq!(decorator.span, 0..5);
+    }
+
+    #[test]
+    fn parses_positional_arguments() {
+        let decorator = parse(r#"@map("users")"#).unwrap();
+        let arguments = decorator.arguments.unwrap();
+        assert_eq!(arguments.len(), 1);
+        assert!(arguments[0].name.is_none());
+        assert!(matches!(
+            &arguments[0].value,
+            Expr::Literal(literal) if literal.value == LiteralValue::String("users".to_owned())
+        ));
+    }
+
+    #[test]
+    fn parses_named_arguments() {
+        let decorator = parse("@relation(fields: [a], references: [b])").unwrap();
+        let arguments = decorator.arguments.unwrap();
+        let names: Vec<&str> = arguments
+            .iter()
+            .map(|argument| argument.name.as_ref().unwrap().name.as_str())
+            .collect();
+        assert_eq!(names, vec!["fields", "references"]);
+        assert!(matches!(&arguments[0].value, Expr::Array { elements, .. } if elements.len() == 1));
+        assert_eq!(arguments[1].span, 23..38);
+    }
+
+    #[test]
+    fn accepts_positional_then_named_arguments() {
+        let (decorator, diagnostics) = parse_with_diagnostics("@default(1, 2)");
+        assert_eq!(labels(&decorator), vec![None, None]);
+        assert!(diagnostics.is_empty());
+        let (decorator, diagnostics) = parse_with_diagnostics("@index([a], name: \"i\", sort: 1)");
+        assert_eq!(labels(&decorator), vec![None, Some("name"), Some("sort")]);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn warns_about_a_positional_argument_after_a_named_one() {
+        let (decorator, diagnostics) = parse_with_diagnostics("@index(name: \"i\", [a])");
+        assert_eq!(labels(&decorator), vec![Some("name"), None]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("positional-after-named"));
+        assert_eq!(diagnostics[0].span, 18..21);
+    }
+
+    #[test]
+    fn parses_namespaced_decorator_with_nested_arguments() {
+        let decorator = parse("@db::index({ fields: [a, b], unique: 1 })").unwrap();
+        assert_eq!(decorator.name(), "db::index");
+        let arguments = decorator.arguments.unwrap();
+        assert!(matches!(&arguments[0].value, Expr::Dict { entries, .. } if entries.len() == 2));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::ast::{Declaration, Decorator, SchemaFile};
+use std::ops::Range;
+
+/// Attach the `///` comments at `doc_comments` to the models, fields and
+/// enums of `schema` they document.
+///
+/// A run of doc comments on consecutive lines documents the node that
+/// starts on the line right after the last of them, counting from the
+/// node's first decorator. A blank line or another comment ends the run.
+/// A model or enum documented with `@@doc` block decorators too gets the
+/// comments first, followed by the documentation of the decorators. The
+/// declarations inside namespaces are documented the same way.
+pub fn attach_doc_comments(schema: &mut SchemaFile, source: &str, doc_comments: &[Range<usize>]) {
+    attach(&mut schema.declarations, source, doc_comments);
+}
+
+fn attach(declarations: &mut [Declaration], source: &str, doc_comments: &[Range<usize>]) {
+    let doc = |decorators: &[Decorator], start: usize| {
+        let start = decorators
+            .first()
+            .map_or(start, |d| d.span.start.min(start));
+        doc_above(source, doc_comments, start)
+    };
+    for declaration in declarations {
+        match declaration {
+            Declaration::Model(model) => {
+                let comments = doc(&model.decorators, model.span.start);
+                model.doc = join(comments, model.doc.take());
+                for field in &mut model.fields {
+                    field.doc = doc(&field.decorators, field.span.start);
+                }
+            }
+            Declaration::Enum(r#enum) => {
+                let comments = doc(&r#enum.decorators, r#enum.span.start);
+                r#enum.doc = join(comments, r#enum.doc.take());
+            }
+            Declaration::Namespace(namespace) => {
+                attach(&mut namespace.declarations, source, doc_comments)
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Join the documentation of `first` and `second` with a line break.
+fn join(first: Option<String>, second: Option<String>) -> Option<String> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(format!("{}\n{}", first, second)),
+        (first, second) => first.or(second),
+    }
+}
+
+/// Returns the text of the run of doc comments directly above `start`.
+fn doc_above(source: &str, doc_comments: &[Range<usize>], start: usize) -> Option<String> {
+    let mut lines = vec![];
+    let mut next = start;
+    for comment in doc_comments.iter().rev().skip_while(|c| c.start >= start) {
+        let gap = &source[comment.end..next];
+        if !gap.trim().is_empty() || gap.matches('\n').count() != 1 {
+            break;
+        }
+        let text = &source[comment.start + 3..comment.end];
+        lines.push(text.strip_prefix(' ').unwrap_or(text).trim_end());
+        next = comment.start;
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    lines.reverse();
+    Some(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::Declaration;
+    use crate::parser::parse_schema;
+
+    fn model_doc(source: &str) -> Option<String> {
+        match &parse_schema(source, "schema.teo").schema.declarations[0] {
+            Declaration::Model(model) => model.doc.clone(),
+            _ => panic!("expected a model"),
+        }
+    }
+
+    #[test]
+    fn attaches_a_doc_comment_directly_above() {
+        assert_eq!(
+            model_doc("/// A user.\nmodel User { id: Int }").as_deref(),
+            Some("A user.")
+        );
+        assert_eq!(
+            model_doc("/// A user.\n@map(\"users\")\nmodel User { id: Int }").as_deref(),
+            Some("A user.")
+        );
+    }
+
+    #[test]
+    fn a_blank_line_breaks_the_association() {
+        assert_eq!(model_doc("/// A user.\n\nmodel User { id: Int }"), None);
+        assert_eq!(
+            model_doc("/// A user.\n// TODO\nmodel User { id: Int }"),
+            None
+        );
+    }
+
+    #[test]
+    fn concatenates_consecutive_lines_in_order() {
+        let source = "/// Unrelated.\n\n/// A user\n///   of the app.\nmodel User {\n  /// The id.\n  @id id: Int\n  name: String\n}\n/// Roles.\nenum Role { ADMIN }";
+        let schema = parse_schema(source, "schema.teo").schema;
+        let Declaration::Model(model) = &schema.declarations[0] else {
+            panic!("expected a model");
+        };
+        assert_eq!(model.doc.as_deref(), Some("A user\n  of the app."));
+        assert_eq!(model.fields[0].doc.as_deref(), Some("The id."));
+        assert_eq!(model.fields[1].doc, None);
+        let Declaration::Enum(r#enum) = &schema.declarations[1] else {
+            panic!("expected an enum");
+        };
+        assert_eq!(r#enum.doc.as_deref(), Some("Roles."));
+    }
+
+    #[test]
+    fn documents_a_model_with_a_doc_decorator() {
+        let source = "model User {\n  @@doc(\"A user.\")\n  id: Int\n}";
+        let schema = parse_schema(source, "schema.teo").schema;
+        let Declaration::Model(model) = &schema.declarations[0] else {
+            panic!("expected a model");
+        };
+        assert_eq!(model.doc.as_deref(), Some("A user."));
+        assert_eq!(model.fields.len(), 1);
+        assert!(model.decorators[0].block);
+    }
+
+    #[test]
+    fn puts_doc_comments_before_doc_decorators() {
+        let source =
+            "/// A user.\n@map(\"users\")\nmodel User {\n  @@doc(\"Of the app.\")\n  id: Int\n}";
+        assert_eq!(model_doc(source).as_deref(), Some("A user.\nOf the app."));
+    }
+
+    #[test]
+    fn documents_declarations_inside_a_namespace() {
+        let source = "namespace blog {\n  /// A post.\n  model Post {\n    /// The title.\n    title: String\n  }\n}";
+        let schema = parse_schema(source, "schema.teo").schema;
+        let post = schema.model("blog::Post").unwrap();
+        assert_eq!(post.doc.as_deref(), Some("A post."));
+        assert_eq!(post.fields[0].doc.as_deref(), Some("The title."));
+    }
+
+    #[test]
+    fn reports_a_doc_decorator_without_a_string() {
+        let result = parse_schema("model User { @@doc(1)\n id: Int }", "schema.teo");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].code, Some("invalid-doc"));
+        let Declaration::Model(model) = &result.schema.declarations[0] else {
+            panic!("expected a model");
+        };
+        assert_eq!(model.doc, None);
+    }
+}
@@ -0,0 +1,216 @@
+use super::{ParseError, Parser};
+use crate::ast::{EnumDeclaration, EnumMember, LiteralValue, TypeExpr};
+use crate::diagnostic::Diagnostic;
+use crate::lexer::Token;
+use std::ops::Range;
+
+impl Parser<'_> {
+    /// Parse an `enum Name { ... }` or `enum Name: Type { ... }`
+    /// declaration.
+    ///
+    /// Errors inside the body are recorded and the parser continues with
+    /// the next member. Only a broken header is returned as an error. Block
+    /// decorators are handled like those of [`Parser::parse_model`]. A
+    /// member value that doesn't fit an `Int`, `Float` or `String` backing
+    /// type is recorded as a `"mismatched-enum-value"` error.
+    pub fn parse_enum(&mut self) -> Result<EnumDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Enum, "`enum`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("an enum name")?;
+        let backing = match self.eat(Token::Colon) {
+            Some(_) => Some(self.parse_type()?),
+            None => None,
+        };
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let (members, decorators, end) =
+            self.parse_members_and_block_decorators(open, "enum", Self::parse_enum_member);
+        let doc = self.block_doc(&decorators);
+        if let Some(backing) = &backing {
+            self.check_enum_values(backing, &members);
+        }
+        self.trace_node("enum", first);
+        Ok(EnumDeclaration {
+            doc,
+            decorators,
+            name,
+            backing,
+            members,
+            span: start..end,
+            source_order,
+        })
+    }
+
+    /// Parse a `NAME` or `NAME = value` enum member with the decorators
+    /// written before it.
+    pub fn parse_enum_member(&mut self) -> Result<EnumMember, ParseError> {
+        let first = self.position();
+        let decorators = self.parse_decorators()?;
+        let name = self.parse_identifier("an enum member name")?;
+        let value = match self.eat(Token::Equal) {
+            Some(_) => Some(self.parse_literal()?),
+            None => None,
+        };
+        self.trace_node("enum member", first);
+        Ok(EnumMember {
+            decorators,
+            span: name.span.start..self.previous_span().end,
+            name,
+            value,
+        })
+    }
+
+    /// Record an error for each member value that doesn't fit `backing`.
+    fn check_enum_values(&mut self, backing: &TypeExpr, members: &[EnumMember]) {
+        let backing = match backing {
+            TypeExpr::Named { path, .. } if path.len() == 1 => path[0].name.as_str(),
+            _ => return,
+        };
+        for value in members.iter().filter_map(|member| member.value.as_ref()) {
+            if !fits_backing(&value.value, backing) {
+                self.report_diagnostic(
+                    Diagnostic::error(
+                        format!("expected a value of type `{}`", backing),
+                        value.span.clone(),
+                    )
+                    .with_code("mismatched-enum-value"),
+                );
+            }
+        }
+    }
+}
+
+/// Returns true if `value` can be the value of a member of an enum backed
+/// by the type named `backing`. Only `Int`, `Float` and `String` are
+/// checked, any value fits another backing type.
+fn fits_backing(value: &LiteralValue, backing: &str) -> bool {
+    match (backing, value) {
+        ("Int", LiteralValue::Number(number)) => !number.contains('.'),
+        ("Float", LiteralValue::Number(_)) => true,
+        ("String", LiteralValue::String(_)) => true,
+        ("Int" | "Float" | "String", _) => false,
+        _ => true,
+    }
+}
+
+/// Parse a single enum declaration from `tokens`, which were lexed from
+/// `source`. Returns the enum, if its header could be parsed, and every
+/// error found along the way.
+pub fn parse_enum(
+    source: &str,
+    tokens: &[(Token, Range<usize>)],
+) -> (Option<EnumDeclaration>, Vec<Diagnostic>) {
+    let mut parser = Parser::new(source, tokens);
+    let r#enum = match parser.parse_enum() {
+        Ok(r#enum) => Some(r#enum),
+        Err(error) => {
+            parser.report(error);
+            None
+        }
+    };
+    (r#enum, parser.take_diagnostics())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::LiteralValue;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> (Option<EnumDeclaration>, Vec<Diagnostic>) {
+        parse_enum(source, &tokenize(source))
+    }
+
+    fn member_names(r#enum: &EnumDeclaration) -> Vec<&str> {
+        r#enum
+            .members
+            .iter()
+            .map(|m| m.name.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parses_basic_enum() {
+        let (r#enum, diagnostics) = parse("enum Role { ADMIN, USER }");
+        let r#enum = r#enum.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(r#enum.name.name, "Role");
+        assert_eq!(member_names(&r#enum), vec!["ADMIN", "USER"]);
+        assert_eq!(r#enum.span, 0..25);
+    }
+
+    #[test]
+    fn parses_newline_separated_members_with_values() {
+        let (r#enum, diagnostics) = parse("enum Role {\n ADMIN = \"admin\"\n USER = 2\n}");
+        let r#enum = r#enum.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(
+            r#enum.members[0].value.as_ref().unwrap().value,
+            LiteralValue::String("admin".to_owned())
+        );
+        assert_eq!(
+            r#enum.members[1].value.as_ref().unwrap().value,
+            LiteralValue::Number("2".to_owned())
+        );
+    }
+
+    #[test]
+    fn parses_dangling_comma() {
+        let (r#enum, diagnostics) = parse("enum Role { ADMIN, USER, }");
+        assert!(diagnostics.is_empty());
+        assert_eq!(member_names(&r#enum.unwrap()), vec!["ADMIN", "USER"]);
+    }
+
+    #[test]
+    fn parses_empty_enum() {
+        let (r#enum, diagnostics) = parse("enum Role { }");
+        assert!(diagnostics.is_empty());
+        assert!(r#enum.unwrap().members.is_empty());
+    }
+
+    #[test]
+    fn parses_an_int_backed_enum() {
+        let (r#enum, diagnostics) = parse("enum Status: Int { ACTIVE = 1, INACTIVE = 0x2 }");
+        let r#enum = r#enum.unwrap();
+        assert!(diagnostics.is_empty());
+        match &r#enum.backing {
+            Some(TypeExpr::Named { path, span, .. }) => {
+                assert_eq!(path[0].name, "Int");
+                assert_eq!(*span, 13..16);
+            }
+            backing => panic!("unexpected backing {:?}", backing),
+        }
+        assert_eq!(member_names(&r#enum), vec!["ACTIVE", "INACTIVE"]);
+    }
+
+    #[test]
+    fn parses_a_string_backed_enum() {
+        let (r#enum, diagnostics) = parse("enum Role: String { ADMIN = \"admin\", USER }");
+        assert!(diagnostics.is_empty());
+        assert!(r#enum.unwrap().backing.is_some());
+    }
+
+    #[test]
+    fn reports_values_that_do_not_fit_the_backing_type() {
+        let source = "enum Role: String { ADMIN = \"admin\", USER = 2 }";
+        let (r#enum, diagnostics) = parse(source);
+        assert_eq!(r#enum.unwrap().members.len(), 2);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("mismatched-enum-value"));
+        assert_eq!(diagnostics[0].message, "expected a value of type `String`");
+        assert_eq!(&source[diagnostics[0].span.clone()], "2");
+        let (_, diagnostics) = parse("enum Status: Int { ACTIVE = 1.5 }");
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn recovers_from_a_member_with_a_missing_value() {
+        let (r#enum, diagnostics) = parse("enum Role { ADMIN = , USER }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "expected a literal, found comma `,`"
+        );
+        assert_eq!(member_names(&r#enum.unwrap()), vec!["USER"]);
+    }
+}
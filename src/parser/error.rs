@@ -0,0 +1,38 @@
+use crate::diagnostic::Diagnostic;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// # Parse error
+///
+/// A syntax error found by the parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// A human readable description of the problem.
+    pub message: String,
+    /// The byte span the error points at.
+    pub span: Range<usize>,
+}
+
+impl ParseError {
+    /// Create a new parse error.
+    pub fn new(message: impl Into<String>, span: Range<usize>) -> Self {
+        Self {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {:?}", self.message, self.span)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for Diagnostic {
+    fn from(error: ParseError) -> Self {
+        Diagnostic::error(error.message, error.span)
+    }
+}
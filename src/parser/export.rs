@@ -0,0 +1,75 @@
+use super::{ParseError, Parser};
+use crate::ast::ExportStatement;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse an `export { a, b }` or `export { a, b } from "path"`
+    /// statement.
+    pub fn parse_export(&mut self) -> Result<ExportStatement, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Export, "`export`")?.start;
+        let source_order = self.next_source_order();
+        if !self.at(Token::BraceOpen) {
+            return Err(self.error_expected("`{`"));
+        }
+        let names = self.parse_identifier_list("export")?;
+        let (source, source_span) = match self.eat(Token::From) {
+            Some(_) => {
+                if !self.at(Token::String) {
+                    return Err(self.error_expected("a source string"));
+                }
+                let (source, span) = self.parse_string()?;
+                (Some(source), Some(span))
+            }
+            None => (None, None),
+        };
+        self.trace_node("export", first);
+        Ok(ExportStatement {
+            names,
+            source,
+            source_span,
+            span: start..self.previous_span().end,
+            source_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Identifier;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<ExportStatement, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_export()
+    }
+
+    #[test]
+    fn parses_a_local_export() {
+        let export = parse("export { User, Role }").unwrap();
+        assert_eq!(
+            export.names,
+            vec![
+                Identifier::new("User", 9..13),
+                Identifier::new("Role", 15..19)
+            ]
+        );
+        assert!(export.source.is_none());
+        assert_eq!(export.span, 0..21);
+    }
+
+    #[test]
+    fn parses_a_re_export_from_another_file() {
+        let export = parse(r#"export { User } from "./user""#).unwrap();
+        assert_eq!(export.source.as_deref(), Some("./user"));
+        assert_eq!(export.source_span, Some(21..29));
+        assert_eq!(export.span, 0..29);
+    }
+
+    #[test]
+    fn reports_an_unterminated_export_list() {
+        let error = parse(r#"export { User from "./user""#).unwrap_err();
+        assert_eq!(error.message, "unterminated export list, expected `}`");
+    }
+}
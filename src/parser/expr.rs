@@ -0,0 +1,475 @@
+use super::{ParseError, Parser};
+use crate::ast::{BinaryOperator, DictEntry, Expr, Identifier, UnaryOperator};
+use crate::diagnostic::merge_spans;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse an expression.
+    ///
+    /// Member access, optional member access like `a?.b`, subscripts and
+    /// calls bind tightest, then the prefix
+    /// operators `!` and `-`, then the binary operators in the order given
+    /// by [`BinaryOperator::precedence`].
+    pub fn parse_expr(&mut self) -> Result<Expr, ParseError> {
+        self.nested(|parser| parser.parse_binary(1))
+    }
+
+    /// Parse a chain of binary operators whose precedence is at least
+    /// `min_precedence`.
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expr, ParseError> {
+        let mut lhs = self.parse_unary()?;
+        while let Some(operator) = self.peek().and_then(binary_operator) {
+            let precedence = operator.precedence();
+            if precedence < min_precedence {
+                break;
+            }
+            self.advance();
+            let rhs = self.parse_binary(precedence + 1)?;
+            lhs = Expr::Binary {
+                span: merge_spans(&lhs.span(), &rhs.span()),
+                operator,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        let operator = match self.peek() {
+            Some(Token::Bang) => UnaryOperator::Not,
+            Some(Token::Minus) => UnaryOperator::Negate,
+            _ => return self.parse_postfix(),
+        };
+        let start = self.peek_span().start;
+        self.advance();
+        let operand = self.nested(Self::parse_unary)?;
+        Ok(Expr::Unary {
+            span: start..operand.span().end,
+            operator,
+            operand: Box::new(operand),
+        })
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            let start = expr.span().start;
+            if self.eat(Token::Period).is_some() {
+                let property = self.parse_identifier("a member name")?;
+                expr = Expr::Member {
+                    span: start..property.span.end,
+                    object: Box::new(expr),
+                    property,
+                };
+            } else if self.at(Token::Question) && self.peek_nth(1) == Some(Token::Period) {
+                self.advance();
+                self.advance();
+                let property = self.parse_identifier("a member name")?;
+                expr = Expr::OptionalMember {
+                    span: start..property.span.end,
+                    object: Box::new(expr),
+                    property,
+                };
+            } else if self.at(Token::BracketOpen) && !self.at_line_start() {
+                self.advance();
+                let index = self.parse_expr()?;
+                let close = self.expect(Token::BracketClose, "`]`")?;
+                expr = Expr::Index {
+                    object: Box::new(expr),
+                    index: Box::new(index),
+                    span: start..close.end,
+                };
+            } else if self.eat(Token::ParenOpen).is_some() {
+                let arguments = self.parse_arguments()?;
+                expr = Expr::Call {
+                    callee: Box::new(expr),
+                    arguments,
+                    span: start..self.previous_span().end,
+                };
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseError> {
+        match self.peek() {
+            Some(Token::String) => self.parse_string_expr(),
+            Some(Token::Number) | Some(Token::Regex) => Ok(Expr::Literal(self.parse_literal()?)),
+            Some(Token::Identifier) => Ok(Expr::Identifier(self.parse_identifier("a name")?)),
+            Some(Token::BracketOpen) => self.parse_array(),
+            Some(Token::BraceOpen) => self.parse_dict(),
+            Some(Token::ParenOpen) => self.parse_parenthesized(),
+            _ => Err(self.error_expected("an expression")),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Expr, ParseError> {
+        let open = self.expect(Token::BracketOpen, "`[`")?;
+        let elements = self.parse_comma_separated(Token::BracketClose, "`]`", Self::parse_expr)?;
+        Ok(Expr::Array {
+            elements,
+            span: open.start..self.previous_span().end,
+        })
+    }
+
+    /// Parse a parenthesized expression, or a tuple if the parentheses
+    /// are empty or contain a `,`.
+    fn parse_parenthesized(&mut self) -> Result<Expr, ParseError> {
+        let open = self.expect(Token::ParenOpen, "`(`")?;
+        if let Some(close) = self.eat(Token::ParenClose) {
+            return Ok(Expr::Tuple {
+                elements: vec![],
+                span: open.start..close.end,
+            });
+        }
+        let first = self.parse_expr()?;
+        if self.eat(Token::Comma).is_none() {
+            self.expect(Token::ParenClose, "`,` or `)`")?;
+            return Ok(first);
+        }
+        let mut elements = vec![first];
+        elements.extend(self.parse_comma_separated(Token::ParenClose, "`)`", Self::parse_expr)?);
+        Ok(Expr::Tuple {
+            elements,
+            span: open.start..self.previous_span().end,
+        })
+    }
+
+    fn parse_dict(&mut self) -> Result<Expr, ParseError> {
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let entries = self.parse_comma_separated(Token::BraceClose, "`}`", |parser| {
+            let quoted = parser.at(Token::String);
+            let key = match quoted {
+                true => {
+                    let (value, span) = parser.parse_string()?;
+                    Identifier::new(value, span)
+                }
+                false => parser.parse_identifier("a dictionary key")?,
+            };
+            parser.expect(Token::Colon, "`:`")?;
+            let value = parser.parse_expr()?;
+            Ok(DictEntry {
+                span: merge_spans(&key.span, &value.span()),
+                key,
+                quoted,
+                value,
+            })
+        })?;
+        Ok(Expr::Dict {
+            entries,
+            span: open.start..self.previous_span().end,
+        })
+    }
+
+    /// Parse `item, item, ...` up to and including the `close` token. A
+    /// trailing comma is allowed.
+    pub(crate) fn parse_comma_separated<T>(
+        &mut self,
+        close: Token,
+        close_description: &str,
+        mut parse_item: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![];
+        loop {
+            if self.eat(close).is_some() {
+                return Ok(items);
+            }
+            items.push(parse_item(self)?);
+            if self.eat(Token::Comma).is_none() {
+                self.expect(close, &format!("`,` or {}", close_description))?;
+                return Ok(items);
+            }
+        }
+    }
+}
+
+fn binary_operator(token: Token) -> Option<BinaryOperator> {
+    Some(match token {
+        Token::PipePipe => BinaryOperator::Or,
+        Token::AmpAmp => BinaryOperator::And,
+        Token::EqualEqual => BinaryOperator::Equal,
+        Token::BangEqual => BinaryOperator::NotEqual,
+        Token::Pipe => BinaryOperator::BitOr,
+        Token::Caret => BinaryOperator::BitXor,
+        Token::Ampersand => BinaryOperator::BitAnd,
+        Token::Plus => BinaryOperator::Add,
+        Token::Minus => BinaryOperator::Subtract,
+        Token::Star => BinaryOperator::Multiply,
+        Token::Slash => BinaryOperator::Divide,
+        Token::Percent => BinaryOperator::Modulo,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{LiteralValue, StringPart};
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<Expr, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_expr()
+    }
+
+    /// Renders an expression as an s-expression to compare tree shapes.
+    fn shape(expr: &Expr) -> String {
+        match expr {
+            Expr::Literal(literal) => match &literal.value {
+                LiteralValue::String(value) => format!("{:?}", value),
+                LiteralValue::Number(value) | LiteralValue::Regex(value) => value.clone(),
+            },
+            Expr::Identifier(identifier) => identifier.name.clone(),
+            Expr::Array { elements, .. } => format!(
+                "[{}]",
+                elements
+                    .iter()
+                    .map(shape)
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+            Expr::Tuple { elements, .. } => format!(
+                "(tuple{})",
+                elements
+                    .iter()
+                    .map(|element| format!(" {}", shape(element)))
+                    .collect::<String>()
+            ),
+            Expr::Dict { entries, .. } => format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|entry| format!("{}: {}", entry.key.name, shape(&entry.value)))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+            Expr::Unary {
+                operator, operand, ..
+            } => format!("({:?} {})", operator, shape(operand)),
+            Expr::Binary {
+                operator, lhs, rhs, ..
+            } => format!("({:?} {} {})", operator, shape(lhs), shape(rhs)),
+            Expr::Member {
+                object, property, ..
+            } => format!("(. {} {})", shape(object), property.name),
+            Expr::OptionalMember {
+                object, property, ..
+            } => format!("(?. {} {})", shape(object), property.name),
+            Expr::Index { object, index, .. } => {
+                format!("(index {} {})", shape(object), shape(index))
+            }
+            Expr::Call {
+                callee, arguments, ..
+            } => format!(
+                "(call {}{})",
+                shape(callee),
+                arguments
+                    .iter()
+                    .map(|argument| format!(" {}", shape(&argument.value)))
+                    .collect::<String>()
+            ),
+            Expr::InterpolatedString { parts, .. } => format!(
+                "(string{})",
+                parts
+                    .iter()
+                    .map(|part| match part {
+                        StringPart::Text(text) => format!(" {:?}", text),
+                        StringPart::Expr(expr) => format!(" {}", shape(expr)),
+                    })
+                    .collect::<String>()
+            ),
+        }
+    }
+
+    fn parsed_shape(source: &str) -> String {
+        shape(&parse(source).unwrap())
+    }
+
+    #[test]
+    fn multiplication_binds_tighter_than_addition() {
+        assert_eq!(parsed_shape("1 + 2 * 3"), "(Add 1 (Multiply 2 3))");
+        assert_eq!(parsed_shape("1 * 2 + 3"), "(Add (Multiply 1 2) 3)");
+    }
+
+    #[test]
+    fn binary_operators_are_left_associative() {
+        assert_eq!(parsed_shape("1 - 2 - 3"), "(Subtract (Subtract 1 2) 3)");
+        assert_eq!(parsed_shape("6 / 2 / 3"), "(Divide (Divide 6 2) 3)");
+        assert_eq!(parsed_shape("6/2/3"), "(Divide (Divide 6 2) 3)");
+        assert_eq!(parsed_shape("a/b/c"), "(Divide (Divide a b) c)");
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and() {
+        assert_eq!(parsed_shape("!a && b"), "(And (Not a) b)");
+        assert_eq!(parsed_shape("a || b && c"), "(Or a (And b c))");
+    }
+
+    #[test]
+    fn equality_binds_looser_than_arithmetic() {
+        assert_eq!(parsed_shape("x == y + 1"), "(Equal x (Add y 1))");
+        assert_eq!(parsed_shape("a != b | c"), "(NotEqual a (BitOr b c))");
+    }
+
+    #[test]
+    fn parentheses_group() {
+        assert_eq!(parsed_shape("(1 + 2) * 3"), "(Multiply (Add 1 2) 3)");
+    }
+
+    #[test]
+    fn member_access_and_calls_bind_tightest() {
+        assert_eq!(
+            parsed_shape("obj.method(1, 2)"),
+            "(call (. obj method) 1 2)"
+        );
+        assert_eq!(parsed_shape("a.b.c"), "(. (. a b) c)");
+        assert_eq!(
+            parsed_shape("-a.b[0] * 2"),
+            "(Multiply (Negate (index (. a b) 0)) 2)"
+        );
+    }
+
+    #[test]
+    fn parses_optional_member_access() {
+        assert_eq!(parsed_shape("a?.b"), "(?. a b)");
+        assert_eq!(parsed_shape("a?.b?.c"), "(?. (?. a b) c)");
+        assert_eq!(
+            parsed_shape("a?.b.c(1) == !x?.y"),
+            "(Equal (call (. (?. a b) c) 1) (Not (?. x y)))"
+        );
+        assert_eq!(parse("a?.b").unwrap().span(), 0..4);
+        let error = parse("a?.1").unwrap_err();
+        assert_eq!(error.message, "expected a member name, found number `1`");
+    }
+
+    #[test]
+    fn a_question_mark_alone_does_not_continue_an_expression() {
+        let tokens = tokenize("x?");
+        let mut parser = Parser::new("x?", &tokens);
+        assert_eq!(parser.parse_expr().unwrap().span(), 0..1);
+        assert_eq!(parser.peek(), Some(Token::Question));
+    }
+
+    #[test]
+    fn call_span_covers_the_arguments() {
+        let expr = parse("obj.method(1, 2)").unwrap();
+        assert_eq!(expr.span(), 0..16);
+    }
+
+    #[test]
+    fn parses_regex_literal() {
+        assert_eq!(parsed_shape("/^a+$/"), "^a+$");
+    }
+
+    #[test]
+    fn reports_missing_operand() {
+        let error = parse("1 +").unwrap_err();
+        assert_eq!(error.message, "expected an expression, found end of file");
+    }
+
+    #[test]
+    fn parses_nested_array_and_dict() {
+        let expr = parse(r#"[{ name: "a", tags: [1, 2] }, b]"#).unwrap();
+        let Expr::Array { elements, span } = expr else {
+            panic!("expected an array");
+        };
+        assert_eq!(span, 0..32);
+        let Expr::Dict { entries, .. } = &elements[0] else {
+            panic!("expected a dictionary");
+        };
+        assert_eq!(entries[0].key.name, "name");
+        assert!(matches!(
+            &entries[0].value,
+            Expr::Literal(literal) if literal.value == LiteralValue::String("a".to_owned())
+        ));
+        assert!(matches!(&entries[1].value, Expr::Array { elements, .. } if elements.len() == 2));
+        assert!(matches!(&elements[1], Expr::Identifier(identifier) if identifier.name == "b"));
+    }
+
+    #[test]
+    fn parses_arrays_with_and_without_elements() {
+        assert_eq!(parsed_shape("[a, b]"), "[a b]");
+        assert_eq!(parsed_shape("[a, b,]"), "[a b]");
+        assert_eq!(parsed_shape("[]"), "[]");
+        assert_eq!(parse("[]").unwrap().span(), 0..2);
+    }
+
+    #[test]
+    fn parses_tuples() {
+        assert_eq!(
+            parsed_shape(r#"("0.0.0.0", 5000)"#),
+            r#"(tuple "0.0.0.0" 5000)"#
+        );
+        assert_eq!(parsed_shape("(1, 2,)"), "(tuple 1 2)");
+        assert_eq!(parsed_shape("()"), "(tuple)");
+    }
+
+    #[test]
+    fn a_trailing_comma_makes_a_one_tuple() {
+        assert_eq!(parsed_shape("(a)"), "a");
+        assert_eq!(parsed_shape("(a,)"), "(tuple a)");
+        assert_eq!(parsed_shape("((a))"), "a");
+        assert_eq!(parse("(a,)").unwrap().span(), 0..4);
+    }
+
+    #[test]
+    fn parses_dictionaries() {
+        assert_eq!(
+            parsed_shape(r#"{ name: "a", age: 1 }"#),
+            r#"{name: "a" age: 1}"#
+        );
+        assert_eq!(parsed_shape("{ a: 1, }"), "{a: 1}");
+        assert_eq!(parsed_shape("{}"), "{}");
+        assert_eq!(parsed_shape("{ a: { b: { c: 1 } } }"), "{a: {b: {c: 1}}}");
+    }
+
+    #[test]
+    fn parses_quoted_dictionary_keys() {
+        let source = r#"{ "content-type": "json", accept: "*" }"#;
+        let Expr::Dict { entries, .. } = parse(source).unwrap() else {
+            panic!("expected a dictionary");
+        };
+        assert_eq!(entries[0].key.name, "content-type");
+        assert_eq!(entries[0].key.span, 2..16);
+        assert!(entries[0].quoted);
+        assert!(!entries[1].quoted);
+    }
+
+    #[test]
+    fn reports_a_dictionary_entry_without_a_value() {
+        let error = parse("{ name: , age: 1 }").unwrap_err();
+        assert_eq!(error.message, "expected an expression, found comma `,`");
+        let error = parse("{ name }").unwrap_err();
+        assert_eq!(error.message, "expected `:`, found closing brace `}`");
+    }
+
+    #[test]
+    fn reports_unterminated_array() {
+        let error = parse("[1, 2").unwrap_err();
+        assert_eq!(error.message, "expected `,` or `]`, found end of file");
+    }
+
+    #[test]
+    fn reports_nesting_too_deep() {
+        let source = format!("{}{}", "[".repeat(1000), "]".repeat(1000));
+        let error = parse(&source).unwrap_err();
+        assert_eq!(error.message, "nesting too deep");
+        assert_eq!(error.span, 256..257);
+        let error = parse(&"!".repeat(1000)).unwrap_err();
+        assert_eq!(error.message, "nesting too deep");
+    }
+
+    #[test]
+    fn the_nesting_limit_can_be_changed() {
+        let tokens = tokenize("[[[1]]]");
+        assert!(Parser::new("[[[1]]]", &tokens).parse_expr().is_ok());
+        let error = Parser::new("[[[1]]]", &tokens)
+            .with_max_depth(3)
+            .parse_expr()
+            .unwrap_err();
+        assert_eq!(error.message, "nesting too deep");
+        assert_eq!(error.span, 3..4);
+    }
+}
@@ -0,0 +1,178 @@
+use super::{ParseError, Parser};
+use crate::ast::{FunctionDeclaration, FunctionParameter};
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Returns true if the next tokens start a function declaration, which
+    /// is `function` after an optional `declare` and an optional `native`.
+    pub(crate) fn at_function(&self) -> bool {
+        let mut n = 0;
+        for modifier in [Token::Declare, Token::Native] {
+            if self.peek_nth(n) == Some(modifier) {
+                n += 1;
+            }
+        }
+        self.peek_nth(n) == Some(Token::Function)
+    }
+
+    /// Parse a `function name(parameters): ReturnType { body }` declaration,
+    /// which may start with `declare` and `native`.
+    ///
+    /// A declared or native function is implemented elsewhere and has no
+    /// body, every other function has one. A missing or unexpected body is
+    /// recorded as an error and the function is returned as written.
+    pub fn parse_function(&mut self) -> Result<FunctionDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.peek_span().start;
+        let declare = self.eat(Token::Declare).is_some();
+        let native = self.eat(Token::Native).is_some();
+        self.expect(Token::Function, "`function`")?;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a function name")?;
+        let type_parameters = self.parse_type_parameters()?;
+        self.expect(Token::ParenOpen, "`(`")?;
+        let parameters =
+            self.parse_comma_separated(Token::ParenClose, "`)`", Self::parse_function_parameter)?;
+        let return_type = match self.eat(Token::Colon) {
+            Some(_) => Some(self.parse_type()?),
+            None => None,
+        };
+        let body = match self.at(Token::BraceOpen) {
+            true => Some(self.parse_pipeline_body()?),
+            false => None,
+        };
+        match (&body, declare || native) {
+            (Some(body), true) => self.report(ParseError::new(
+                format!(
+                    "{} function `{}` cannot have a body",
+                    modifier(declare),
+                    name.name
+                ),
+                body.span(),
+            )),
+            (None, false) => self.report(ParseError::new(
+                format!("function `{}` has no body", name.name),
+                name.span.clone(),
+            )),
+            _ => (),
+        }
+        self.trace_node("function", first);
+        Ok(FunctionDeclaration {
+            declare,
+            native,
+            name,
+            type_parameters,
+            parameters,
+            return_type,
+            body,
+            span: start..self.previous_span().end,
+            source_order,
+        })
+    }
+
+    /// Parse a `name: Type` or `name: Type = default` function parameter.
+    fn parse_function_parameter(&mut self) -> Result<FunctionParameter, ParseError> {
+        let name = self.parse_identifier("a parameter name")?;
+        self.expect(Token::Colon, "`:`")?;
+        let type_expr = self.parse_type()?;
+        let default = match self.eat(Token::Equal) {
+            Some(_) => Some(self.parse_expr()?),
+            None => None,
+        };
+        Ok(FunctionParameter {
+            span: name.span.start..self.previous_span().end,
+            name,
+            type_expr,
+            default,
+        })
+    }
+}
+
+fn modifier(declare: bool) -> &'static str {
+    match declare {
+        true => "declared",
+        false => "native",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Declaration, Expr, TypeExpr};
+    use crate::diagnostic::Diagnostic;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> (Result<FunctionDeclaration, ParseError>, Vec<Diagnostic>) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let function = parser.parse_function();
+        (function, parser.take_diagnostics())
+    }
+
+    #[test]
+    fn parses_a_native_declaration_without_a_body() {
+        let source = "declare native function format(value: Int, pattern: String): String";
+        let (function, diagnostics) = parse(source);
+        let function = function.unwrap();
+        assert!(diagnostics.is_empty());
+        assert!(function.declare && function.native);
+        assert_eq!(function.name.name, "format");
+        let names: Vec<&str> = function
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["value", "pattern"]);
+        assert_eq!(
+            function
+                .return_type
+                .as_ref()
+                .and_then(TypeExpr::name)
+                .as_deref(),
+            Some("String")
+        );
+        assert!(function.body.is_none());
+        assert_eq!(function.span, 0..source.len());
+    }
+
+    #[test]
+    fn parses_a_parameter_with_a_default_value() {
+        let (function, diagnostics) = parse("function greet(name: String = \"world\") { name }");
+        let function = function.unwrap();
+        assert!(diagnostics.is_empty());
+        assert!(!function.declare && !function.native);
+        let parameter = &function.parameters[0];
+        assert!(matches!(parameter.default, Some(Expr::Literal(_))));
+        assert_eq!(parameter.span, 15..37);
+        assert!(function.return_type.is_none());
+        assert!(matches!(function.body, Some(Expr::Identifier(_))));
+    }
+
+    #[test]
+    fn parses_a_generic_function() {
+        let (function, _) = parse("declare function first<T>(items: T[]): T?");
+        let function = function.unwrap();
+        assert_eq!(function.type_parameters[0].name.name, "T");
+        assert_eq!(function.parameters[0].type_expr.to_string(), "T[]");
+    }
+
+    #[test]
+    fn parses_functions_among_declarations() {
+        let source = "declare function now(): DateTime\nmodel User { id: Int }";
+        let (schema, diagnostics) = crate::parser::parse(source, &tokenize(source));
+        assert!(diagnostics.is_empty());
+        assert!(matches!(schema.declarations[0], Declaration::Function(_)));
+        assert!(matches!(schema.declarations[1], Declaration::Model(_)));
+    }
+
+    #[test]
+    fn reports_a_missing_or_unexpected_body() {
+        let (_, diagnostics) = parse("function greet()");
+        assert_eq!(diagnostics[0].message, "function `greet` has no body");
+        let (_, diagnostics) = parse("native function now(): DateTime { 1 }");
+        assert_eq!(
+            diagnostics[0].message,
+            "native function `now` cannot have a body"
+        );
+    }
+}
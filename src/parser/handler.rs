@@ -0,0 +1,76 @@
+use super::{ParseError, Parser};
+use crate::ast::{Expr, HandlerDeclaration};
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a `handler name { pipeline }` declaration.
+    ///
+    /// A handler without a body is recorded as an error and returned
+    /// without one.
+    pub fn parse_handler(&mut self) -> Result<HandlerDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Handler, "`handler`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a handler name")?;
+        let body = match self.at(Token::BraceOpen) {
+            true => Some(self.parse_pipeline_body()?),
+            false => {
+                self.report(ParseError::new(
+                    format!("handler `{}` has no body", name.name),
+                    name.span.clone(),
+                ));
+                None
+            }
+        };
+        self.trace_node("handler", first);
+        Ok(HandlerDeclaration {
+            decorators: vec![],
+            name,
+            body,
+            span: start..self.previous_span().end,
+            source_order,
+        })
+    }
+
+    /// Parse a `{ pipeline }` body of a handler or a middleware.
+    pub(crate) fn parse_pipeline_body(&mut self) -> Result<Expr, ParseError> {
+        self.expect(Token::BraceOpen, "`{`")?;
+        let body = self.parse_expr()?;
+        self.expect(Token::BraceClose, "`}`")?;
+        Ok(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Diagnostic;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> (Result<HandlerDeclaration, ParseError>, Vec<Diagnostic>) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let handler = parser.parse_handler();
+        (handler, parser.take_diagnostics())
+    }
+
+    #[test]
+    fn parses_a_named_handler_with_a_pipeline_body() {
+        let (handler, diagnostics) = parse("handler signIn { identity.signIn(credentials) }");
+        let handler = handler.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(handler.name.name, "signIn");
+        assert_eq!(handler.span, 0..47);
+        assert!(matches!(handler.body, Some(Expr::Call { .. })));
+    }
+
+    #[test]
+    fn reports_a_handler_without_a_body() {
+        let (handler, diagnostics) = parse("handler signIn");
+        let handler = handler.unwrap();
+        assert!(handler.body.is_none());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "handler `signIn` has no body");
+        assert_eq!(diagnostics[0].span, 8..14);
+    }
+}
@@ -0,0 +1,136 @@
+use super::{ParseError, Parser};
+use crate::ast::{Identifier, ImportKind, ImportStatement};
+use crate::lexer::Token;
+use std::ops::Range;
+
+impl Parser<'_> {
+    /// Parse an `import { a, b } from "path"`, `import "path"` or
+    /// `include "path"` statement.
+    pub fn parse_import(&mut self) -> Result<ImportStatement, ParseError> {
+        let first = self.position();
+        let start = self.peek_span().start;
+        let source_order = self.next_source_order();
+        let kind = if self.eat(Token::Import).is_some() {
+            ImportKind::Import
+        } else if self.eat(Token::Include).is_some() {
+            ImportKind::Include
+        } else {
+            return Err(self.error_expected("`import` or `include`"));
+        };
+        let mut identifiers = vec![];
+        if kind == ImportKind::Import && self.at(Token::BraceOpen) {
+            identifiers = self.parse_identifier_list("import")?;
+            self.expect(Token::From, "`from`")?;
+        }
+        if !self.at(Token::String) {
+            return Err(self.error_expected("a source string"));
+        }
+        let (source, source_span) = self.parse_string()?;
+        self.trace_node("import", first);
+        Ok(ImportStatement {
+            kind,
+            identifiers,
+            source,
+            source_span: source_span.clone(),
+            span: start..source_span.end,
+            source_order,
+        })
+    }
+
+    /// Parse a `{ a, b }` list of the identifiers of an import or export.
+    pub(crate) fn parse_identifier_list(
+        &mut self,
+        list: &str,
+    ) -> Result<Vec<Identifier>, ParseError> {
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let mut identifiers = vec![];
+        loop {
+            if self.eat(Token::BraceClose).is_some() {
+                return Ok(identifiers);
+            }
+            if self.is_at_end() || self.at(Token::From) {
+                return Err(ParseError::new(
+                    format!("unterminated {} list, expected `}}`", list),
+                    open,
+                ));
+            }
+            let span = self.expect(Token::Identifier, "an identifier")?;
+            identifiers.push(Identifier::new(self.text(span.clone()), span));
+            if self.eat(Token::Comma).is_none() && !self.at(Token::BraceClose) {
+                if self.is_at_end() || self.at(Token::From) {
+                    return Err(ParseError::new(
+                        format!("unterminated {} list, expected `}}`", list),
+                        open,
+                    ));
+                }
+                return Err(self.error_expected("`,` or `}`"));
+            }
+        }
+    }
+}
+
+/// Parse a single import statement from `tokens`, which were lexed from
+/// `source`.
+pub fn parse_import(
+    source: &str,
+    tokens: &[(Token, Range<usize>)],
+) -> Result<ImportStatement, ParseError> {
+    Parser::new(source, tokens).parse_import()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<ImportStatement, ParseError> {
+        parse_import(source, &tokenize(source))
+    }
+
+    #[test]
+    fn parses_multi_identifier_import() {
+        let import = parse(r#"import { a, b } from "./path""#).unwrap();
+        assert_eq!(import.kind, ImportKind::Import);
+        assert_eq!(
+            import.identifiers,
+            vec![Identifier::new("a", 9..10), Identifier::new("b", 12..13)]
+        );
+        assert_eq!(import.source, "./path");
+        assert_eq!(import.source_span, 21..29);
+        assert_eq!(import.span, 0..29);
+    }
+
+    #[test]
+    fn parses_trailing_comma_in_import_list() {
+        let import = parse(r#"import { a, } from "./path""#).unwrap();
+        assert_eq!(import.identifiers.len(), 1);
+    }
+
+    #[test]
+    fn parses_include() {
+        let import = parse(r#"include "./models""#).unwrap();
+        assert_eq!(import.kind, ImportKind::Include);
+        assert!(import.identifiers.is_empty());
+        assert_eq!(import.source, "./models");
+    }
+
+    #[test]
+    fn reports_missing_from() {
+        let error = parse(r#"import { a } "./path""#).unwrap_err();
+        assert_eq!(error.message, "expected `from`, found string literal");
+        assert_eq!(error.span, 13..21);
+    }
+
+    #[test]
+    fn reports_unterminated_brace() {
+        let error = parse(r#"import { a, b from "./path""#).unwrap_err();
+        assert_eq!(error.message, "unterminated import list, expected `}`");
+        assert_eq!(error.span, 7..8);
+    }
+
+    #[test]
+    fn reports_missing_source() {
+        let error = parse("import { a } from").unwrap_err();
+        assert_eq!(error.message, "expected a source string, found end of file");
+    }
+}
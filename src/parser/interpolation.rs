@@ -0,0 +1,231 @@
+use super::{ParseError, Parser};
+use crate::ast::{Expr, Literal, LiteralValue, StringPart};
+use crate::lexer::{tokenize, unescape, Token};
+use std::ops::Range;
+
+impl Parser<'_> {
+    /// Parse a string literal, or adjacent ones like [`Parser::parse_string`],
+    /// as an expression.
+    ///
+    /// Every unescaped `${...}` inside embeds an expression, making an
+    /// [`Expr::InterpolatedString`]; a string without any is an
+    /// [`Expr::Literal`]. A `\${` is a literal `${`. A `${` without a
+    /// matching `}` in its literal is recorded as an error and read as
+    /// text, and an embedded expression that doesn't parse is recorded and
+    /// left out.
+    pub fn parse_string_expr(&mut self) -> Result<Expr, ParseError> {
+        let first = self.expect(Token::String, "a string")?;
+        let mut parts = vec![];
+        let mut end = first.end;
+        self.parse_string_parts(first.clone(), &mut parts);
+        while let Some(span) = self.eat(Token::String) {
+            end = span.end;
+            self.parse_string_parts(span, &mut parts);
+        }
+        let span = first.start..end;
+        match parts.as_slice() {
+            [] => Ok(string_literal(String::new(), span)),
+            [StringPart::Text(text)] => Ok(string_literal(text.clone(), span)),
+            _ => Ok(Expr::InterpolatedString { parts, span }),
+        }
+    }
+
+    /// Append the parts of the string literal at `span` to `parts`.
+    fn parse_string_parts(&mut self, span: Range<usize>, parts: &mut Vec<StringPart>) {
+        let text = self.text(span.clone());
+        let inner = &text[1..text.len() - 1];
+        let offset = span.start + 1;
+        let bytes = inner.as_bytes();
+        let mut text_start = 0;
+        let mut index = 0;
+        while index < bytes.len() {
+            match bytes[index] {
+                b'\\' => index += 2,
+                b'$' if bytes.get(index + 1) == Some(&b'{') => {
+                    push_text(parts, unescape(&inner[text_start..index]));
+                    let Some(close) = matching_brace(bytes, index + 2) else {
+                        self.report(ParseError::new(
+                            "unterminated interpolation, expected `}`",
+                            offset + index..offset + index + 2,
+                        ));
+                        text_start = index;
+                        break;
+                    };
+                    let start = offset + index + 2;
+                    let mut tokens: Vec<_> = tokenize(&inner[index + 2..close])
+                        .into_iter()
+                        .filter(|(token, _)| !token.is_comment())
+                        .map(|(token, range)| (token, start + range.start..start + range.end))
+                        .collect();
+                    tokens.push((Token::BraceClose, offset + close..offset + close + 1));
+                    match self.parse_embedded_expr(&tokens) {
+                        Ok(expr) => parts.push(StringPart::Expr(expr)),
+                        Err(error) => self.report(error),
+                    }
+                    index = close + 1;
+                    text_start = index;
+                }
+                _ => index += 1,
+            }
+        }
+        push_text(parts, unescape(&inner[text_start..]));
+    }
+}
+
+fn string_literal(value: String, span: Range<usize>) -> Expr {
+    Expr::Literal(Literal {
+        value: LiteralValue::String(value),
+        span,
+    })
+}
+
+/// Append `text` to `parts`, joining it to the text before it if any.
+fn push_text(parts: &mut Vec<StringPart>, text: String) {
+    if text.is_empty() {
+        return;
+    }
+    match parts.last_mut() {
+        Some(StringPart::Text(last)) => last.push_str(&text),
+        _ => parts.push(StringPart::Text(text)),
+    }
+}
+
+/// Returns the index of the `}` closing a `${` whose expression starts at
+/// `start`, skipping over the braces nested inside.
+fn matching_brace(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, byte) in bytes.iter().enumerate().skip(start) {
+        match byte {
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return Some(index),
+            b'}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{BinaryOperator, Identifier};
+
+    fn parse(source: &str) -> (Result<Expr, ParseError>, Vec<String>) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let result = parser.parse_expr();
+        let messages = parser
+            .take_diagnostics()
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect();
+        (result, messages)
+    }
+
+    fn parts(source: &str) -> Vec<StringPart> {
+        match parse(source) {
+            (Ok(Expr::InterpolatedString { parts, .. }), messages) if messages.is_empty() => parts,
+            other => panic!("not an interpolated string: {:?}", other),
+        }
+    }
+
+    fn name(name: &str, start: usize) -> Expr {
+        Expr::Identifier(Identifier {
+            name: name.to_owned(),
+            span: start..start + name.len(),
+        })
+    }
+
+    #[test]
+    fn parses_an_embedded_expression() {
+        let (result, messages) = parse("\"hello ${name}!\"");
+        assert!(messages.is_empty());
+        assert_eq!(
+            result.unwrap(),
+            Expr::InterpolatedString {
+                parts: vec![
+                    StringPart::Text("hello ".to_owned()),
+                    StringPart::Expr(name("name", 9)),
+                    StringPart::Text("!".to_owned()),
+                ],
+                span: 0..16,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_several_embedded_expressions() {
+        let parts = parts("\"${a + b} and ${c}\"");
+        assert_eq!(parts.len(), 3);
+        assert!(matches!(
+            &parts[0],
+            StringPart::Expr(Expr::Binary {
+                operator: BinaryOperator::Add,
+                ..
+            })
+        ));
+        assert_eq!(parts[1], StringPart::Text(" and ".to_owned()));
+        assert_eq!(parts[2], StringPart::Expr(name("c", 16)));
+    }
+
+    #[test]
+    fn skips_over_nested_braces() {
+        let parts = parts("\"${ {a: 1} }\"");
+        assert!(matches!(&parts[..], [StringPart::Expr(Expr::Dict { .. })]));
+    }
+
+    #[test]
+    fn an_escaped_dollar_sign_is_text() {
+        let (result, messages) = parse("\"costs \\${price} \\\"x\\\"\"");
+        assert!(messages.is_empty());
+        assert!(matches!(
+            result.unwrap(),
+            Expr::Literal(Literal {
+                value: LiteralValue::String(value),
+                ..
+            }) if value == "costs ${price} \"x\""
+        ));
+    }
+
+    #[test]
+    fn joins_adjacent_strings() {
+        let parts = parts("\"a ${b}\" \" c\"");
+        assert_eq!(
+            parts,
+            vec![
+                StringPart::Text("a ".to_owned()),
+                StringPart::Expr(name("b", 5)),
+                StringPart::Text(" c".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_an_unterminated_interpolation() {
+        let (result, messages) = parse("\"a ${b\"");
+        assert_eq!(
+            messages,
+            vec!["unterminated interpolation, expected `}`".to_owned()]
+        );
+        assert!(matches!(
+            result.unwrap(),
+            Expr::Literal(Literal {
+                value: LiteralValue::String(value),
+                ..
+            }) if value == "a ${b"
+        ));
+    }
+
+    #[test]
+    fn reports_an_embedded_expression_that_does_not_parse() {
+        let (result, messages) = parse("\"a ${b c} d\"");
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(
+            result.unwrap(),
+            Expr::Literal(Literal {
+                value: LiteralValue::String(value),
+                ..
+            }) if value == "a  d"
+        ));
+    }
+}
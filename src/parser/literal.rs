@@ -0,0 +1,125 @@
+use super::{ParseError, Parser};
+use crate::ast::{Literal, LiteralValue};
+use crate::diagnostic::Diagnostic;
+use crate::lexer::{invalid_number_digit, Token};
+use std::ops::Range;
+
+impl Parser<'_> {
+    /// Parse a string, number or regex literal.
+    ///
+    /// A number with a digit that doesn't match its radix prefix is
+    /// recorded as an `"invalid-number"` error and parsed as written.
+    pub fn parse_literal(&mut self) -> Result<Literal, ParseError> {
+        if self.at(Token::String) {
+            let (value, span) = self.parse_string()?;
+            return Ok(Literal {
+                value: LiteralValue::String(value),
+                span,
+            });
+        }
+        if let Some(span) = self.eat(Token::Number) {
+            if let Some((digit, message)) = invalid_number_digit(self.text(span.clone())) {
+                self.report_diagnostic(
+                    Diagnostic::error(message, span.start + digit.start..span.start + digit.end)
+                        .with_code("invalid-number"),
+                );
+            }
+            return Ok(Literal {
+                value: LiteralValue::Number(self.text(span.clone()).to_owned()),
+                span,
+            });
+        }
+        if let Some(span) = self.eat(Token::Regex) {
+            let text = self.text(span.clone());
+            return Ok(Literal {
+                value: LiteralValue::Regex(text[1..text.len() - 1].to_owned()),
+                span,
+            });
+        }
+        Err(self.error_expected("a literal"))
+    }
+
+    /// Parse a string literal and return its decoded value and span.
+    ///
+    /// Adjacent string literals separated only by whitespace are joined
+    /// into a single string, so `"abc" "def"` reads as `"abcdef"` with a
+    /// span covering both literals. There is no multiline string literal in
+    /// the language, so only plain `"..."` literals take part.
+    pub fn parse_string(&mut self) -> Result<(String, Range<usize>), ParseError> {
+        let first = self.expect(Token::String, "a string")?;
+        let mut value = self.string_value(first.clone());
+        let mut end = first.end;
+        while let Some(span) = self.eat(Token::String) {
+            value.push_str(&self.string_value(span.clone()));
+            end = span.end;
+        }
+        Ok((value, first.start..end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<Literal, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_literal()
+    }
+
+    #[test]
+    fn concatenates_adjacent_strings() {
+        let literal = parse(r#""abc" "def""#).unwrap();
+        assert_eq!(literal.value, LiteralValue::String("abcdef".to_owned()));
+        assert_eq!(literal.span, 0..11);
+    }
+
+    #[test]
+    fn concatenates_strings_across_lines() {
+        let literal = parse("\"abc\"\n  \"def\"\n  \"ghi\"").unwrap();
+        assert_eq!(literal.value, LiteralValue::String("abcdefghi".to_owned()));
+        assert_eq!(literal.span, 0..21);
+    }
+
+    #[test]
+    fn leaves_a_single_string_unaffected() {
+        let literal = parse(r#""abc""#).unwrap();
+        assert_eq!(literal.value, LiteralValue::String("abc".to_owned()));
+        assert_eq!(literal.span, 0..5);
+    }
+
+    #[test]
+    fn does_not_concatenate_a_string_and_a_number() {
+        let source = r#""abc" 1"#;
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let literal = parser.parse_literal().unwrap();
+        assert_eq!(literal.span, 0..5);
+        assert!(parser.at(Token::Number));
+    }
+
+    #[test]
+    fn reports_a_digit_outside_of_the_radix() {
+        for (source, span) in [("0xG1", 2..3), ("0b12", 3..4), ("0o8", 2..3)] {
+            let tokens = tokenize(source);
+            let mut parser = Parser::new(source, &tokens);
+            let literal = parser.parse_literal().unwrap();
+            assert_eq!(literal.value, LiteralValue::Number(source.to_owned()));
+            assert!(parser.is_at_end());
+            let diagnostics = parser.diagnostics();
+            assert_eq!(diagnostics.len(), 1, "{}", source);
+            assert_eq!(diagnostics[0].code, Some("invalid-number"));
+            assert_eq!(diagnostics[0].span, span);
+        }
+    }
+
+    #[test]
+    fn accepts_numbers_with_a_radix_prefix() {
+        for source in ["0x1F", "0b10", "0o7"] {
+            let tokens = tokenize(source);
+            let mut parser = Parser::new(source, &tokens);
+            parser.parse_literal().unwrap();
+            assert!(parser.diagnostics().is_empty(), "{}", source);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use super::{ParseError, Parser};
+use crate::ast::MiddlewareDeclaration;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a `middleware name` or `middleware name { pipeline }`
+    /// declaration.
+    pub fn parse_middleware(&mut self) -> Result<MiddlewareDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Middleware, "`middleware`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a middleware name")?;
+        let body = match self.at(Token::BraceOpen) {
+            true => Some(self.parse_pipeline_body()?),
+            false => None,
+        };
+        self.trace_node("middleware", first);
+        Ok(MiddlewareDeclaration {
+            decorators: vec![],
+            name,
+            body,
+            span: start..self.previous_span().end,
+            source_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Expr;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> MiddlewareDeclaration {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let middleware = parser.parse_middleware().unwrap();
+        assert!(parser.diagnostics().is_empty());
+        middleware
+    }
+
+    #[test]
+    fn parses_a_middleware_declaration() {
+        let middleware = parse("middleware logRequest");
+        assert_eq!(middleware.name.name, "logRequest");
+        assert!(middleware.body.is_none());
+        assert_eq!(middleware.span, 0..21);
+    }
+
+    #[test]
+    fn parses_a_middleware_with_a_body() {
+        let middleware = parse("middleware timing { log.elapsed }");
+        assert!(matches!(middleware.body, Some(Expr::Member { .. })));
+        assert_eq!(middleware.span, 0..33);
+    }
+}
@@ -1 +1,35 @@
-pub mod parser;
\ No newline at end of file
+pub mod binding;
+pub mod config;
+pub mod decorator;
+pub mod doc_comment;
+pub mod enumeration;
+pub mod error;
+pub mod export;
+pub mod expr;
+pub mod function;
+pub mod handler;
+pub mod import;
+pub mod interpolation;
+pub mod literal;
+pub mod middleware;
+pub mod model;
+pub mod namespace;
+pub mod parse_result;
+#[allow(clippy::module_inception)]
+pub mod parser;
+pub mod schema;
+pub mod structure;
+pub mod trace;
+pub mod type_alias;
+pub mod type_expr;
+pub mod type_param;
+pub mod unknown;
+
+pub use enumeration::parse_enum;
+pub use error::ParseError;
+pub use import::parse_import;
+pub use model::parse_model;
+pub use parse_result::{parse_schema, parse_schema_anonymous, ParseResult, ANONYMOUS_PATH};
+pub use parser::Parser;
+pub use schema::parse;
+pub use trace::TraceEntry;
@@ -0,0 +1,282 @@
+use super::{ParseError, Parser};
+use crate::ast::{FieldDeclaration, ModelDeclaration};
+use crate::diagnostic::Diagnostic;
+use crate::lexer::Token;
+use std::ops::Range;
+
+impl Parser<'_> {
+    /// Parse a `model Name { ... }` declaration.
+    ///
+    /// Errors inside the body are recorded and the parser continues with
+    /// the next field. Only a broken header is returned as an error. The
+    /// `@@` block decorators of the body become decorators of the model,
+    /// and `@@doc("...")` ones its documentation.
+    pub fn parse_model(&mut self) -> Result<ModelDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Model, "`model`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a model name")?;
+        let type_parameters = self.parse_type_parameters()?;
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let (fields, decorators, end) =
+            self.parse_members_and_block_decorators(open, "model", Self::parse_field);
+        let doc = self.block_doc(&decorators);
+        self.trace_node("model", first);
+        Ok(ModelDeclaration {
+            doc,
+            decorators,
+            name,
+            type_parameters,
+            fields,
+            span: start..end,
+            source_order,
+        })
+    }
+
+    /// Parse a `name: Type` or `name: Type = default` field with the
+    /// decorators written before it.
+    ///
+    /// A field with both an inline default and a `@default` decorator is
+    /// kept as written and reported with a warning.
+    pub fn parse_field(&mut self) -> Result<FieldDeclaration, ParseError> {
+        let first = self.position();
+        let decorators = self.parse_decorators()?;
+        let name = self.parse_identifier("a field name")?;
+        self.expect(Token::Colon, "`:`")?;
+        if !self.at(Token::Identifier) && !self.at(Token::ParenOpen) {
+            return Err(self.error_expected("a field type"));
+        }
+        let type_expr = self.parse_type()?;
+        let default = match self.eat(Token::Equal) {
+            Some(_) => Some(self.parse_expr()?),
+            None => None,
+        };
+        if let Some(default) = &default {
+            if decorators
+                .iter()
+                .any(|decorator| decorator.name() == "default")
+            {
+                self.report_diagnostic(
+                    Diagnostic::warning(
+                        format!(
+                            "field `{}` has both an inline default and a `@default` decorator",
+                            name.name
+                        ),
+                        default.span(),
+                    )
+                    .with_code("conflicting-default"),
+                );
+            }
+        }
+        self.trace_node("field", first);
+        Ok(FieldDeclaration {
+            doc: None,
+            decorators,
+            span: name.span.start..self.previous_span().end,
+            name,
+            type_expr,
+            default,
+        })
+    }
+}
+
+/// Parse a single model declaration from `tokens`, which were lexed from
+/// `source`. Returns the model, if its header could be parsed, and every
+/// error found along the way.
+pub fn parse_model(
+    source: &str,
+    tokens: &[(Token, Range<usize>)],
+) -> (Option<ModelDeclaration>, Vec<Diagnostic>) {
+    let mut parser = Parser::new(source, tokens);
+    let model = match parser.parse_model() {
+        Ok(model) => Some(model),
+        Err(error) => {
+            parser.report(error);
+            None
+        }
+    };
+    (model, parser.take_diagnostics())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Expr, LiteralValue};
+    use crate::diagnostic::{merge_spans, Severity};
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> (Option<ModelDeclaration>, Vec<Diagnostic>) {
+        parse_model(source, &tokenize(source))
+    }
+
+    #[test]
+    fn parses_user_model() {
+        let source = r#"model User {
+            id: String,
+            name: String,
+            email: String,
+            password: String,
+            created_at: String,
+            updated_at: String,
+        }"#;
+        let (model, diagnostics) = parse(source);
+        let model = model.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(model.name.name, "User");
+        assert_eq!(model.name.span, 6..10);
+        let names: Vec<&str> = model.fields.iter().map(|f| f.name.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "id",
+                "name",
+                "email",
+                "password",
+                "created_at",
+                "updated_at"
+            ]
+        );
+        assert!(model
+            .fields
+            .iter()
+            .all(|f| f.type_expr.name().as_deref() == Some("String")));
+        assert_eq!(model.span, 0..source.len());
+    }
+
+    #[test]
+    fn spans_cover_the_keyword_through_the_closing_brace() {
+        let source = "/// Users.\n@map(\"users\")\nmodel User {\n    @id id: Int\n    name: String = \"x\"\n}  ";
+        let tokens = tokenize(source);
+        let first = tokens
+            .iter()
+            .position(|(token, _)| *token == Token::Model)
+            .unwrap();
+        let (model, diagnostics) = parse_model(source, &tokens[first..]);
+        let model = model.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(model.span.start, source.find("model").unwrap());
+        assert_eq!(model.span.end, source.rfind('}').unwrap() + 1);
+        let field = &model.fields[1];
+        assert_eq!(&source[field.span.clone()], "name: String = \"x\"");
+        assert_eq!(
+            field.span,
+            merge_spans(&field.name.span, &field.default.as_ref().unwrap().span())
+        );
+    }
+
+    #[test]
+    fn parses_newline_separated_and_optional_fields() {
+        let (model, diagnostics) = parse("model User {\n id: String\n bio: String?\n}");
+        let model = model.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(model.fields.len(), 2);
+        assert!(!model.fields[0].is_optional());
+        assert!(model.fields[1].is_optional());
+    }
+
+    #[test]
+    fn recovers_from_a_field_with_a_missing_type() {
+        let source = "model User {\n id: String,\n email: ,\n name: String\n}";
+        let (model, diagnostics) = parse(source);
+        let model = model.unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "expected a field type, found comma `,`"
+        );
+        assert_eq!(&source[diagnostics[0].span.clone()], ",");
+        let names: Vec<&str> = model.fields.iter().map(|f| f.name.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "name"]);
+    }
+
+    #[test]
+    fn reports_unterminated_body() {
+        let (model, diagnostics) = parse("model User {\n id: String");
+        assert_eq!(model.unwrap().fields.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "unterminated model body, expected `}`"
+        );
+    }
+
+    #[test]
+    fn reports_missing_name() {
+        let (model, diagnostics) = parse("model { }");
+        assert!(model.is_none());
+        assert_eq!(
+            diagnostics[0].message,
+            "expected a model name, found opening brace `{`"
+        );
+    }
+
+    #[test]
+    fn parses_field_type_expressions() {
+        let (model, diagnostics) = parse("model Post {\n tags: String[]?\n author: Foo::User\n}");
+        let model = model.unwrap();
+        assert!(diagnostics.is_empty());
+        assert!(model.fields[0].is_optional());
+        assert_eq!(model.fields[1].type_expr.name().unwrap(), "Foo::User");
+    }
+
+    #[test]
+    fn parses_an_inline_default() {
+        let (model, diagnostics) = parse("model Post {\n count: Int = 0\n title: String\n}");
+        let model = model.unwrap();
+        assert!(diagnostics.is_empty());
+        assert!(matches!(
+            &model.fields[0].default,
+            Some(Expr::Literal(literal)) if literal.value == LiteralValue::Number("0".to_owned())
+        ));
+        assert_eq!(model.fields[0].span, 14..28);
+        assert_eq!(model.fields[1].name.name, "title");
+    }
+
+    #[test]
+    fn warns_about_an_inline_default_and_a_default_decorator() {
+        let (model, diagnostics) = parse("model Post {\n @default(1) count: Int = 0\n}");
+        let model = model.unwrap();
+        assert!(model.fields[0].default.is_some());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].code, Some("conflicting-default"));
+        assert_eq!(diagnostics[0].span, 39..40);
+    }
+
+    #[test]
+    fn a_field_without_a_default_has_none() {
+        let (model, diagnostics) = parse("model Post {\n count: Int\n}");
+        assert!(diagnostics.is_empty());
+        assert!(model.unwrap().fields[0].default.is_none());
+    }
+
+    #[test]
+    fn parses_type_parameters() {
+        let (model, diagnostics) = parse("model Cache<T> where T: String { value: T }");
+        let model = model.unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(model.type_parameters[0].name.name, "T");
+        assert_eq!(
+            model.type_parameters[0].bound.as_ref().unwrap().to_string(),
+            "String"
+        );
+        assert_eq!(model.fields[0].type_expr.to_string(), "T");
+    }
+
+    #[test]
+    fn keeps_block_decorators_apart_from_field_decorators() {
+        let source = "model User {\n @@index([id, email])\n @id id: Int\n email: String\n}";
+        let (model, diagnostics) = parse(source);
+        let model = model.unwrap();
+        assert!(diagnostics.is_empty());
+        let block: Vec<String> = model.block_decorators().map(|d| d.name()).collect();
+        assert_eq!(block, vec!["index"]);
+        assert!(matches!(
+            model.decorators[0].arguments.as_deref(),
+            Some([argument]) if matches!(argument.value, Expr::Array { .. })
+        ));
+        assert_eq!(model.fields.len(), 2);
+        assert_eq!(model.fields[0].decorators[0].name(), "id");
+        assert!(!model.fields[0].decorators[0].block);
+        assert!(model.fields[1].decorators.is_empty());
+    }
+}
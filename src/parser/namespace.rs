@@ -0,0 +1,138 @@
+use super::{ParseError, Parser};
+use crate::ast::{Declaration, NamespaceDeclaration};
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a `namespace Name { ... }` block. The body holds declarations
+    /// like the top level of a file, namespaces included.
+    ///
+    /// A declaration of the body that cannot be parsed is recorded as an
+    /// error and skipped up to the next declaration or the closing brace
+    /// of the namespace. Only a broken header is returned as an error. An
+    /// `import` or `include` of the body is an error and left out, since
+    /// files are only imported at the top level.
+    pub fn parse_namespace(&mut self) -> Result<NamespaceDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Namespace, "`namespace`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a namespace name")?;
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let mut declarations = vec![];
+        let end = loop {
+            if let Some(close) = self.eat(Token::BraceClose) {
+                break close.end;
+            }
+            if self.is_at_end() {
+                self.report(ParseError::new(
+                    "unterminated namespace body, expected `}`",
+                    open,
+                ));
+                break self.previous_span().end;
+            }
+            let declaration_start = self.position();
+            match self.nested(Self::parse_declaration) {
+                Ok(Declaration::Import(import)) => self.report(ParseError::new(
+                    "imports are written at the top level of a file, not inside a namespace",
+                    import.span,
+                )),
+                Ok(declaration) => {
+                    self.trace_node("declaration", declaration_start);
+                    declarations.push(declaration);
+                }
+                Err(error) => {
+                    self.report(error);
+                    self.recover_declaration(true);
+                }
+            }
+        };
+        self.trace_node("namespace", first);
+        Ok(NamespaceDeclaration {
+            name,
+            declarations,
+            span: start..end,
+            source_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> (NamespaceDeclaration, Vec<String>) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        let namespace = parser.parse_namespace().unwrap();
+        let messages = parser
+            .take_diagnostics()
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect();
+        (namespace, messages)
+    }
+
+    fn names(declarations: &[Declaration]) -> Vec<&str> {
+        declarations
+            .iter()
+            .filter_map(|declaration| declaration.name())
+            .map(|name| name.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parses_a_namespace_with_two_declarations() {
+        let (namespace, messages) =
+            parse("namespace blog { model Post { id: Int } enum Status { DRAFT } }");
+        assert!(messages.is_empty());
+        assert_eq!(namespace.name.name, "blog");
+        assert_eq!(names(&namespace.declarations), vec!["Post", "Status"]);
+        assert!(matches!(namespace.declarations[0], Declaration::Model(_)));
+        assert!(matches!(namespace.declarations[1], Declaration::Enum(_)));
+        assert_eq!(namespace.span, 0..63);
+    }
+
+    #[test]
+    fn parses_a_nested_namespace() {
+        let (namespace, messages) =
+            parse("namespace blog {\n  namespace drafts {\n    enum Status { DRAFT }\n  }\n  model Post { id: Int }\n}");
+        assert!(messages.is_empty());
+        assert_eq!(names(&namespace.declarations), vec!["drafts", "Post"]);
+        let Declaration::Namespace(drafts) = &namespace.declarations[0] else {
+            panic!("expected a namespace");
+        };
+        assert_eq!(names(&drafts.declarations), vec!["Status"]);
+    }
+
+    #[test]
+    fn recovers_inside_the_body() {
+        let (namespace, messages) =
+            parse("namespace blog { model { } enum Status { DRAFT } }\nmodel After {}");
+        assert_eq!(
+            messages,
+            vec!["expected a model name, found opening brace `{`"]
+        );
+        assert_eq!(names(&namespace.declarations), vec!["Status"]);
+    }
+
+    #[test]
+    fn rejects_imports_inside_the_body() {
+        let (namespace, messages) =
+            parse("namespace blog { import \"./user\" include \"./post\" model Post { id: Int } }");
+        assert_eq!(
+            messages,
+            vec![
+                "imports are written at the top level of a file, not inside a namespace",
+                "imports are written at the top level of a file, not inside a namespace"
+            ]
+        );
+        assert_eq!(names(&namespace.declarations), vec!["Post"]);
+    }
+
+    #[test]
+    fn reports_an_unterminated_body() {
+        let (namespace, messages) = parse("namespace blog { model Post { id: Int }");
+        assert_eq!(messages, vec!["unterminated namespace body, expected `}`"]);
+        assert_eq!(names(&namespace.declarations), vec!["Post"]);
+    }
+}
@@ -0,0 +1,156 @@
+use super::doc_comment::attach_doc_comments;
+use super::Parser;
+use crate::ast::{Declaration, SchemaFile};
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::lexer::{tokenize, Token};
+use std::ops::Range;
+
+/// # Parse result
+///
+/// Everything found when parsing one schema source with [`parse_schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseResult {
+    /// The path the source was read from, used when reporting diagnostics.
+    pub path: String,
+    /// The parsed declarations.
+    pub schema: SchemaFile,
+    /// Every problem found while parsing, in source order.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The byte spans of the comments in the source, including the doc
+    /// comments attached to the declarations. They are not seen by the
+    /// parser.
+    pub comments: Vec<Range<usize>>,
+}
+
+impl ParseResult {
+    /// Returns true if any diagnostic is an error.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+}
+
+/// Lex and parse the schema `source` read from `path`.
+pub fn parse_schema(source: &str, path: &str) -> ParseResult {
+    let (comments, tokens): (Vec<_>, Vec<_>) = tokenize(source)
+        .into_iter()
+        .partition(|(token, _)| token.is_comment());
+    let mut parser = Parser::new(source, &tokens);
+    let mut schema = parser.parse_schema_file();
+    let doc_comments: Vec<Range<usize>> = comments
+        .iter()
+        .filter(|(token, _)| *token == Token::DocComment)
+        .map(|(_, span)| span.clone())
+        .collect();
+    attach_doc_comments(&mut schema, source, &doc_comments);
+    ParseResult {
+        path: path.to_owned(),
+        schema,
+        diagnostics: parser.take_diagnostics(),
+        comments: comments.into_iter().map(|(_, span)| span).collect(),
+    }
+}
+
+/// The path of a source parsed with [`parse_schema_anonymous`].
+pub const ANONYMOUS_PATH: &str = "<anonymous>";
+
+/// Lex and parse the schema `source` that wasn't read from a file, like
+/// a snippet pasted into a playground, under the path [`ANONYMOUS_PATH`].
+///
+/// There is no path to resolve imports against, so every `import` and
+/// `include` produces an `"anonymous-import"` error spanning its source
+/// string. Its declaration is still kept.
+pub fn parse_schema_anonymous(source: &str) -> ParseResult {
+    let mut result = parse_schema(source, ANONYMOUS_PATH);
+    report_imports(&result.schema.declarations, &mut result.diagnostics);
+    result
+        .diagnostics
+        .sort_by_key(|diagnostic| diagnostic.span.start);
+    result
+}
+
+fn report_imports(declarations: &[Declaration], diagnostics: &mut Vec<Diagnostic>) {
+    for declaration in declarations {
+        match declaration {
+            Declaration::Import(import) => diagnostics.push(
+                Diagnostic::error(
+                    format!(
+                        "cannot resolve `{}` in a source without a path",
+                        import.source
+                    ),
+                    import.source_span.clone(),
+                )
+                .with_code("anonymous-import"),
+            ),
+            Declaration::Namespace(namespace) => {
+                report_imports(&namespace.declarations, diagnostics)
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_comments_out_of_the_parser() {
+        let source = "// users\nmodel User { // the id\n id: Int }";
+        let result = parse_schema(source, "schema.teo");
+        assert!(!result.has_errors());
+        assert_eq!(result.comments, vec![0..8, 22..31]);
+        assert_eq!(result.schema.declarations.len(), 1);
+    }
+
+    #[test]
+    fn reports_an_unterminated_string_literal() {
+        let source = "let x = \"abc\nmodel User { id: Int }";
+        let result = parse_schema(source, "schema.teo");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].message, "unterminated string literal");
+        assert_eq!(result.diagnostics[0].span, 8..12);
+        assert!(result.schema.model("User").is_some());
+    }
+
+    #[test]
+    fn parses_an_anonymous_snippet() {
+        let result = parse_schema_anonymous("model User { id: Int }");
+        assert_eq!(result.path, "<anonymous>");
+        assert!(result.diagnostics.is_empty());
+        assert!(result.schema.model("User").is_some());
+    }
+
+    #[test]
+    fn reports_imports_in_an_anonymous_source() {
+        let source = "import { Role } from \"./role\"\nmodel User { id: }\ninclude \"./mixins\"";
+        let result = parse_schema_anonymous(source);
+        let diagnostics: Vec<(Option<&str>, &str, Range<usize>)> = result
+            .diagnostics
+            .iter()
+            .map(|d| (d.code, d.message.as_str(), d.span.clone()))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    Some("anonymous-import"),
+                    "cannot resolve `./role` in a source without a path",
+                    21..29
+                ),
+                (
+                    None,
+                    "expected a field type, found closing brace `}`",
+                    47..48
+                ),
+                (
+                    Some("anonymous-import"),
+                    "cannot resolve `./mixins` in a source without a path",
+                    57..67
+                ),
+            ]
+        );
+        assert_eq!(result.schema.declarations.len(), 3);
+    }
+}
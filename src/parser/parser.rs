@@ -0,0 +1,401 @@
+use super::{ParseError, TraceEntry};
+use crate::ast::{Decorator, Expr, Identifier};
+use crate::diagnostic::{merge_spans, Diagnostic, DiagnosticSink};
+use crate::lexer::{string_value, Token};
+use std::ops::Range;
+
+/// # Parser
+///
+/// A recursive descent parser over the tokens produced by the lexer. The
+/// parser keeps a reference to the source so that it can read the text of
+/// identifiers and literals.
+pub struct Parser<'a> {
+    /// The source the tokens were produced from.
+    source: &'a str,
+    /// The tokens to parse.
+    tokens: &'a [(Token, Range<usize>)],
+    /// The index of the next token to consume.
+    position: usize,
+    /// The problems found so far, unless they are sent to a sink.
+    diagnostics: Vec<Diagnostic>,
+    /// The sink problems are sent to as they are found, if there is one.
+    sink: Option<&'a mut dyn DiagnosticSink>,
+    /// The source order to assign to the next declaration.
+    next_source_order: usize,
+    /// The nodes parsed so far, if tracing is enabled.
+    trace: Option<Vec<TraceEntry>>,
+    /// Whether declarations with an unknown keyword are kept instead of
+    /// being reported.
+    unknown_declarations: bool,
+    /// The number of nested expressions, types and namespaces being parsed.
+    depth: usize,
+    /// The nesting depth past which parsing stops with an error.
+    max_depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// The nesting depth a parser accepts unless told otherwise.
+    pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+    /// Create a new parser over `tokens`, which were lexed from `source`.
+    pub fn new(source: &'a str, tokens: &'a [(Token, Range<usize>)]) -> Self {
+        Self {
+            source,
+            tokens,
+            position: 0,
+            diagnostics: vec![],
+            sink: None,
+            next_source_order: 0,
+            trace: None,
+            unknown_declarations: false,
+            depth: 0,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Returns the parser with problems sent to `sink` as they are found,
+    /// instead of being collected.
+    pub fn with_sink(mut self, sink: &'a mut dyn DiagnosticSink) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// Returns the parser with `keyword Name { ... }` declarations whose
+    /// keyword it doesn't know kept as [`Declaration::Unknown`], instead of
+    /// being reported as errors. Known keywords are parsed as usual.
+    ///
+    /// [`Declaration::Unknown`]: crate::ast::Declaration::Unknown
+    pub fn with_unknown_declarations(mut self) -> Self {
+        self.unknown_declarations = true;
+        self
+    }
+
+    /// Returns the parser with expressions, types and namespaces nested
+    /// more than `max_depth` levels deep reported as `nesting too deep`,
+    /// instead of [`Parser::DEFAULT_MAX_DEPTH`] levels. The limit keeps
+    /// hostile input from overflowing the stack.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns true if unknown declarations are kept.
+    pub(crate) fn unknown_declarations(&self) -> bool {
+        self.unknown_declarations
+    }
+
+    /// Returns the problems found so far. Empty if the parser has a sink.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Take the problems found so far.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Start recording the tokens each parsed node consumed. Tracing is off
+    /// by default because most callers only need the syntax tree.
+    pub fn enable_trace(&mut self) {
+        self.trace.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the nodes parsed so far, in the order they were completed.
+    /// Empty unless tracing is enabled.
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Record that a `node` was parsed from the token at index `first` up
+    /// to the current position, if tracing is enabled.
+    pub(crate) fn trace_node(&mut self, node: &'static str, first: usize) {
+        let span = self.span_from(first);
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                node,
+                span,
+                token_range: first..self.position,
+            });
+        }
+    }
+
+    /// Returns the span from the token at index `first` through the last
+    /// consumed token, the span of a node starting at that token.
+    pub(crate) fn span_from(&self, first: usize) -> Range<usize> {
+        match self.tokens.get(first) {
+            Some((_, span)) => merge_spans(span, &self.previous_span()),
+            None => self.previous_span(),
+        }
+    }
+
+    /// Returns the source order for a new declaration. Declarations get
+    /// increasing numbers in the order they are written, all over the file.
+    pub(crate) fn next_source_order(&mut self) -> usize {
+        let source_order = self.next_source_order;
+        self.next_source_order += 1;
+        source_order
+    }
+
+    /// Record a syntax error and keep parsing.
+    pub(crate) fn report(&mut self, error: ParseError) {
+        self.report_diagnostic(error.into());
+    }
+
+    /// Record a diagnostic and keep parsing.
+    pub(crate) fn report_diagnostic(&mut self, diagnostic: Diagnostic) {
+        match &mut self.sink {
+            Some(sink) => sink.emit(diagnostic),
+            None => self.diagnostics.push(diagnostic),
+        }
+    }
+
+    /// Run `parse` one nesting level deeper, or fail with `nesting too deep`
+    /// if that is past the limit.
+    pub(crate) fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        if self.depth >= self.max_depth {
+            return Err(ParseError::new("nesting too deep", self.peek_span()));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Parse the expression of `tokens`, which come from a region of the
+    /// same source and end with a `}`, at the nesting depth of this parser.
+    /// What goes wrong inside is recorded as a problem of this parser.
+    pub(crate) fn parse_embedded_expr(
+        &mut self,
+        tokens: &[(Token, Range<usize>)],
+    ) -> Result<Expr, ParseError> {
+        let mut parser = Parser::new(self.source, tokens);
+        parser.depth = self.depth;
+        parser.max_depth = self.max_depth;
+        let result = parser.parse_expr().and_then(|expr| {
+            parser.expect(Token::BraceClose, "`}`")?;
+            Ok(expr)
+        });
+        for diagnostic in parser.take_diagnostics() {
+            self.report_diagnostic(diagnostic);
+        }
+        result
+    }
+
+    /// Returns true if every token has been consumed.
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    /// Returns the index of the next token to consume.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Parse the members of a `{ ... }` body whose opening brace at `open`
+    /// was already consumed, through the closing brace. Returns the members
+    /// and the end offset of the body.
+    ///
+    /// A member that fails to parse is recorded as an error and skipped, so
+    /// that the following members are still parsed.
+    pub(crate) fn parse_members<T>(
+        &mut self,
+        open: Range<usize>,
+        body: &str,
+        mut parse_member: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> (Vec<T>, usize) {
+        let mut members = vec![];
+        loop {
+            if let Some(close) = self.eat(Token::BraceClose) {
+                return (members, close.end);
+            }
+            if self.is_at_end() {
+                self.report(ParseError::new(
+                    format!("unterminated {} body, expected `}}`", body),
+                    open,
+                ));
+                return (members, self.previous_span().end);
+            }
+            let member_start = self.position;
+            match parse_member(self) {
+                Ok(member) => {
+                    members.push(member);
+                    self.parse_member_separator();
+                }
+                Err(error) => {
+                    self.report(error);
+                    self.recover_member(member_start);
+                }
+            }
+        }
+    }
+
+    /// Parse a body like [`Parser::parse_members`], where `@@` block
+    /// decorators may be written between the members. Returns the members,
+    /// the block decorators and the end offset of the body.
+    pub(crate) fn parse_members_and_block_decorators<T>(
+        &mut self,
+        open: Range<usize>,
+        body: &str,
+        mut parse_member: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> (Vec<T>, Vec<Decorator>, usize) {
+        let mut decorators = vec![];
+        let (members, end) = self.parse_members(open, body, |parser| {
+            if !parser.at(Token::AtAt) {
+                return parse_member(parser).map(Some);
+            }
+            decorators.push(parser.parse_decorator()?);
+            Ok(None)
+        });
+        (members.into_iter().flatten().collect(), decorators, end)
+    }
+
+    /// Parse an identifier token.
+    pub(crate) fn parse_identifier(&mut self, expected: &str) -> Result<Identifier, ParseError> {
+        let span = self.expect(Token::Identifier, expected)?;
+        Ok(Identifier::new(self.text(span.clone()), span))
+    }
+
+    /// Members of a body are separated by a `,` or by a line break. A
+    /// trailing `,` before the closing brace is allowed.
+    pub(crate) fn parse_member_separator(&mut self) {
+        if self.eat(Token::Comma).is_some() || self.at(Token::BraceClose) || self.is_at_end() {
+            return;
+        }
+        if !self.at_line_start() {
+            let error = self.error_expected("`,` or a new line");
+            self.report(error);
+            self.recover_member(self.position());
+        }
+    }
+
+    /// Skip the rest of a broken member of a `{ ... }` body which started
+    /// at token index `start`. Stops after a `,`, before a `}`, or before
+    /// the first token on a new line.
+    pub(crate) fn recover_member(&mut self, start: usize) {
+        while !self.is_at_end() && !self.at(Token::BraceClose) {
+            if self.eat(Token::Comma).is_some() {
+                return;
+            }
+            if self.position > start && self.at_line_start() {
+                return;
+            }
+            self.advance();
+        }
+    }
+
+    /// Returns the next token without consuming it.
+    pub(crate) fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.position).map(|(token, _)| *token)
+    }
+
+    /// Returns the token `n` tokens after the next one without consuming
+    /// anything.
+    pub(crate) fn peek_nth(&self, n: usize) -> Option<Token> {
+        self.tokens.get(self.position + n).map(|(token, _)| *token)
+    }
+
+    /// Returns the span of the next token, or an empty span at the end of
+    /// the source if there are no more tokens.
+    pub(crate) fn peek_span(&self) -> Range<usize> {
+        match self.tokens.get(self.position) {
+            Some((_, span)) => span.clone(),
+            None => self.source.len()..self.source.len(),
+        }
+    }
+
+    /// Returns true if the next token is `token`.
+    pub(crate) fn at(&self, token: Token) -> bool {
+        self.peek() == Some(token)
+    }
+
+    /// Consume the next token and return its span.
+    pub(crate) fn advance(&mut self) -> Option<(Token, Range<usize>)> {
+        let token = self.tokens.get(self.position).cloned();
+        if token.is_some() {
+            self.position += 1;
+        }
+        token
+    }
+
+    /// Consume the next token if it is `token`.
+    pub(crate) fn eat(&mut self, token: Token) -> Option<Range<usize>> {
+        if self.at(token) {
+            self.advance().map(|(_, span)| span)
+        } else {
+            None
+        }
+    }
+
+    /// Consume the next token if it is `token`, otherwise return an error
+    /// saying that `expected` was expected.
+    pub(crate) fn expect(
+        &mut self,
+        token: Token,
+        expected: &str,
+    ) -> Result<Range<usize>, ParseError> {
+        match self.eat(token) {
+            Some(span) => Ok(span),
+            None => Err(self.error_expected(expected)),
+        }
+    }
+
+    /// Build an error saying that `expected` was expected at the next token.
+    /// The token found is described with [`Token::description`], followed
+    /// by its text for the tokens whose text varies. A string literal left
+    /// open is reported as unterminated whatever was expected.
+    pub(crate) fn error_expected(&self, expected: &str) -> ParseError {
+        match self.tokens.get(self.position) {
+            Some((Token::UnterminatedString, span)) => {
+                ParseError::new("unterminated string literal", span.clone())
+            }
+            Some((token, span)) => {
+                let found = match token {
+                    Token::Identifier | Token::Number | Token::Error => {
+                        format!("{} `{}`", token.description(), self.text(span.clone()))
+                    }
+                    _ => token.description().to_owned(),
+                };
+                ParseError::new(
+                    format!("expected {}, found {}", expected, found),
+                    span.clone(),
+                )
+            }
+            None => ParseError::new(
+                format!("expected {}, found end of file", expected),
+                self.peek_span(),
+            ),
+        }
+    }
+
+    /// Returns the span of the most recently consumed token.
+    pub(crate) fn previous_span(&self) -> Range<usize> {
+        match self
+            .position
+            .checked_sub(1)
+            .and_then(|i| self.tokens.get(i))
+        {
+            Some((_, span)) => span.clone(),
+            None => 0..0,
+        }
+    }
+
+    /// Returns true if the next token is the first token on its line.
+    pub(crate) fn at_line_start(&self) -> bool {
+        let start = self.peek_span().start;
+        let previous_end = self.previous_span().end.min(start);
+        self.position == 0 || self.source[previous_end..start].contains('\n')
+    }
+
+    /// Returns the source text of `span`.
+    pub(crate) fn text(&self, span: Range<usize>) -> &'a str {
+        &self.source[span]
+    }
+
+    /// Returns the decoded value of the string literal at `span`.
+    pub(crate) fn string_value(&self, span: Range<usize>) -> String {
+        string_value(self.text(span))
+    }
+}
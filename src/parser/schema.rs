@@ -0,0 +1,341 @@
+use super::{ParseError, Parser};
+use crate::ast::{Declaration, SchemaFile};
+use crate::diagnostic::Diagnostic;
+use crate::lexer::Token;
+use std::ops::Range;
+
+impl Parser<'_> {
+    /// Parse every declaration until the end of the tokens.
+    ///
+    /// A declaration that cannot be parsed is recorded as an error and
+    /// skipped up to the next declaration keyword.
+    pub fn parse_schema_file(&mut self) -> SchemaFile {
+        let mut declarations = vec![];
+        while !self.is_at_end() {
+            let first = self.position();
+            match self.parse_declaration() {
+                Ok(declaration) => {
+                    self.trace_node("declaration", first);
+                    declarations.push(declaration);
+                }
+                Err(error) => {
+                    self.report(error);
+                    self.recover_declaration(false);
+                }
+            }
+        }
+        SchemaFile::new(declarations)
+    }
+
+    /// Parse a top level declaration with the decorators written before it.
+    pub fn parse_declaration(&mut self) -> Result<Declaration, ParseError> {
+        let decorators = self.parse_decorators()?;
+        match self.peek() {
+            Some(Token::Import) | Some(Token::Include) if decorators.is_empty() => {
+                self.parse_import().map(Declaration::Import)
+            }
+            Some(Token::Export) if decorators.is_empty() => {
+                self.parse_export().map(Declaration::Export)
+            }
+            Some(Token::Model) => {
+                let mut model = self.parse_model()?;
+                let block = std::mem::replace(&mut model.decorators, decorators);
+                model.decorators.extend(block);
+                Ok(Declaration::Model(model))
+            }
+            Some(Token::Enum) => {
+                let mut r#enum = self.parse_enum()?;
+                let block = std::mem::replace(&mut r#enum.decorators, decorators);
+                r#enum.decorators.extend(block);
+                Ok(Declaration::Enum(r#enum))
+            }
+            Some(Token::Config) if decorators.is_empty() => {
+                self.parse_config().map(Declaration::Config)
+            }
+            Some(Token::BracketOpen) if decorators.is_empty() && self.at_line_start() => {
+                self.parse_config_section().map(Declaration::ConfigSection)
+            }
+            Some(Token::Type) if decorators.is_empty() => {
+                self.parse_type_alias().map(Declaration::TypeAlias)
+            }
+            Some(Token::Let) | Some(Token::Var) if decorators.is_empty() => {
+                self.parse_let().map(Declaration::Let)
+            }
+            Some(Token::Struct) if decorators.is_empty() => {
+                self.parse_struct().map(Declaration::Struct)
+            }
+            Some(Token::Declare) | Some(Token::Native) | Some(Token::Function)
+                if decorators.is_empty() && self.at_function() =>
+            {
+                self.parse_function().map(Declaration::Function)
+            }
+            Some(Token::Middleware) => {
+                let mut middleware = self.parse_middleware()?;
+                middleware.decorators = decorators;
+                Ok(Declaration::Middleware(middleware))
+            }
+            Some(Token::Handler) => {
+                let mut handler = self.parse_handler()?;
+                handler.decorators = decorators;
+                Ok(Declaration::Handler(handler))
+            }
+            Some(Token::Namespace) if decorators.is_empty() => {
+                self.parse_namespace().map(Declaration::Namespace)
+            }
+            Some(Token::Identifier) if decorators.is_empty() && self.at_unknown_declaration() => {
+                self.parse_unknown_declaration().map(Declaration::Unknown)
+            }
+            Some(Token::AtAt) => Err(ParseError::new(
+                "`@@` block decorators are written inside the body of a model or an enum",
+                self.peek_span(),
+            )),
+            _ if !decorators.is_empty() => {
+                Err(self.error_expected("a model, an enum, a middleware or a handler"))
+            }
+            _ => Err(self.error_expected("a declaration")),
+        }
+    }
+
+    /// Skip tokens up to the start of the next declaration. Inside a
+    /// namespace, also stop before the `}` closing it.
+    pub(crate) fn recover_declaration(&mut self, in_namespace: bool) {
+        let mut depth = match self.advance() {
+            Some((Token::BraceOpen, _)) => 1,
+            _ => 0,
+        };
+        while let Some(token) = self.peek() {
+            if is_declaration_keyword(token)
+                || (token == Token::BracketOpen && self.at_line_start())
+                || (token == Token::BraceClose && depth == 0 && in_namespace)
+            {
+                return;
+            }
+            match token {
+                Token::BraceOpen => depth += 1,
+                Token::BraceClose => depth -= 1,
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+}
+
+fn is_declaration_keyword(token: Token) -> bool {
+    matches!(
+        token,
+        Token::Import
+            | Token::Include
+            | Token::Export
+            | Token::Model
+            | Token::Enum
+            | Token::Config
+            | Token::Type
+            | Token::Let
+            | Token::Var
+            | Token::Struct
+            | Token::Declare
+            | Token::Native
+            | Token::Function
+            | Token::Middleware
+            | Token::Handler
+            | Token::Namespace
+    )
+}
+
+/// Parse a whole schema file from `tokens`, which were lexed from `source`.
+/// Returns the file and every error found along the way.
+pub fn parse(source: &str, tokens: &[(Token, Range<usize>)]) -> (SchemaFile, Vec<Diagnostic>) {
+    let mut parser = Parser::new(source, tokens);
+    let schema = parser.parse_schema_file();
+    (schema, parser.take_diagnostics())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Severity;
+    use crate::lexer::tokenize;
+
+    fn parse_source(source: &str) -> (SchemaFile, Vec<Diagnostic>) {
+        parse(source, &tokenize(source))
+    }
+
+    #[test]
+    fn assigns_increasing_source_order() {
+        let (schema, diagnostics) = parse_source(
+            r#"
+            import { a } from "./a"
+            model User { id: String }
+            include "./b"
+            model Post { id: String }
+            "#,
+        );
+        assert!(diagnostics.is_empty());
+        let orders: Vec<usize> = schema
+            .declarations
+            .iter()
+            .map(|d| d.source_order())
+            .collect();
+        assert_eq!(orders, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn source_order_survives_reordering() {
+        let (schema, _) = parse_source("model B { }\nmodel A { }\nmodel C { }");
+        let mut declarations = schema.declarations.clone();
+        declarations.sort_by_key(|d| match d {
+            Declaration::Model(model) => model.name.name.clone(),
+            Declaration::Import(import) => import.source.clone(),
+            Declaration::Export(export) => export.names[0].name.clone(),
+            Declaration::Enum(r#enum) => r#enum.name.name.clone(),
+            Declaration::Config(config) => config
+                .kind
+                .as_ref()
+                .map(|kind| kind.name.clone())
+                .unwrap_or_default(),
+            Declaration::ConfigSection(section) => section.name.name.clone(),
+            Declaration::TypeAlias(alias) => alias.name.name.clone(),
+            Declaration::Let(binding) => binding.name.name.clone(),
+            Declaration::Struct(r#struct) => r#struct.name.name.clone(),
+            Declaration::Function(function) => function.name.name.clone(),
+            Declaration::Middleware(middleware) => middleware.name.name.clone(),
+            Declaration::Handler(handler) => handler.name.name.clone(),
+            Declaration::Namespace(namespace) => namespace.name.name.clone(),
+            Declaration::Unknown(unknown) => unknown.name.name.clone(),
+        });
+        declarations.sort_by_key(|d| d.source_order());
+        assert_eq!(declarations, schema.declarations);
+    }
+
+    #[test]
+    fn skips_to_the_next_declaration_after_an_error() {
+        let (schema, diagnostics) = parse_source("model { }\nmodel User { id: String }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(schema.declarations.len(), 1);
+        assert_eq!(schema.declarations[0].source_order(), 1);
+    }
+
+    #[test]
+    fn reports_deeply_nested_input_without_overflowing() {
+        let nested = format!("{}1{}", "[".repeat(1000), "]".repeat(1000));
+        let source = format!("let deep = {}\nmodel User {{ id: Int }}", nested);
+        let (schema, diagnostics) = parse_source(&source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "nesting too deep");
+        assert!(matches!(
+            schema.declarations.last(),
+            Some(Declaration::Model(_))
+        ));
+        let source = format!("{}{}", "namespace a { ".repeat(100), "}".repeat(100));
+        let tokens = tokenize(&source);
+        let mut parser = Parser::new(&source, &tokens).with_max_depth(32);
+        parser.parse_schema_file();
+        assert_eq!(parser.diagnostics()[0].message, "nesting too deep");
+    }
+
+    #[test]
+    fn reports_a_block_decorator_outside_a_body() {
+        let (schema, diagnostics) = parse_source("@@index([id])\nmodel User { id: Int }");
+        assert_eq!(
+            diagnostics[0].message,
+            "`@@` block decorators are written inside the body of a model or an enum"
+        );
+        assert_eq!(diagnostics[0].span, 0..2);
+        assert_eq!(schema.declarations.len(), 1);
+    }
+
+    #[test]
+    fn attaches_decorators_to_declarations() {
+        let (schema, diagnostics) = parse_source(
+            r#"
+            @map("users")
+            model User {
+                @id @default(1) id: Int
+                @unique
+                email: String
+            }
+            @map("roles") enum Role {
+                @map("admin") ADMIN
+                USER
+            }
+            "#,
+        );
+        assert!(diagnostics.is_empty());
+        let Declaration::Model(model) = &schema.declarations[0] else {
+            panic!("expected a model");
+        };
+        assert_eq!(model.decorators[0].name(), "map");
+        let field_decorators: Vec<Vec<String>> = model
+            .fields
+            .iter()
+            .map(|field| field.decorators.iter().map(|d| d.name()).collect())
+            .collect();
+        assert_eq!(
+            field_decorators,
+            vec![vec!["id", "default"], vec!["unique"]]
+        );
+        let Declaration::Enum(r#enum) = &schema.declarations[1] else {
+            panic!("expected an enum");
+        };
+        assert_eq!(r#enum.decorators[0].name(), "map");
+        assert_eq!(r#enum.members[0].decorators[0].name(), "map");
+        assert!(r#enum.members[1].decorators.is_empty());
+    }
+
+    #[test]
+    fn reports_decorators_before_an_import() {
+        let (_, diagnostics) = parse_source("@map(\"a\") import { a } from \"./a\"");
+        assert_eq!(
+            diagnostics[0].message,
+            "expected a model, an enum, a middleware or a handler, found keyword `import`"
+        );
+    }
+
+    #[test]
+    fn reports_every_independent_error() {
+        let source = "model User {\n  id: ,\n  name: String\n}\nenum Role { \"admin\" }\nmodel Post { id: Int }";
+        let (schema, diagnostics) = parse_source(source);
+        let reported: Vec<(&str, Range<usize>)> = diagnostics
+            .iter()
+            .map(|d| (d.message.as_str(), d.span.clone()))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                ("expected a field type, found comma `,`", 19..20),
+                ("expected an enum member name, found string literal", 50..57),
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+        assert_eq!(schema.declarations.len(), 3);
+    }
+
+    #[test]
+    fn parses_decorated_middleware_and_handlers() {
+        let (schema, diagnostics) = parse_source(
+            "@map(\"/auth\") handler signIn { identity.signIn() }\nmiddleware timing\nmodel User { id: Int }",
+        );
+        assert!(diagnostics.is_empty());
+        let Declaration::Handler(handler) = &schema.declarations[0] else {
+            panic!("expected a handler");
+        };
+        assert_eq!(handler.decorators[0].name(), "map");
+        assert!(matches!(&schema.declarations[1], Declaration::Middleware(m) if m.body.is_none()));
+        assert!(matches!(&schema.declarations[2], Declaration::Model(_)));
+    }
+
+    #[test]
+    fn parses_config_sections_between_declarations() {
+        let (schema, diagnostics) = parse_source(
+            "[server]\nbind = (\"0.0.0.0\", 5000)\nmodel User { id: Int }\n[client]\nhost = \"x\"",
+        );
+        assert!(diagnostics.is_empty());
+        assert!(
+            matches!(&schema.declarations[0], Declaration::ConfigSection(s) if s.entries.len() == 1)
+        );
+        assert!(matches!(&schema.declarations[1], Declaration::Model(_)));
+        assert!(
+            matches!(&schema.declarations[2], Declaration::ConfigSection(s) if s.name.name == "client")
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use super::{ParseError, Parser};
+use crate::ast::StructDeclaration;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a `struct Name { ... }` or `struct Name<A, B> { ... }`
+    /// declaration. The type parameters may be given bounds by a `where`
+    /// clause before the body.
+    ///
+    /// The fields are parsed like those of a model. Errors inside the body
+    /// are recorded and the parser continues with the next field. Only a
+    /// broken header, including a broken generic parameter list, is
+    /// returned as an error.
+    pub fn parse_struct(&mut self) -> Result<StructDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Struct, "`struct`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a struct name")?;
+        let type_parameters = self.parse_type_parameters()?;
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let (fields, end) = self.parse_members(open, "struct", Self::parse_field);
+        self.trace_node("struct", first);
+        Ok(StructDeclaration {
+            name,
+            type_parameters,
+            fields,
+            span: start..end,
+            source_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<StructDeclaration, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_struct()
+    }
+
+    fn field_names(r#struct: &StructDeclaration) -> Vec<&str> {
+        r#struct
+            .fields
+            .iter()
+            .map(|field| field.name.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parses_a_plain_struct() {
+        let r#struct = parse("struct Point { x: Int, y: Int }").unwrap();
+        assert_eq!(r#struct.name.name, "Point");
+        assert!(r#struct.type_parameters.is_empty());
+        assert_eq!(field_names(&r#struct), vec!["x", "y"]);
+        assert_eq!(r#struct.span, 0..31);
+    }
+
+    #[test]
+    fn parses_a_generic_struct() {
+        let r#struct = parse("struct Pair<A, B> {\n  first: A\n  second: B?\n}").unwrap();
+        let generics: Vec<&str> = r#struct
+            .type_parameters
+            .iter()
+            .map(|parameter| parameter.name.name.as_str())
+            .collect();
+        assert_eq!(generics, vec!["A", "B"]);
+        assert_eq!(field_names(&r#struct), vec!["first", "second"]);
+        assert!(r#struct.fields[1].is_optional());
+    }
+
+    #[test]
+    fn reports_a_malformed_generic_list() {
+        let error = parse("struct Pair<A B> { }").unwrap_err();
+        assert_eq!(error.message, "expected `,` or `>`, found identifier `B`");
+        let error = parse("struct Pair<A, { }").unwrap_err();
+        assert_eq!(
+            error.message,
+            "expected a type parameter name, found opening brace `{`"
+        );
+    }
+
+    #[test]
+    fn is_a_top_level_declaration() {
+        let source = "struct Point { x: Int }\nmodel User { id: Int }";
+        let result = crate::parser::parse_schema(source, "schema.teo");
+        assert!(result.diagnostics.is_empty());
+        assert!(matches!(
+            result.schema.declarations[0],
+            crate::ast::Declaration::Struct(_)
+        ));
+        assert_eq!(result.schema.declarations[1].source_order(), 1);
+    }
+}
@@ -0,0 +1,48 @@
+use std::ops::Range;
+
+/// # Trace entry
+///
+/// A node recorded by a parser with tracing enabled, with the tokens it
+/// was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// The kind of node, like `"model"` or `"field"`.
+    pub node: &'static str,
+    /// The byte span of the node.
+    pub span: Range<usize>,
+    /// The indices of the tokens the node consumed.
+    pub token_range: Range<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lexer::{tokenize, Token};
+    use crate::parser::Parser;
+
+    #[test]
+    fn model_token_range_covers_its_tokens() {
+        let source = "import { Role } from \"./role\"\nmodel User { id: Int }";
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        parser.enable_trace();
+        parser.parse_schema_file();
+        let model = parser
+            .trace()
+            .iter()
+            .find(|entry| entry.node == "model")
+            .unwrap();
+        assert_eq!(tokens[model.token_range.start].0, Token::Model);
+        assert_eq!(tokens[model.token_range.end - 1].0, Token::BraceClose);
+        assert_eq!(model.token_range.end, tokens.len());
+        assert_eq!(&source[model.span.clone()], "model User { id: Int }");
+    }
+
+    #[test]
+    fn tracing_is_off_by_default() {
+        let source = "model User { id: Int }";
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        parser.parse_model().unwrap();
+        assert!(parser.trace().is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use super::{ParseError, Parser};
+use crate::ast::TypeAliasDeclaration;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a `type Name = Target` declaration, with optional type
+    /// parameters after the name, like `type Pair<A, B> = (A, B)`.
+    pub fn parse_type_alias(&mut self) -> Result<TypeAliasDeclaration, ParseError> {
+        let first = self.position();
+        let start = self.expect(Token::Type, "`type`")?.start;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a type alias name")?;
+        let type_parameters = self.parse_type_parameters()?;
+        self.expect(Token::Equal, "`=`")?;
+        let target = self.parse_type()?;
+        self.trace_node("type alias", first);
+        Ok(TypeAliasDeclaration {
+            span: start..target.span().end,
+            name,
+            type_parameters,
+            target,
+            source_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<TypeAliasDeclaration, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_type_alias()
+    }
+
+    #[test]
+    fn parses_a_type_alias() {
+        let alias = parse("type Tags = String[]?").unwrap();
+        assert_eq!(alias.name.name, "Tags");
+        assert!(alias.target.is_optional());
+        assert_eq!(alias.span, 0..21);
+    }
+
+    #[test]
+    fn parses_a_generic_type_alias() {
+        let alias = parse("type Pair<A, B> = (A, B)").unwrap();
+        assert_eq!(alias.name.name, "Pair");
+        let names: Vec<&str> = alias
+            .type_parameters
+            .iter()
+            .map(|parameter| parameter.name.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["A", "B"]);
+        assert_eq!(alias.target.to_string(), "(A, B)");
+        assert_eq!(alias.span, 0..24);
+    }
+
+    #[test]
+    fn reports_a_missing_equal_sign() {
+        let error = parse("type ID String").unwrap_err();
+        assert_eq!(error.message, "expected `=`, found identifier `String`");
+    }
+}
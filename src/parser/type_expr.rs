@@ -0,0 +1,262 @@
+use super::{ParseError, Parser};
+use crate::ast::TypeExpr;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse a type expression.
+    ///
+    /// `|` binds loosest, then the `?` and `[]` suffixes, which apply from
+    /// left to right. So `String[]?` is an optional array and
+    /// `Int | String?` is a union whose second member is optional. Named
+    /// types may have type arguments, which may be nested, like
+    /// `Array<Map<String, Int>>`. Parentheses group a type, like
+    /// `(Int | String)?`, and are a tuple if they are empty or contain a
+    /// `,`, like `(Int, String)` or `(Int,)`.
+    pub fn parse_type(&mut self) -> Result<TypeExpr, ParseError> {
+        self.nested(Self::parse_union)
+    }
+
+    fn parse_union(&mut self) -> Result<TypeExpr, ParseError> {
+        let first = self.parse_type_with_suffixes()?;
+        if !self.at(Token::Pipe) {
+            return Ok(first);
+        }
+        let start = first.span().start;
+        let mut members = vec![first];
+        while self.eat(Token::Pipe).is_some() {
+            members.push(self.parse_type_with_suffixes()?);
+        }
+        Ok(TypeExpr::Union {
+            span: start..self.previous_span().end,
+            members,
+        })
+    }
+
+    fn parse_type_with_suffixes(&mut self) -> Result<TypeExpr, ParseError> {
+        let start = self.peek_span().start;
+        let mut type_expr = match self.at(Token::ParenOpen) {
+            true => self.parse_parenthesized_type()?,
+            false => self.parse_named_type()?,
+        };
+        loop {
+            if let Some(question) = self.eat(Token::Question) {
+                type_expr = TypeExpr::Optional {
+                    inner: Box::new(type_expr),
+                    span: start..question.end,
+                };
+            } else if self.eat(Token::BracketOpen).is_some() {
+                let close = self.expect(Token::BracketClose, "`]`")?;
+                type_expr = TypeExpr::Array {
+                    inner: Box::new(type_expr),
+                    span: start..close.end,
+                };
+            } else {
+                return Ok(type_expr);
+            }
+        }
+    }
+
+    /// Parse a parenthesized type, or a tuple if the parentheses are empty
+    /// or contain a `,`.
+    fn parse_parenthesized_type(&mut self) -> Result<TypeExpr, ParseError> {
+        let open = self.expect(Token::ParenOpen, "`(`")?;
+        if let Some(close) = self.eat(Token::ParenClose) {
+            return Ok(TypeExpr::Tuple {
+                elements: vec![],
+                span: open.start..close.end,
+            });
+        }
+        let first = self.parse_type()?;
+        if self.eat(Token::Comma).is_none() {
+            self.expect(Token::ParenClose, "`,` or `)`")?;
+            return Ok(first);
+        }
+        let mut elements = vec![first];
+        elements.extend(self.parse_comma_separated(Token::ParenClose, "`)`", Self::parse_type)?);
+        Ok(TypeExpr::Tuple {
+            elements,
+            span: open.start..self.previous_span().end,
+        })
+    }
+
+    fn parse_named_type(&mut self) -> Result<TypeExpr, ParseError> {
+        let mut path = vec![self.parse_identifier("a type")?];
+        while self.eat(Token::ColonColon).is_some() {
+            path.push(self.parse_identifier("a type name after `::`")?);
+        }
+        let arguments = match self.eat(Token::AngleOpen) {
+            Some(_) => self.parse_comma_separated(Token::AngleClose, "`>`", Self::parse_type)?,
+            None => vec![],
+        };
+        Ok(TypeExpr::Named {
+            span: path[0].span.start..self.previous_span().end,
+            path,
+            arguments,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<TypeExpr, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_type()
+    }
+
+    /// Renders a type expression with explicit grouping to compare shapes.
+    fn shape(type_expr: &TypeExpr) -> String {
+        match type_expr {
+            TypeExpr::Named { .. } => type_expr.name().unwrap(),
+            TypeExpr::Optional { inner, .. } => format!("optional({})", shape(inner)),
+            TypeExpr::Array { inner, .. } => format!("array({})", shape(inner)),
+            TypeExpr::Union { members, .. } => format!(
+                "union({})",
+                members
+                    .iter()
+                    .map(shape)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            TypeExpr::Tuple { elements, .. } => format!(
+                "tuple({})",
+                elements
+                    .iter()
+                    .map(shape)
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    #[test]
+    fn parses_named_type() {
+        let type_expr = parse("String").unwrap();
+        assert_eq!(shape(&type_expr), "String");
+        assert_eq!(type_expr.span(), 0..6);
+    }
+
+    #[test]
+    fn parses_namespaced_type() {
+        let type_expr = parse("Foo::Bar").unwrap();
+        assert_eq!(shape(&type_expr), "Foo::Bar");
+        assert_eq!(type_expr.span(), 0..8);
+    }
+
+    #[test]
+    fn parses_optional_type() {
+        let type_expr = parse("String?").unwrap();
+        assert_eq!(shape(&type_expr), "optional(String)");
+        assert!(type_expr.is_optional());
+    }
+
+    #[test]
+    fn parses_array_type() {
+        let type_expr = parse("String[]").unwrap();
+        assert_eq!(shape(&type_expr), "array(String)");
+        assert_eq!(type_expr.span(), 0..8);
+    }
+
+    #[test]
+    fn parses_optional_array() {
+        assert_eq!(
+            shape(&parse("String[]?").unwrap()),
+            "optional(array(String))"
+        );
+        assert_eq!(shape(&parse("Enum[]?").unwrap()), "optional(array(Enum))");
+    }
+
+    #[test]
+    fn parses_array_of_optionals() {
+        assert_eq!(
+            shape(&parse("String?[]").unwrap()),
+            "array(optional(String))"
+        );
+    }
+
+    #[test]
+    fn parses_union_of_three_members() {
+        let type_expr = parse("Int | String? | Foo::Bar[]").unwrap();
+        assert_eq!(
+            shape(&type_expr),
+            "union(Int, optional(String), array(Foo::Bar))"
+        );
+        assert_eq!(type_expr.span(), 0..26);
+    }
+
+    #[test]
+    fn parses_tuple_types() {
+        let type_expr = parse("(Int, String | Bool)[]?").unwrap();
+        assert_eq!(
+            shape(&type_expr),
+            "optional(array(tuple(Int, union(String, Bool))))"
+        );
+        assert_eq!(type_expr.span(), 0..23);
+        assert_eq!(type_expr.to_string(), "(Int, String | Bool)[]?");
+        assert_eq!(shape(&parse("()").unwrap()), "tuple()");
+        let error = parse("(Int String)").unwrap_err();
+        assert_eq!(
+            error.message,
+            "expected `,` or `)`, found identifier `String`"
+        );
+    }
+
+    #[test]
+    fn parentheses_group_a_single_type() {
+        let type_expr = parse("(Int | String)?").unwrap();
+        assert_eq!(shape(&type_expr), "optional(union(Int, String))");
+        assert_eq!(type_expr.span(), 0..15);
+        assert_eq!(shape(&parse("((Int))[]").unwrap()), "array(Int)");
+        assert_eq!(shape(&parse("(Int,)").unwrap()), "tuple(Int)");
+        assert_eq!(parse("(Int,)").unwrap().span(), 0..6);
+        assert_eq!(parse("(Int,)").unwrap().to_string(), "(Int,)");
+        assert_eq!(
+            parse("(Int | String)?").unwrap().to_string(),
+            "(Int | String)?"
+        );
+    }
+
+    #[test]
+    fn reports_unterminated_array_suffix() {
+        let error = parse("String[").unwrap_err();
+        assert_eq!(error.message, "expected `]`, found end of file");
+    }
+
+    #[test]
+    fn reports_missing_union_member() {
+        let error = parse("Int |").unwrap_err();
+        assert_eq!(error.message, "expected a type, found end of file");
+    }
+
+    #[test]
+    fn parses_nested_type_arguments() {
+        let type_expr = parse("Array<Map<String, Int>>?").unwrap();
+        assert_eq!(shape(&type_expr), "optional(Array)");
+        assert_eq!(type_expr.to_string(), "Array<Map<String, Int>>?");
+        let TypeExpr::Optional { inner, .. } = type_expr else {
+            panic!("expected an optional type");
+        };
+        let TypeExpr::Named {
+            arguments, span, ..
+        } = *inner
+        else {
+            panic!("expected a named type");
+        };
+        assert_eq!(span, 0..23);
+        assert_eq!(arguments[0].to_string(), "Map<String, Int>");
+    }
+
+    #[test]
+    fn reports_unterminated_type_arguments() {
+        let error = parse("Map<String Int>").unwrap_err();
+        assert_eq!(error.message, "expected `,` or `>`, found identifier `Int`");
+    }
+
+    #[test]
+    fn reports_type_arguments_nested_too_deep() {
+        let source = format!("{}Int{}", "Array<".repeat(1000), ">".repeat(1000));
+        assert_eq!(parse(&source).unwrap_err().message, "nesting too deep");
+    }
+}
@@ -0,0 +1,140 @@
+use super::{ParseError, Parser};
+use crate::ast::{TypeConstraint, TypeExpr, TypeParam};
+use crate::diagnostic::merge_spans;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Parse the `<A, B>` type parameters of a declaration after its name,
+    /// followed by an optional `where A: Bound, B: Bound` clause giving
+    /// them bounds. Returns no parameters if the next token isn't `<`.
+    ///
+    /// A bound for a name that isn't one of the parameters is an error.
+    pub fn parse_type_parameters(&mut self) -> Result<Vec<TypeParam>, ParseError> {
+        if self.eat(Token::AngleOpen).is_none() {
+            return Ok(vec![]);
+        }
+        let mut parameters = self.parse_comma_separated(Token::AngleClose, "`>`", |parser| {
+            Ok(TypeParam {
+                name: parser.parse_identifier("a type parameter name")?,
+                bound: None,
+            })
+        })?;
+        for constraint in self.parse_where_clause()? {
+            let name = match &constraint.subject {
+                TypeExpr::Named {
+                    path, arguments, ..
+                } if path.len() == 1 && arguments.is_empty() => Some(&path[0].name),
+                _ => None,
+            };
+            match parameters.iter_mut().find(|p| Some(&p.name.name) == name) {
+                Some(parameter) => parameter.bound = Some(constraint.bound),
+                None => {
+                    return Err(ParseError::new(
+                        format!("`{}` is not a type parameter", constraint.subject),
+                        constraint.subject.span(),
+                    ))
+                }
+            }
+        }
+        Ok(parameters)
+    }
+
+    /// Parse a `where A: Bound, B: Bound` clause, where each constraint is
+    /// a type, a `:` and the type it is constrained to. Returns no
+    /// constraints if the next token isn't `where`.
+    pub fn parse_where_clause(&mut self) -> Result<Vec<TypeConstraint>, ParseError> {
+        let mut constraints = vec![];
+        if self.eat(Token::Where).is_none() {
+            return Ok(constraints);
+        }
+        loop {
+            let subject = self.parse_type()?;
+            self.expect(Token::Colon, "`:`")?;
+            let bound = self.parse_type()?;
+            constraints.push(TypeConstraint {
+                span: merge_spans(&subject.span(), &bound.span()),
+                subject,
+                bound,
+            });
+            if self.eat(Token::Comma).is_none() {
+                return Ok(constraints);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+
+    fn parse(source: &str) -> Result<Vec<TypeParam>, ParseError> {
+        let tokens = tokenize(source);
+        Parser::new(source, &tokens).parse_type_parameters()
+    }
+
+    fn names(parameters: &[TypeParam]) -> Vec<&str> {
+        parameters
+            .iter()
+            .map(|parameter| parameter.name.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn parses_a_single_type_parameter() {
+        let parameters = parse("<T>").unwrap();
+        assert_eq!(names(&parameters), vec!["T"]);
+        assert_eq!(parameters[0].bound, None);
+        assert!(parse("{").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_multiple_type_parameters() {
+        let parameters = parse("<A, B, C>").unwrap();
+        assert_eq!(names(&parameters), vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn parses_where_constrained_type_parameters() {
+        let source = "<K, V> where K: String | Int, V: Array<Map<String, Int>>";
+        let parameters = parse(source).unwrap();
+        assert_eq!(names(&parameters), vec!["K", "V"]);
+        let bounds: Vec<String> = parameters
+            .iter()
+            .map(|parameter| parameter.bound.as_ref().unwrap().to_string())
+            .collect();
+        assert_eq!(bounds, vec!["String | Int", "Array<Map<String, Int>>"]);
+    }
+
+    fn constraints(source: &str) -> Vec<String> {
+        let tokens = tokenize(source);
+        let constraints = Parser::new(source, &tokens).parse_where_clause().unwrap();
+        constraints
+            .iter()
+            .map(|constraint| format!("{}: {}", constraint.subject, constraint.bound))
+            .collect()
+    }
+
+    #[test]
+    fn parses_a_single_constraint() {
+        assert_eq!(constraints("where T: Scalar"), vec!["T: Scalar"]);
+        assert!(constraints("{").is_empty());
+    }
+
+    #[test]
+    fn parses_comma_separated_constraints() {
+        assert_eq!(
+            constraints("where K: String | Int, Array<V>: Serializable"),
+            vec!["K: String | Int", "Array<V>: Serializable"]
+        );
+    }
+
+    #[test]
+    fn reports_a_bound_for_an_unknown_parameter() {
+        let error = parse("<T> where U: String").unwrap_err();
+        assert_eq!(error.message, "`U` is not a type parameter");
+        assert_eq!(error.span, 10..11);
+        let error = parse("<T> where T[]: String").unwrap_err();
+        assert_eq!(error.message, "`T[]` is not a type parameter");
+    }
+}
@@ -0,0 +1,109 @@
+use super::{ParseError, Parser};
+use crate::ast::UnknownDeclaration;
+use crate::lexer::Token;
+
+impl Parser<'_> {
+    /// Returns true if unknown declarations are allowed and the next tokens
+    /// are an identifier, a name and a `{`.
+    pub(crate) fn at_unknown_declaration(&self) -> bool {
+        self.unknown_declarations()
+            && self.at(Token::Identifier)
+            && self.peek_nth(1) == Some(Token::Identifier)
+            && self.peek_nth(2) == Some(Token::BraceOpen)
+    }
+
+    /// Parse a `keyword Name { ... }` declaration with an unknown keyword.
+    ///
+    /// The body is skipped through its matching closing brace without being
+    /// parsed, so it may contain anything but unbalanced braces.
+    pub fn parse_unknown_declaration(&mut self) -> Result<UnknownDeclaration, ParseError> {
+        let first = self.position();
+        let keyword = self.parse_identifier("a declaration keyword")?;
+        let source_order = self.next_source_order();
+        let name = self.parse_identifier("a declaration name")?;
+        let open = self.expect(Token::BraceOpen, "`{`")?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.advance() {
+                Some((Token::BraceOpen, _)) => depth += 1,
+                Some((Token::BraceClose, _)) => depth -= 1,
+                Some(_) => (),
+                None => {
+                    self.report(ParseError::new(
+                        format!("unterminated {} body, expected `}}`", keyword.name),
+                        open.clone(),
+                    ));
+                    break;
+                }
+            }
+        }
+        let end = self.previous_span().end;
+        self.trace_node("unknown declaration", first);
+        Ok(UnknownDeclaration {
+            span: keyword.span.start..end,
+            keyword,
+            name,
+            body_span: open.start..end,
+            source_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::Declaration;
+    use crate::lexer::tokenize;
+    use crate::parser::Parser;
+
+    fn parse(source: &str) -> (Vec<Declaration>, usize) {
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens).with_unknown_declarations();
+        let schema = parser.parse_schema_file();
+        (schema.declarations, parser.diagnostics().len())
+    }
+
+    #[test]
+    fn captures_an_unknown_declaration() {
+        let source = "view Active {\n  select { users: [1, 2] }\n}";
+        let (declarations, errors) = parse(source);
+        assert_eq!(errors, 0);
+        let Declaration::Unknown(unknown) = &declarations[0] else {
+            panic!("expected an unknown declaration");
+        };
+        assert_eq!(unknown.keyword.name, "view");
+        assert_eq!(unknown.name.name, "Active");
+        assert_eq!(
+            &source[unknown.body_span.clone()],
+            "{\n  select { users: [1, 2] }\n}"
+        );
+        assert_eq!(unknown.span, 0..source.len());
+    }
+
+    #[test]
+    fn parses_known_declarations_structurally() {
+        let (declarations, errors) = parse("view Active { }\nmodel User { id: Int }");
+        assert_eq!(errors, 0);
+        let Declaration::Model(model) = &declarations[1] else {
+            panic!("expected a model");
+        };
+        assert_eq!(model.name.name, "User");
+        assert_eq!(model.fields.len(), 1);
+        assert_eq!(model.source_order, 1);
+    }
+
+    #[test]
+    fn reports_unknown_declarations_unless_allowed() {
+        let source = "view Active { }";
+        let tokens = tokenize(source);
+        let mut parser = Parser::new(source, &tokens);
+        assert!(parser.parse_schema_file().declarations.is_empty());
+        assert_eq!(parser.diagnostics().len(), 1);
+    }
+
+    #[test]
+    fn reports_an_unterminated_body() {
+        let (declarations, errors) = parse("view Active { select {");
+        assert_eq!(errors, 1);
+        assert!(matches!(declarations[0], Declaration::Unknown(_)));
+    }
+}
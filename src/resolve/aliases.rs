@@ -0,0 +1,239 @@
+use crate::ast::TypeExpr;
+use crate::diagnostic::Diagnostic;
+use crate::symbol::SymbolTable;
+
+/// Replace every named type in `type_expr` that refers to a type alias of
+/// `table` with the type the alias stands for, recursively.
+///
+/// Named types that aren't aliases are kept, with their type arguments
+/// expanded. The type arguments of a generic alias take the place of its
+/// type parameters in the target, so `Pair<Int, String>` of
+/// `type Pair<A, B> = (A, B)` expands to `(Int, String)`. Expanded types
+/// keep the spans of the alias targets they came from.
+///
+/// An alias that refers back to itself, directly or through other
+/// aliases, produces a `"cyclic-alias"` error spanning the named type
+/// being expanded. An alias given a different number of type arguments
+/// than it has type parameters produces an `"alias-arguments"` error.
+pub fn expand_aliases(type_expr: &TypeExpr, table: &SymbolTable) -> Result<TypeExpr, Diagnostic> {
+    expand(type_expr, table, &mut vec![])
+}
+
+fn expand(
+    type_expr: &TypeExpr,
+    table: &SymbolTable,
+    expanding: &mut Vec<String>,
+) -> Result<TypeExpr, Diagnostic> {
+    Ok(match type_expr {
+        TypeExpr::Named {
+            path,
+            arguments,
+            span,
+        } => {
+            let name = type_expr.name().unwrap_or_default();
+            let arguments = arguments
+                .iter()
+                .map(|argument| expand(argument, table, expanding))
+                .collect::<Result<Vec<_>, _>>()?;
+            let Some(alias) = table.alias(&name) else {
+                return Ok(TypeExpr::Named {
+                    path: path.clone(),
+                    arguments,
+                    span: span.clone(),
+                });
+            };
+            if expanding.contains(&name) {
+                return Err(Diagnostic::error(
+                    format!("type alias `{}` refers to itself", expanding[0]),
+                    span.clone(),
+                )
+                .with_code("cyclic-alias"));
+            }
+            if arguments.len() != alias.type_parameters.len() {
+                return Err(Diagnostic::error(
+                    format!(
+                        "type alias `{}` takes {} type arguments, found {}",
+                        name,
+                        alias.type_parameters.len(),
+                        arguments.len()
+                    ),
+                    span.clone(),
+                )
+                .with_code("alias-arguments"));
+            }
+            let parameters: Vec<&str> = alias
+                .type_parameters
+                .iter()
+                .map(|parameter| parameter.name.name.as_str())
+                .collect();
+            let target = substitute(&alias.target, &parameters, &arguments);
+            expanding.push(name);
+            let expanded = expand(&target, table, expanding)?;
+            expanding.pop();
+            expanded
+        }
+        TypeExpr::Optional { inner, span } => TypeExpr::Optional {
+            inner: Box::new(expand(inner, table, expanding)?),
+            span: span.clone(),
+        },
+        TypeExpr::Array { inner, span } => TypeExpr::Array {
+            inner: Box::new(expand(inner, table, expanding)?),
+            span: span.clone(),
+        },
+        TypeExpr::Union { members, span } => TypeExpr::Union {
+            members: members
+                .iter()
+                .map(|member| expand(member, table, expanding))
+                .collect::<Result<_, _>>()?,
+            span: span.clone(),
+        },
+        TypeExpr::Tuple { elements, span } => TypeExpr::Tuple {
+            elements: elements
+                .iter()
+                .map(|element| expand(element, table, expanding))
+                .collect::<Result<_, _>>()?,
+            span: span.clone(),
+        },
+    })
+}
+
+/// Returns `type_expr` with every named type called like one of
+/// `parameters` replaced by the argument at the same position.
+fn substitute(type_expr: &TypeExpr, parameters: &[&str], arguments: &[TypeExpr]) -> TypeExpr {
+    let all = |types: &[TypeExpr]| {
+        types
+            .iter()
+            .map(|type_expr| substitute(type_expr, parameters, arguments))
+            .collect()
+    };
+    match type_expr {
+        TypeExpr::Named {
+            path,
+            arguments: type_arguments,
+            span,
+        } => {
+            if let ([segment], []) = (path.as_slice(), type_arguments.as_slice()) {
+                if let Some(index) = parameters.iter().position(|p| *p == segment.name) {
+                    return arguments[index].clone();
+                }
+            }
+            TypeExpr::Named {
+                path: path.clone(),
+                arguments: all(type_arguments),
+                span: span.clone(),
+            }
+        }
+        TypeExpr::Optional { inner, span } => TypeExpr::Optional {
+            inner: Box::new(substitute(inner, parameters, arguments)),
+            span: span.clone(),
+        },
+        TypeExpr::Array { inner, span } => TypeExpr::Array {
+            inner: Box::new(substitute(inner, parameters, arguments)),
+            span: span.clone(),
+        },
+        TypeExpr::Union { members, span } => TypeExpr::Union {
+            members: all(members),
+            span: span.clone(),
+        },
+        TypeExpr::Tuple { elements, span } => TypeExpr::Tuple {
+            elements: all(elements),
+            span: span.clone(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Declaration, SchemaFile};
+    use crate::parser::parse_schema;
+
+    fn schema(source: &str) -> SchemaFile {
+        let result = parse_schema(source, "schema.teo");
+        assert!(result.diagnostics.is_empty());
+        result.schema
+    }
+
+    fn field_type(schema: &SchemaFile, field: usize) -> &TypeExpr {
+        schema
+            .declarations
+            .iter()
+            .find_map(|declaration| match declaration {
+                Declaration::Model(model) => Some(&model.fields[field].type_expr),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn expands_a_simple_alias() {
+        let schema = schema("type ID = String\nmodel User { id: ID?\n name: String }");
+        let table = SymbolTable::from_schema(&schema);
+        let id = expand_aliases(field_type(&schema, 0), &table).unwrap();
+        let TypeExpr::Optional { inner, .. } = id else {
+            panic!("expected an optional type");
+        };
+        assert_eq!(inner.name().as_deref(), Some("String"));
+        assert_eq!(inner.span(), 10..16);
+        let name = field_type(&schema, 1);
+        assert_eq!(&expand_aliases(name, &table).unwrap(), name);
+    }
+
+    #[test]
+    fn expands_a_chained_alias() {
+        let schema =
+            schema("type Key = ID[]\ntype ID = Int | String\nmodel User { keys: Key, id: ID? }");
+        let table = SymbolTable::from_schema(&schema);
+        let keys = expand_aliases(field_type(&schema, 0), &table).unwrap();
+        let id = expand_aliases(field_type(&schema, 1), &table).unwrap();
+        assert_eq!(keys.to_string(), "(Int | String)[]");
+        assert_eq!(id.to_string(), "(Int | String)?");
+        let written = self::schema(&format!("model User {{ keys: {}, id: {} }}", keys, id));
+        assert_eq!(field_type(&written, 0).to_string(), keys.to_string());
+        assert_eq!(field_type(&written, 1).to_string(), id.to_string());
+        assert!(field_type(&written, 1).is_optional());
+        let TypeExpr::Array { inner, .. } = keys else {
+            panic!("expected an array type");
+        };
+        let TypeExpr::Union { members, .. } = *inner else {
+            panic!("expected a union type");
+        };
+        let names: Vec<String> = members.iter().filter_map(TypeExpr::name).collect();
+        assert_eq!(names, vec!["Int", "String"]);
+    }
+
+    #[test]
+    fn expands_a_generic_alias() {
+        let schema = schema(
+            "type Pair<A, B> = (A, B?)\ntype Ids<T> = Pair<T, T>[]\nmodel User { ids: Ids<Int>, pair: Pair<String, Ids<Int>> }",
+        );
+        let table = SymbolTable::from_schema(&schema);
+        let ids = expand_aliases(field_type(&schema, 0), &table).unwrap();
+        assert_eq!(ids.to_string(), "(Int, Int?)[]");
+        let pair = expand_aliases(field_type(&schema, 1), &table).unwrap();
+        assert_eq!(pair.to_string(), "(String, (Int, Int?)[]?)");
+    }
+
+    #[test]
+    fn reports_the_wrong_number_of_type_arguments() {
+        let schema = schema("type Pair<A, B> = (A, B)\nmodel User { pair: Pair<Int> }");
+        let table = SymbolTable::from_schema(&schema);
+        let error = expand_aliases(field_type(&schema, 0), &table).unwrap_err();
+        assert_eq!(error.code, Some("alias-arguments"));
+        assert_eq!(
+            error.message,
+            "type alias `Pair` takes 2 type arguments, found 1"
+        );
+        assert_eq!(error.span, 44..53);
+    }
+
+    #[test]
+    fn reports_a_cyclic_alias() {
+        let schema = schema("type A = B\ntype B = A?\nmodel User { a: A }");
+        let table = SymbolTable::from_schema(&schema);
+        let error = expand_aliases(field_type(&schema, 0), &table).unwrap_err();
+        assert_eq!(error.code, Some("cyclic-alias"));
+        assert_eq!(error.message, "type alias `A` refers to itself");
+        assert_eq!(error.span, 20..21);
+    }
+}
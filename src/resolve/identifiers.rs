@@ -0,0 +1,357 @@
+use super::{ImportGraph, Namespaces, Scope};
+use crate::ast::{
+    Declaration, EnumDeclaration, EnumMember, ExportStatement, Expr, FieldDeclaration,
+    FunctionDeclaration, HandlerDeclaration, Identifier, ImportStatement, LetDeclaration,
+    MiddlewareDeclaration, ModelDeclaration, NamespaceDeclaration, SchemaFile, StructDeclaration,
+    TypeAliasDeclaration, TypeExpr,
+};
+use crate::diagnostic::cover_spans;
+use crate::symbol::SymbolTable;
+use crate::visit::{walk, Visitor};
+use std::ops::Range;
+
+/// The identifier of a lexical scope of a file: `ScopeId(0)` is the top
+/// level, and every namespace, model, struct and enum body gets the next
+/// one in source order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScopeId(pub usize);
+
+/// Whether an [`IdentOccurrence`] declares its name or refers to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdentRole {
+    Definition,
+    Reference,
+}
+
+/// # Identifier occurrence
+///
+/// A place where a name of a schema is written, for finding the edits
+/// that rename it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentOccurrence {
+    /// The name the occurrence resolves to, qualified with its namespaces
+    /// like `blog::Post`. A field or enum member is qualified with its
+    /// declaration, like `blog::Post.title`.
+    pub name: String,
+    /// The byte span of the name as written.
+    pub span: Range<usize>,
+    pub role: IdentRole,
+    /// The scope the occurrence is written in.
+    pub scope: ScopeId,
+}
+
+/// Returns every occurrence of a declared name in `schema`, in source
+/// order: the names of declarations, fields and enum members as
+/// definitions, and the type names, expression names, imported names and
+/// exported names that resolve to one of them as references.
+///
+/// Names are resolved with the [`SymbolTable`] of the file the way
+/// [`check_type_references`](crate::check::check_type_references) looks
+/// them up: in the enclosing namespaces from the innermost one, then at
+/// the top level. Imported names, and the names an `export { .. } from`
+/// statement re-exports, are references to the file they come from.
+/// Names that resolve to nothing, like type parameters and built-in
+/// types, are left out.
+pub fn collect_identifiers(schema: &SchemaFile) -> Vec<IdentOccurrence> {
+    collect(
+        schema,
+        Scope::from_table(&SymbolTable::from_schema(schema), ""),
+    )
+}
+
+/// Returns the occurrences of the file at `path` of `graph` like
+/// [`collect_identifiers`], resolving references with the [`Scope`] of the
+/// file, so that a name imported with `import "./user"` and written
+/// `user::User` is a reference too. Returns nothing if there is no such
+/// file.
+pub fn collect_identifiers_in(graph: &ImportGraph, path: &str) -> Vec<IdentOccurrence> {
+    match graph.files.get(path) {
+        Some(file) => collect(&file.schema, graph.scope(path)),
+        None => vec![],
+    }
+}
+
+fn collect(schema: &SchemaFile, names: Scope) -> Vec<IdentOccurrence> {
+    let mut collector = Collector {
+        names,
+        namespaces: Namespaces::new(),
+        scopes: vec![],
+        next_scope: 1,
+        occurrences: vec![],
+    };
+    walk(&mut collector, schema);
+    collector
+        .occurrences
+        .sort_by_key(|occurrence| occurrence.span.start);
+    collector.occurrences
+}
+
+/// A scope being collected: its id, the offset it ends at, and the prefix
+/// its declarations are qualified with.
+struct OpenScope {
+    id: ScopeId,
+    end: usize,
+    prefix: String,
+}
+
+struct Collector {
+    /// The names a reference may resolve to.
+    names: Scope,
+    namespaces: Namespaces,
+    scopes: Vec<OpenScope>,
+    next_scope: usize,
+    occurrences: Vec<IdentOccurrence>,
+}
+
+impl Collector {
+    fn scope(&self) -> ScopeId {
+        self.scopes.last().map_or(ScopeId(0), |scope| scope.id)
+    }
+
+    /// Returns the prefix the names declared here are qualified with.
+    fn prefix(&self) -> &str {
+        self.scopes.last().map_or("", |scope| scope.prefix.as_str())
+    }
+
+    fn record(&mut self, name: String, span: Range<usize>, role: IdentRole) {
+        self.occurrences.push(IdentOccurrence {
+            name,
+            span,
+            role,
+            scope: self.scope(),
+        });
+    }
+
+    fn define(&mut self, identifier: &Identifier) {
+        let name = format!("{}{}", self.prefix(), identifier.name);
+        self.record(name, identifier.span.clone(), IdentRole::Definition);
+    }
+
+    /// Record the definition of `identifier`, a declaration with a body,
+    /// and open the scope of the body, which ends at `end`.
+    fn open(&mut self, identifier: &Identifier, end: usize, namespace: bool) {
+        self.define(identifier);
+        let separator = if namespace { "::" } else { "." };
+        let prefix = format!("{}{}{}", self.prefix(), identifier.name, separator);
+        self.scopes.push(OpenScope {
+            id: ScopeId(self.next_scope),
+            end,
+            prefix,
+        });
+        self.next_scope += 1;
+    }
+
+    /// Record a reference to `segments` if it resolves to a name.
+    fn refer(&mut self, segments: &[&Identifier]) {
+        let names: Vec<&str> = segments
+            .iter()
+            .map(|segment| segment.name.as_str())
+            .collect();
+        let resolved = self
+            .names
+            .resolve(&names, &self.namespaces, |_| true)
+            .map(|(name, _)| name.to_owned());
+        let span = cover_spans(segments.iter().map(|segment| &segment.span));
+        if let (Some(name), Some(span)) = (resolved, span) {
+            self.record(name, span, IdentRole::Reference);
+        }
+    }
+}
+
+impl Visitor for Collector {
+    fn visit_declaration(&mut self, declaration: &Declaration) {
+        let start = declaration.span().start;
+        self.scopes.retain(|scope| scope.end > start);
+        self.namespaces.leave(declaration);
+    }
+
+    fn visit_import(&mut self, import: &ImportStatement) {
+        for identifier in &import.identifiers {
+            self.record(
+                identifier.name.clone(),
+                identifier.span.clone(),
+                IdentRole::Reference,
+            );
+        }
+    }
+
+    fn visit_export(&mut self, export: &ExportStatement) {
+        for identifier in &export.names {
+            if export.source.is_some() {
+                self.record(
+                    identifier.name.clone(),
+                    identifier.span.clone(),
+                    IdentRole::Reference,
+                );
+            } else {
+                self.refer(&[identifier]);
+            }
+        }
+    }
+
+    fn visit_model(&mut self, model: &ModelDeclaration) {
+        self.open(&model.name, model.span.end, false);
+    }
+
+    fn visit_field(&mut self, field: &FieldDeclaration) {
+        self.define(&field.name);
+    }
+
+    fn visit_enum(&mut self, r#enum: &EnumDeclaration) {
+        self.open(&r#enum.name, r#enum.span.end, false);
+    }
+
+    fn visit_enum_member(&mut self, member: &EnumMember) {
+        self.define(&member.name);
+    }
+
+    fn visit_type_alias(&mut self, alias: &TypeAliasDeclaration) {
+        self.define(&alias.name);
+    }
+
+    fn visit_let(&mut self, binding: &LetDeclaration) {
+        self.define(&binding.name);
+    }
+
+    fn visit_struct(&mut self, r#struct: &StructDeclaration) {
+        self.open(&r#struct.name, r#struct.span.end, false);
+    }
+
+    fn visit_function(&mut self, function: &FunctionDeclaration) {
+        self.define(&function.name);
+    }
+
+    fn visit_middleware(&mut self, middleware: &MiddlewareDeclaration) {
+        self.define(&middleware.name);
+    }
+
+    fn visit_handler(&mut self, handler: &HandlerDeclaration) {
+        self.define(&handler.name);
+    }
+
+    fn visit_namespace(&mut self, namespace: &NamespaceDeclaration) {
+        self.open(&namespace.name, namespace.span.end, true);
+        self.namespaces.enter(namespace);
+    }
+
+    fn visit_type_expr(&mut self, type_expr: &TypeExpr) {
+        if let TypeExpr::Named { path, .. } = type_expr {
+            self.refer(&path.iter().collect::<Vec<_>>());
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let Expr::Identifier(identifier) = expr {
+            self.refer(&[identifier]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fsutil::FSUtil;
+    use crate::parser::parse_schema;
+    use crate::resolve::resolve_imports;
+    use std::collections::HashMap;
+
+    fn occurrences(source: &str) -> Vec<(String, &str, IdentRole, usize)> {
+        let result = parse_schema(source, "schema.teo");
+        assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+        collect_identifiers(&result.schema)
+            .into_iter()
+            .map(|o| (o.name, &source[o.span], o.role, o.scope.0))
+            .collect()
+    }
+
+    #[test]
+    fn finds_the_definition_and_references_of_a_model() {
+        let source = "model User { id: Int, friend: User? }\nmodel Post { author: User }";
+        let users: Vec<_> = occurrences(source)
+            .into_iter()
+            .filter(|(name, ..)| name == "User")
+            .collect();
+        assert_eq!(
+            users,
+            vec![
+                ("User".to_owned(), "User", IdentRole::Definition, 0),
+                ("User".to_owned(), "User", IdentRole::Reference, 1),
+                ("User".to_owned(), "User", IdentRole::Reference, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn qualifies_members_and_namespaced_names() {
+        let source = "let size = 20\n\
+                      namespace blog {\n\
+                      enum Status { DRAFT }\n\
+                      model Post { @map(size) status: Status, other: blog::Status }\n\
+                      }\n\
+                      export { blog }";
+        let all = occurrences(source);
+        assert_eq!(
+            all,
+            vec![
+                ("size".to_owned(), "size", IdentRole::Definition, 0),
+                ("blog".to_owned(), "blog", IdentRole::Definition, 0),
+                (
+                    "blog::Status".to_owned(),
+                    "Status",
+                    IdentRole::Definition,
+                    1
+                ),
+                (
+                    "blog::Status.DRAFT".to_owned(),
+                    "DRAFT",
+                    IdentRole::Definition,
+                    2
+                ),
+                ("blog::Post".to_owned(), "Post", IdentRole::Definition, 1),
+                ("size".to_owned(), "size", IdentRole::Reference, 3),
+                (
+                    "blog::Post.status".to_owned(),
+                    "status",
+                    IdentRole::Definition,
+                    3
+                ),
+                ("blog::Status".to_owned(), "Status", IdentRole::Reference, 3),
+                (
+                    "blog::Post.other".to_owned(),
+                    "other",
+                    IdentRole::Definition,
+                    3
+                ),
+                (
+                    "blog::Status".to_owned(),
+                    "blog::Status",
+                    IdentRole::Reference,
+                    3
+                ),
+                ("blog".to_owned(), "blog", IdentRole::Reference, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_imported_names_through_the_scope() {
+        let files = HashMap::from([
+            (
+                "schema/main.teo".to_owned(),
+                "import \"./user\"\nmodel Post { author: user::User, editor: User }".to_owned(),
+            ),
+            (
+                "schema/user.teo".to_owned(),
+                "model User { id: Int }".to_owned(),
+            ),
+        ]);
+        let graph = resolve_imports("schema/main.teo", &FSUtil::in_memory(files));
+        let references: Vec<String> = collect_identifiers_in(&graph, "schema/main.teo")
+            .into_iter()
+            .filter(|o| o.role == IdentRole::Reference)
+            .map(|o| o.name)
+            .collect();
+        assert_eq!(references, vec!["user.User"]);
+        assert!(collect_identifiers_in(&graph, "schema/missing.teo").is_empty());
+    }
+}
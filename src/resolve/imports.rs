@@ -0,0 +1,611 @@
+use super::session::FileCache;
+use crate::ast::{Declaration, ImportStatement};
+use crate::diagnostic::{Diagnostic, DiagnosticSink};
+use crate::fsutil::{FSUtil, FsError};
+use crate::parser::{parse_schema, ParseResult};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The extension tried when an imported path names no file.
+pub const SCHEMA_EXTENSION: &str = "teo";
+
+/// The file tried when an imported path names a directory.
+pub const INDEX_FILE: &str = "index.teo";
+
+/// # Import graph
+///
+/// Every schema file reachable from an entry file through `import` and
+/// `include` statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportGraph {
+    /// The path of the file the resolution started from.
+    pub entry: String,
+    /// The parsed files by path. Problems resolving an import are reported
+    /// in the diagnostics of the importing file. A file is shared with the
+    /// cache of a [`ParseSession`](super::ParseSession) that parsed it.
+    pub files: BTreeMap<String, Arc<ParseResult>>,
+    /// The imports of each file that were resolved to a file of the graph,
+    /// in the order they are written. Unresolved, unreadable and circular
+    /// imports are left out.
+    pub imports: BTreeMap<String, Vec<ResolvedImport>>,
+}
+
+/// # Resolved import
+///
+/// An import statement and the file of the graph it refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedImport {
+    /// The statement as written.
+    pub import: ImportStatement,
+    /// The key of the imported file.
+    pub path: String,
+}
+
+impl ImportGraph {
+    /// Returns the parsed entry file, which is empty if it couldn't be
+    /// read.
+    pub fn entry(&self) -> Option<&ParseResult> {
+        self.files.get(&self.entry).map(Arc::as_ref)
+    }
+}
+
+/// Parse `entry_path` and every file it imports, transitively.
+///
+/// Each import is resolved relative to the importing file with
+/// [`FSUtil::import_path`]. When the path names no file, the path with the
+/// [`SCHEMA_EXTENSION`] appended is tried, then the [`INDEX_FILE`] inside
+/// the path if it is a directory, so `import "./models"` finds
+/// `./models.teo` or `./models/index.teo`. Files are keyed by their
+/// [canonical](FSUtil::canonicalize) paths, when the utility can resolve
+/// them, with `/` separators on every platform, so every file is parsed
+/// once however it is reached. An import of
+/// no file produces an `"unresolved-import"` error and an import
+/// that leads back to a file still being resolved produces a
+/// `"circular-import"` error listing the chain of files. An import of a
+/// file that cannot be read produces an `"unreadable-import"` error with
+/// the reason, like the permission being denied. When the utility
+/// [checks case](FSUtil::with_case_check), an import naming a file in a
+/// case other than the stored one produces an `"import-case-mismatch"`
+/// warning. All of them span the source string of the import. An entry
+/// file that cannot be read is in the graph as an empty file with an
+/// `"unreadable-entry"` error at its start.
+pub fn resolve_imports(entry_path: &str, fs: &FSUtil) -> ImportGraph {
+    Resolver::new(fs, None, None).resolve(entry_path)
+}
+
+/// Parse `entry_path` and every file it imports, like [`resolve_imports`],
+/// sending every diagnostic to `sink`, with the path of its file, as soon
+/// as it is found. The diagnostics of the files of the graph are left
+/// empty.
+pub fn resolve_imports_with_sink(
+    entry_path: &str,
+    fs: &FSUtil,
+    sink: &mut dyn DiagnosticSink,
+) -> ImportGraph {
+    Resolver::new(fs, Some(sink), None).resolve(entry_path)
+}
+
+/// Resolve the graph of `entry_path` like [`resolve_imports`], taking the
+/// parsed files from `cache` when it has them.
+pub(super) fn resolve_imports_with_cache(
+    entry_path: &str,
+    fs: &FSUtil,
+    cache: &mut FileCache,
+) -> ImportGraph {
+    Resolver::new(fs, None, Some(cache)).resolve(entry_path)
+}
+
+struct Resolver<'a> {
+    fs: &'a FSUtil,
+    files: BTreeMap<String, Arc<ParseResult>>,
+    imports: BTreeMap<String, Vec<ResolvedImport>>,
+    /// The paths being resolved, from the entry file to the current one.
+    stack: Vec<String>,
+    /// Where diagnostics are sent as they are found, if not kept in the
+    /// files.
+    sink: Option<&'a mut dyn DiagnosticSink>,
+    /// The files parsed before, if they can be reused.
+    cache: Option<&'a mut FileCache>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(
+        fs: &'a FSUtil,
+        sink: Option<&'a mut dyn DiagnosticSink>,
+        cache: Option<&'a mut FileCache>,
+    ) -> Self {
+        Self {
+            fs,
+            files: BTreeMap::new(),
+            imports: BTreeMap::new(),
+            stack: vec![],
+            sink,
+            cache,
+        }
+    }
+
+    fn resolve(mut self, entry_path: &str) -> ImportGraph {
+        let entry = self.key(entry_path.to_owned());
+        match self.parse(&entry) {
+            Ok(result) => self.visit(&entry, result),
+            Err(error) => {
+                let mut result = Arc::new(parse_schema("", &entry));
+                self.report(
+                    &entry,
+                    &mut result,
+                    Diagnostic::error(format!("cannot read `{}`: {}", entry_path, error), 0..0)
+                        .with_code("unreadable-entry"),
+                );
+                self.files.insert(entry.clone(), result);
+                self.imports.insert(entry.clone(), vec![]);
+            }
+        }
+        ImportGraph {
+            entry,
+            files: self.files,
+            imports: self.imports,
+        }
+    }
+
+    /// Send `diagnostic` about the file at `path` to the sink, or keep it
+    /// in `result` if there is none.
+    fn report(&mut self, path: &str, result: &mut Arc<ParseResult>, diagnostic: Diagnostic) {
+        match &mut self.sink {
+            Some(sink) => sink.emit_in_file(path, diagnostic),
+            None => Arc::make_mut(result).diagnostics.push(diagnostic),
+        }
+    }
+
+    /// Returns the path files are keyed by for `path`.
+    fn key(&self, path: String) -> String {
+        self.fs.path_key(&path)
+    }
+
+    /// Returns the key of the file `path` refers to, trying the schema
+    /// extension and the index file of a directory when `path` names no
+    /// file. With it the stored path of the file, if the utility checks
+    /// case and `path` names it in another case.
+    fn locate(&self, path: String) -> Option<(String, Option<String>)> {
+        let is_file = |path: &str| self.fs.file_exists(path) && !self.fs.file_is_directory(path);
+        // The case is checked before canonicalizing, which may answer the
+        // stored case.
+        let found = |candidate: &str| {
+            let key = self.key(candidate.to_owned());
+            if !is_file(&key) {
+                return None;
+            }
+            let mismatch = match self.fs.checks_case() {
+                true => self.fs.case_mismatch(candidate),
+                false => None,
+            };
+            Some((key, mismatch))
+        };
+        if let Some(found) = found(&path) {
+            return Some(found);
+        }
+        if let Some(found) = found(&format!("{}.{}", path, SCHEMA_EXTENSION)) {
+            return Some(found);
+        }
+        if self.fs.file_is_directory(&self.key(path.clone())) {
+            return found(&self.fs.path_join(&path, INDEX_FILE));
+        }
+        None
+    }
+
+    /// Parse the file at `path`, or take it from the cache. An error if
+    /// the file cannot be read.
+    fn parse(&mut self, path: &str) -> Result<Arc<ParseResult>, FsError> {
+        match &mut self.cache {
+            Some(cache) => cache.parse(self.fs, path),
+            None => Ok(Arc::new(parse_schema(&self.fs.try_read_file(path)?, path))),
+        }
+    }
+
+    fn visit(&mut self, path: &str, mut result: Arc<ParseResult>) {
+        if let Some(sink) = &mut self.sink {
+            if !result.diagnostics.is_empty() {
+                for diagnostic in Arc::make_mut(&mut result).diagnostics.drain(..) {
+                    sink.emit_in_file(path, diagnostic);
+                }
+            }
+        }
+        self.stack.push(path.to_owned());
+        let imports: Vec<ImportStatement> = result
+            .schema
+            .declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                Declaration::Import(import) => Some(import.clone()),
+                _ => None,
+            })
+            .collect();
+        let mut resolved = vec![];
+        for import in imports {
+            let Some((imported, mismatch)) = self.locate(self.fs.import_path(path, &import.source))
+            else {
+                self.report(
+                    path,
+                    &mut result,
+                    Diagnostic::error(
+                        format!("cannot find `{}`", import.source),
+                        import.source_span.clone(),
+                    )
+                    .with_code("unresolved-import"),
+                );
+                continue;
+            };
+            if let Some(stored) = mismatch {
+                self.report(
+                    path,
+                    &mut result,
+                    Diagnostic::warning(
+                        format!(
+                            "`{}` names `{}` in another case, which only works on case-insensitive file systems",
+                            import.source, stored
+                        ),
+                        import.source_span.clone(),
+                    )
+                    .with_code("import-case-mismatch"),
+                );
+            }
+            if let Some(index) = self.stack.iter().position(|p| *p == imported) {
+                let mut chain = self.stack[index..].to_vec();
+                chain.push(imported);
+                self.report(
+                    path,
+                    &mut result,
+                    Diagnostic::error(
+                        format!("circular import: {}", chain.join(" -> ")),
+                        import.source_span.clone(),
+                    )
+                    .with_code("circular-import"),
+                );
+            } else if self.files.contains_key(&imported) {
+                resolved.push(ResolvedImport {
+                    import,
+                    path: imported,
+                });
+            } else {
+                match self.parse(&imported) {
+                    Ok(imported_result) => {
+                        self.visit(&imported, imported_result);
+                        resolved.push(ResolvedImport {
+                            import,
+                            path: imported,
+                        });
+                    }
+                    Err(error) => self.report(
+                        path,
+                        &mut result,
+                        Diagnostic::error(
+                            format!("cannot read `{}`: {}", import.source, error),
+                            import.source_span.clone(),
+                        )
+                        .with_code("unreadable-import"),
+                    ),
+                }
+            }
+        }
+        self.stack.pop();
+        self.files.insert(path.to_owned(), result);
+        self.imports.insert(path.to_owned(), resolved);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_file(path: &str) -> Option<String> {
+        let source = match path {
+            "schema/main.teo" => "import { User } from \"./user.teo\"\ninclude \"./post.teo\"",
+            "schema/user.teo" => "include \"./shared/role.teo\"\nmodel User { id: Int }",
+            "schema/post.teo" => "include \"./user.teo\"\ninclude \"./missing.teo\"",
+            "schema/shared/role.teo" => "enum Role { ADMIN }",
+            "cycle/a.teo" => "include \"./b.teo\"",
+            "cycle/b.teo" => "include \"./a.teo\"",
+            "ring/a.teo" => "include \"./b.teo\"\nmodel A { id: Int }",
+            "ring/b.teo" => "include \"./c.teo\"",
+            "ring/c.teo" => "model C { id: Int }\ninclude \"./a.teo\"",
+            "dup/main.teo" => "include \"./a/x.teo\"\ninclude \"./a/../a/y.teo\"",
+            "dup/a/x.teo" => "model X { id: Int }",
+            "dup/a/y.teo" => "include \"./x.teo\"",
+            "ext/main.teo" => {
+                "include \"./user.teo\"\ninclude \"./role\"\ninclude \"./models\"\ninclude \"./post\""
+            }
+            "ext/user.teo" => "model User { id: Int }",
+            "ext/role.teo" => "enum Role { ADMIN }",
+            "ext/models/index.teo" => "model Post { id: Int }",
+            _ => return None,
+        };
+        Some(source.to_owned())
+    }
+
+    fn file_exists(path: &str) -> bool {
+        read_file(path).is_some() || file_is_directory(path)
+    }
+
+    fn file_is_directory(path: &str) -> bool {
+        path == "ext/models"
+    }
+
+    fn path_join(base: &str, path: &str) -> String {
+        let path = path.trim_start_matches("./");
+        match base {
+            "" => path.to_owned(),
+            _ => format!("{}/{}", base, path),
+        }
+    }
+
+    fn parent_directory(path: &str) -> String {
+        match path.rsplit_once('/') {
+            Some((parent, _)) => parent.to_owned(),
+            None => "".to_owned(),
+        }
+    }
+
+    fn path_is_absolute(path: &str) -> bool {
+        path.starts_with('/')
+    }
+
+    fn write_file(_path: &str, _contents: &str) -> Result<(), String> {
+        Err("read-only".to_owned())
+    }
+
+    fn read_dir(_path: &str) -> Option<Vec<String>> {
+        None
+    }
+
+    fn fs() -> FSUtil {
+        FSUtil::new(
+            read_file,
+            file_exists,
+            file_is_directory,
+            path_join,
+            parent_directory,
+            path_is_absolute,
+            write_file,
+            read_dir,
+        )
+    }
+
+    #[test]
+    fn loads_imports_transitively() {
+        let graph = resolve_imports("schema/main.teo", &fs());
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "schema/main.teo",
+                "schema/post.teo",
+                "schema/shared/role.teo",
+                "schema/user.teo",
+            ]
+        );
+        assert!(graph.entry().unwrap().diagnostics.is_empty());
+        assert!(graph.files["schema/user.teo"].diagnostics.is_empty());
+    }
+
+    #[test]
+    fn reports_an_unresolved_import() {
+        let graph = resolve_imports("schema/main.teo", &fs());
+        let diagnostics = &graph.files["schema/post.teo"].diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("unresolved-import"));
+        assert_eq!(diagnostics[0].message, "cannot find `./missing.teo`");
+        assert_eq!(diagnostics[0].span, 29..44);
+    }
+
+    #[test]
+    fn reports_a_circular_import_instead_of_looping() {
+        let graph = resolve_imports("cycle/a.teo", &fs());
+        assert_eq!(graph.files.len(), 2);
+        assert!(graph.files["cycle/a.teo"].diagnostics.is_empty());
+        let diagnostics = &graph.files["cycle/b.teo"].diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("circular-import"));
+        assert_eq!(
+            diagnostics[0].message,
+            "circular import: cycle/a.teo -> cycle/b.teo -> cycle/a.teo"
+        );
+        assert_eq!(diagnostics[0].span, 8..17);
+    }
+
+    #[test]
+    fn reports_the_chain_of_a_three_file_cycle() {
+        let graph = resolve_imports("ring/b.teo", &fs());
+        assert_eq!(graph.files.len(), 3);
+        let diagnostics = &graph.files["ring/a.teo"].diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "circular import: ring/b.teo -> ring/c.teo -> ring/a.teo -> ring/b.teo"
+        );
+        assert_eq!(diagnostics[0].span, 8..17);
+        assert!(graph.files["ring/c.teo"].diagnostics.is_empty());
+    }
+
+    #[test]
+    fn keys_files_by_their_canonical_paths() {
+        let normalize = |path: &str| {
+            let mut segments: Vec<&str> = vec![];
+            for segment in path.split('/') {
+                match segment {
+                    "." => (),
+                    ".." => {
+                        segments.pop();
+                    }
+                    segment => segments.push(segment),
+                }
+            }
+            Some(segments.join("/"))
+        };
+        let graph = resolve_imports("dup/main.teo", &fs().with_canonicalize(normalize));
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["dup/a/x.teo", "dup/a/y.teo", "dup/main.teo"]);
+    }
+
+    #[test]
+    fn keys_files_with_forward_slashes() {
+        let posix = resolve_imports("schema/main.teo", &fs());
+        let windows = resolve_imports("schema\\main.teo", &fs());
+        assert_eq!(windows.entry, "schema/main.teo");
+        assert_eq!(windows, posix);
+    }
+
+    #[test]
+    fn falls_back_to_the_extension_and_the_directory_index() {
+        let graph = resolve_imports("ext/main.teo", &fs());
+        let paths: Vec<&str> = graph.files.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "ext/main.teo",
+                "ext/models/index.teo",
+                "ext/role.teo",
+                "ext/user.teo"
+            ]
+        );
+        let diagnostics = &graph.entry().unwrap().diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("unresolved-import"));
+        assert_eq!(diagnostics[0].message, "cannot find `./post`");
+    }
+
+    #[test]
+    fn streams_diagnostics_with_their_paths_to_a_sink() {
+        struct PathSink(Vec<(String, &'static str)>);
+        impl DiagnosticSink for PathSink {
+            fn emit(&mut self, _diagnostic: Diagnostic) {
+                unreachable!("the resolver always knows the path");
+            }
+            fn emit_in_file(&mut self, path: &str, diagnostic: Diagnostic) {
+                self.0.push((path.to_owned(), diagnostic.code.unwrap()));
+            }
+        }
+        let mut sink = PathSink(vec![]);
+        let graph = resolve_imports_with_sink("cycle/a.teo", &fs(), &mut sink);
+        assert_eq!(sink.0, vec![("cycle/b.teo".to_owned(), "circular-import")]);
+        assert!(graph.files.values().all(|file| file.diagnostics.is_empty()));
+    }
+
+    #[test]
+    fn reports_why_an_import_cannot_be_read() {
+        let fs = fs().with_try_read_file(|path| match path {
+            "schema/shared/role.teo" => Err(FsError::PermissionDenied),
+            "schema/post.teo" => Err(FsError::InvalidUtf8),
+            _ => read_file(path).ok_or(FsError::NotFound),
+        });
+        let graph = resolve_imports("schema/main.teo", &fs);
+        let messages = |path: &str| -> Vec<(Option<&str>, String)> {
+            let diagnostics = &graph.files[path].diagnostics;
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.code, diagnostic.message.clone()))
+                .collect()
+        };
+        assert_eq!(
+            messages("schema/user.teo"),
+            vec![(
+                Some("unreadable-import"),
+                "cannot read `./shared/role.teo`: permission denied".to_owned()
+            )]
+        );
+        assert_eq!(
+            messages("schema/main.teo"),
+            vec![(
+                Some("unreadable-import"),
+                "cannot read `./post.teo`: not valid UTF-8".to_owned()
+            )]
+        );
+        assert!(!graph.files.contains_key("schema/post.teo"));
+    }
+
+    #[test]
+    fn warns_about_an_import_in_another_case() {
+        let files = FSUtil::in_memory(std::collections::HashMap::from([
+            (
+                "schema/main.teo".to_owned(),
+                "include \"./User\"\ninclude \"./role\"".to_owned(),
+            ),
+            (
+                "schema/user.teo".to_owned(),
+                "model User { id: Int }".to_owned(),
+            ),
+            (
+                "schema/role.teo".to_owned(),
+                "enum Role { ADMIN }".to_owned(),
+            ),
+        ]));
+        // Looks files up ignoring case, like the default file system of macOS.
+        let (read, exists, directory, join, parent, list) = (
+            files.clone(),
+            files.clone(),
+            files.clone(),
+            files.clone(),
+            files.clone(),
+            files,
+        );
+        let fs = FSUtil::new(
+            move |path| read.read_file(&read.stored_case(path)?),
+            move |path| exists.stored_case(path).is_some(),
+            move |path| {
+                let stored = directory.stored_case(path);
+                stored.is_some_and(|path| directory.file_is_directory(&path))
+            },
+            move |base, path| join.path_join(base, path),
+            move |path| parent.parent_directory(path),
+            |path| path.starts_with('/'),
+            |_, _| Err("read-only".to_owned()),
+            move |path| list.read_dir(&list.stored_case(path)?),
+        );
+        let graph = resolve_imports("schema/main.teo", &fs);
+        assert!(graph.entry().unwrap().diagnostics.is_empty());
+        let graph = resolve_imports("schema/main.teo", &fs.with_case_check());
+        let diagnostics = &graph.entry().unwrap().diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, Some("import-case-mismatch"));
+        assert_eq!(
+            diagnostics[0].message,
+            "`./User` names `schema/user.teo` in another case, which only works on case-insensitive file systems"
+        );
+        assert_eq!(diagnostics[0].span, 8..16);
+    }
+
+    #[test]
+    fn reports_an_entry_that_cannot_be_read() {
+        let graph = resolve_imports("nowhere.teo", &fs());
+        let entry = graph.entry().unwrap();
+        assert!(entry.schema.declarations.is_empty());
+        assert_eq!(entry.diagnostics.len(), 1);
+        assert_eq!(entry.diagnostics[0].code, Some("unreadable-entry"));
+        assert_eq!(entry.diagnostics[0].span, 0..0);
+        assert!(entry.diagnostics[0]
+            .message
+            .starts_with("cannot read `nowhere.teo`: "));
+        assert_eq!(graph.files.len(), 1);
+    }
+
+    #[test]
+    fn an_import_inside_a_namespace_is_an_error() {
+        let files = FSUtil::in_memory(std::collections::HashMap::from([
+            (
+                "schema/main.teo".to_owned(),
+                "namespace blog { import \"./user\" }".to_owned(),
+            ),
+            (
+                "schema/user.teo".to_owned(),
+                "model User { id: Int }".to_owned(),
+            ),
+        ]));
+        let graph = resolve_imports("schema/main.teo", &files);
+        let diagnostics = &graph.entry().unwrap().diagnostics;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "imports are written at the top level of a file, not inside a namespace"
+        );
+        assert_eq!(diagnostics[0].span, 17..32);
+        assert_eq!(graph.files.len(), 1);
+    }
+}
@@ -0,0 +1,15 @@
+pub mod aliases;
+pub mod extract;
+pub mod identifiers;
+pub mod imports;
+pub mod scope;
+pub mod session;
+
+pub use aliases::expand_aliases;
+pub use extract::extract_imports;
+pub use identifiers::{
+    collect_identifiers, collect_identifiers_in, IdentOccurrence, IdentRole, ScopeId,
+};
+pub use imports::{resolve_imports, resolve_imports_with_sink, ImportGraph, ResolvedImport};
+pub use scope::{Namespaces, Scope, ScopeEntry};
+pub use session::ParseSession;
@@ -0,0 +1,328 @@
+use super::ImportGraph;
+use crate::ast::{Declaration, ImportKind, NamespaceDeclaration};
+use crate::symbol::{Symbol, SymbolKind, SymbolTable};
+use std::collections::{BTreeMap, HashSet};
+
+/// # Scope
+///
+/// The names visible at the top level of a file of an [`ImportGraph`],
+/// with the files declaring them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scope {
+    entries: BTreeMap<String, ScopeEntry>,
+}
+
+/// A name visible in a [`Scope`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeEntry {
+    /// The symbol as declared, under the name it was declared with.
+    pub symbol: Symbol,
+    /// The path of the file declaring the symbol.
+    pub path: String,
+}
+
+impl Scope {
+    /// Returns the entry visible as `name`, if there is one.
+    pub fn get(&self, name: &str) -> Option<&ScopeEntry> {
+        self.entries.get(name)
+    }
+
+    /// Returns true if something is visible as `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Returns the visible names in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Returns the name `segments` refer to inside `namespaces`, with its
+    /// entry: the first of their [`Namespaces::candidates`] visible here
+    /// whose entry `accept` returns true for.
+    pub fn resolve(
+        &self,
+        segments: &[&str],
+        namespaces: &Namespaces,
+        accept: impl Fn(&ScopeEntry) -> bool,
+    ) -> Option<(&str, &ScopeEntry)> {
+        namespaces.candidates(segments).find_map(|name| {
+            self.entries
+                .get_key_value(&name)
+                .filter(|(_, entry)| accept(entry))
+                .map(|(name, entry)| (name.as_str(), entry))
+        })
+    }
+
+    /// Returns the scope of a file at `path` that imports nothing, with
+    /// every symbol of `table` visible.
+    pub(crate) fn from_table(table: &SymbolTable, path: &str) -> Scope {
+        let mut scope = Scope::default();
+        for symbol in table.iter() {
+            let entry = ScopeEntry {
+                symbol: symbol.clone(),
+                path: path.to_owned(),
+            };
+            scope.insert(symbol.name.clone(), entry);
+        }
+        scope
+    }
+
+    fn insert(&mut self, name: String, entry: ScopeEntry) {
+        self.entries.entry(name).or_insert(entry);
+    }
+}
+
+/// # Namespaces
+///
+/// The namespaces enclosing the declaration a [`Visitor`] is at, for
+/// looking names up with [`Scope::resolve`] the way they resolve there.
+///
+/// [`Visitor`]: crate::visit::Visitor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Namespaces {
+    /// The qualified name of each namespace, like `blog::drafts::`, with
+    /// the offset it ends at, from the outermost one.
+    open: Vec<(String, usize)>,
+}
+
+impl Namespaces {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leave the namespaces that end before `declaration`. Call it from
+    /// [`Visitor::visit_declaration`](crate::visit::Visitor::visit_declaration),
+    /// which comes before the hook of each declaration.
+    pub fn leave(&mut self, declaration: &Declaration) {
+        let start = declaration.span().start;
+        self.open.retain(|(_, end)| *end > start);
+    }
+
+    /// Enter `namespace`, whose declarations come next.
+    pub fn enter(&mut self, namespace: &NamespaceDeclaration) {
+        let prefix = format!("{}{}::", self.prefix(), namespace.name.name);
+        self.open.push((prefix, namespace.span.end));
+    }
+
+    /// Returns the prefix the declarations here are qualified with, like
+    /// `blog::drafts::`, or nothing at the top level.
+    pub fn prefix(&self) -> &str {
+        self.open.last().map_or("", |(prefix, _)| prefix.as_str())
+    }
+
+    /// Returns the names `segments` may refer to here, in the order they
+    /// are looked up: qualified with the enclosing namespaces from the
+    /// innermost one, unqualified, then joined with dots like the
+    /// `user.User` of an `import "./user"`.
+    pub fn candidates(&self, segments: &[&str]) -> impl Iterator<Item = String> + '_ {
+        let name = segments.join("::");
+        let dotted = segments.join(".");
+        let qualified: Vec<String> = self
+            .open
+            .iter()
+            .rev()
+            .map(|(prefix, _)| format!("{}{}", prefix, name))
+            .collect();
+        qualified.into_iter().chain([name, dotted])
+    }
+}
+
+impl ImportGraph {
+    /// Returns the names visible in the file at `path`.
+    ///
+    /// `include` merges the declarations of the included file into the
+    /// including one, so they are visible unqualified, and so are the
+    /// declarations the included file includes in turn. `import` keeps
+    /// them apart: `import { User } from "./user"` makes only `User`
+    /// visible, and `import "./user"` makes every declaration visible
+    /// qualified with the name of the imported path, like `user.User`.
+    /// When a name is visible more than once, the file's own declarations
+    /// win, then the earliest statement.
+    pub fn scope(&self, path: &str) -> Scope {
+        let mut scope = Scope::default();
+        for (name, entry) in self.members(path) {
+            scope.insert(name, entry);
+        }
+        for resolved in self.imports.get(path).into_iter().flatten() {
+            let import = &resolved.import;
+            if import.kind != ImportKind::Import {
+                continue;
+            }
+            let members = self.members(&resolved.path);
+            if import.identifiers.is_empty() {
+                let namespace = namespace(&import.source);
+                for (name, entry) in members {
+                    scope.insert(format!("{}.{}", namespace, name), entry);
+                }
+            } else {
+                for identifier in &import.identifiers {
+                    let name = &identifier.name;
+                    if let Some((_, entry)) = members.iter().find(|(member, _)| member == name) {
+                        scope.insert(name.clone(), entry.clone());
+                    }
+                }
+            }
+        }
+        scope
+    }
+
+    /// Returns the declarations of the file at `path` and of the files it
+    /// includes, transitively, in the order they are found.
+    fn members(&self, path: &str) -> Vec<(String, ScopeEntry)> {
+        let mut members = vec![];
+        let mut visited = HashSet::new();
+        self.collect_members(path, &mut visited, &mut members);
+        members
+    }
+
+    fn collect_members(
+        &self,
+        path: &str,
+        visited: &mut HashSet<String>,
+        members: &mut Vec<(String, ScopeEntry)>,
+    ) {
+        if !visited.insert(path.to_owned()) {
+            return;
+        }
+        let Some(file) = self.files.get(path) else {
+            return;
+        };
+        let table = SymbolTable::from_schema(&file.schema);
+        for symbol in table.iter().filter(|s| s.kind != SymbolKind::Import) {
+            let entry = ScopeEntry {
+                symbol: symbol.clone(),
+                path: path.to_owned(),
+            };
+            members.push((symbol.name.clone(), entry));
+        }
+        for resolved in self.imports.get(path).into_iter().flatten() {
+            if resolved.import.kind == ImportKind::Include {
+                self.collect_members(&resolved.path, visited, members);
+            }
+        }
+    }
+}
+
+/// Returns the name the declarations of an `import "source"` are qualified
+/// with: the last segment of the source without the schema extension.
+fn namespace(source: &str) -> &str {
+    let name = source.rsplit(['/', '\\']).next().unwrap_or(source);
+    name.strip_suffix(".teo").unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fsutil::FSUtil;
+    use crate::resolve::resolve_imports;
+    use std::collections::HashMap;
+
+    fn graph(files: &[(&str, &str)]) -> ImportGraph {
+        let files = files
+            .iter()
+            .map(|(path, source)| (path.to_string(), source.to_string()))
+            .collect::<HashMap<String, String>>();
+        resolve_imports("schema/main.teo", &FSUtil::in_memory(files))
+    }
+
+    #[test]
+    fn include_merges_and_import_namespaces() {
+        let graph = graph(&[
+            (
+                "schema/main.teo",
+                "include \"./shared.teo\"\n\
+                 import { User } from \"./user.teo\"\n\
+                 import \"./post\"\n\
+                 model Main { id: Int }",
+            ),
+            (
+                "schema/shared.teo",
+                "include \"./base.teo\"\nenum Role { ADMIN }",
+            ),
+            ("schema/base.teo", "model Base { id: Int }"),
+            (
+                "schema/user.teo",
+                "model User { id: Int }\nmodel Hidden { id: Int }",
+            ),
+            ("schema/post.teo", "model Post { id: Int }"),
+        ]);
+        let scope = graph.scope("schema/main.teo");
+        let names: Vec<&str> = scope.names().collect();
+        assert_eq!(names, vec!["Base", "Main", "Role", "User", "post.Post"]);
+        assert_eq!(scope.get("Role").unwrap().path, "schema/shared.teo");
+        assert_eq!(scope.get("post.Post").unwrap().symbol.name, "Post");
+        assert!(!scope.contains("Hidden"));
+        assert!(!scope.contains("Post"));
+    }
+
+    #[test]
+    fn an_import_sees_what_the_imported_file_includes() {
+        let graph = graph(&[
+            ("schema/main.teo", "import { Role } from \"./user.teo\""),
+            (
+                "schema/user.teo",
+                "include \"./role.teo\"\nmodel User { id: Int }",
+            ),
+            ("schema/role.teo", "enum Role { ADMIN }"),
+        ]);
+        let scope = graph.scope("schema/main.teo");
+        assert_eq!(scope.names().collect::<Vec<&str>>(), vec!["Role"]);
+        assert_eq!(scope.get("Role").unwrap().path, "schema/role.teo");
+        assert!(graph.scope("schema/user.teo").contains("Role"));
+        assert!(!graph.scope("schema/role.teo").contains("User"));
+    }
+
+    #[test]
+    fn own_declarations_win_over_included_ones() {
+        let graph = graph(&[
+            (
+                "schema/main.teo",
+                "include \"./other.teo\"\nmodel User { id: Int }",
+            ),
+            ("schema/other.teo", "enum User { ADMIN }"),
+        ]);
+        let scope = graph.scope("schema/main.teo");
+        assert_eq!(scope.get("User").unwrap().symbol.kind, SymbolKind::Model);
+    }
+
+    #[test]
+    fn resolves_names_from_the_innermost_namespace() {
+        let graph = graph(&[
+            (
+                "schema/main.teo",
+                "import \"./user\"\n\
+                 model Post { id: Int }\n\
+                 namespace blog { model Post { id: Int } namespace drafts { } }\n\
+                 model Tail { id: Int }",
+            ),
+            ("schema/user.teo", "model User { id: Int }"),
+        ]);
+        let scope = graph.scope("schema/main.teo");
+        let schema = &graph.files["schema/main.teo"].schema;
+        let Declaration::Namespace(blog) = &schema.declarations[2] else {
+            panic!("expected a namespace");
+        };
+        let Declaration::Namespace(drafts) = &blog.declarations[1] else {
+            panic!("expected a namespace");
+        };
+        let mut namespaces = Namespaces::new();
+        namespaces.enter(blog);
+        namespaces.enter(drafts);
+        assert_eq!(namespaces.prefix(), "blog::drafts::");
+        let name = |segments: &[&str]| {
+            scope
+                .resolve(segments, &namespaces, |_| true)
+                .map(|(name, _)| name)
+        };
+        assert_eq!(name(&["Post"]), Some("blog::Post"));
+        assert_eq!(name(&["user", "User"]), Some("user.User"));
+        assert_eq!(name(&["Draft"]), None);
+        let model = scope.resolve(&["Post"], &namespaces, |entry| entry.symbol.name == "Post");
+        assert_eq!(model.map(|(name, _)| name), Some("Post"));
+        namespaces.leave(&blog.declarations[1]);
+        assert_eq!(namespaces.prefix(), "blog::drafts::");
+        namespaces.leave(&schema.declarations[3]);
+        assert_eq!(namespaces.prefix(), "");
+    }
+}
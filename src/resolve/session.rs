@@ -0,0 +1,210 @@
+use super::imports::resolve_imports_with_cache;
+use super::ImportGraph;
+use crate::fsutil::{FSUtil, FsError};
+use crate::parser::{parse_schema, ParseResult};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// # Parse session
+///
+/// The import graph of an entry file, kept across edits for tools like
+/// language servers that need it after every change.
+///
+/// The graph is built the first time it is asked for and again after a
+/// file was reported as changed. Rebuilding reads only the changed files,
+/// and parses them again only if their content is different, reusing the
+/// parsed form of every other file.
+pub struct ParseSession {
+    fs: FSUtil,
+    entry: String,
+    cache: FileCache,
+    /// The graph as of the last build, None if it has to be rebuilt.
+    graph: Option<ImportGraph>,
+}
+
+impl ParseSession {
+    /// Create a session for the graph of `entry_path`, read through `fs`.
+    /// Nothing is read until the graph is asked for.
+    pub fn new(entry_path: &str, fs: FSUtil) -> Self {
+        Self {
+            fs,
+            entry: entry_path.to_owned(),
+            cache: FileCache::default(),
+            graph: None,
+        }
+    }
+
+    /// Returns the import graph, rebuilding it if a file changed since it
+    /// was last built.
+    pub fn graph(&mut self) -> &ImportGraph {
+        if self.graph.is_none() {
+            let graph = resolve_imports_with_cache(&self.entry, &self.fs, &mut self.cache);
+            self.cache.retain(|path| graph.files.contains_key(path));
+            self.graph = Some(graph);
+        }
+        self.graph.as_ref().unwrap()
+    }
+
+    /// Report that the content of the file at `path` changed, or may have,
+    /// so that it is read again when the graph is next asked for.
+    pub fn file_changed(&mut self, path: &str) {
+        self.cache.changed.insert(self.fs.path_key(path));
+        self.graph = None;
+    }
+}
+
+/// The parsed files of a [`ParseSession`] by path, with the hash of the
+/// content each was parsed from. The graph shares them, so a file that
+/// didn't change isn't copied.
+#[derive(Default)]
+pub(super) struct FileCache {
+    files: HashMap<String, (u64, Arc<ParseResult>)>,
+    /// The paths whose content has to be read again.
+    changed: HashSet<String>,
+    /// The number of files parsed so far.
+    parses: usize,
+}
+
+impl FileCache {
+    /// Returns the parsed file at `path`, parsing it only if it isn't
+    /// cached or its content changed. An error if the file cannot be read.
+    pub(super) fn parse(&mut self, fs: &FSUtil, path: &str) -> Result<Arc<ParseResult>, FsError> {
+        if !self.changed.contains(path) {
+            if let Some((_, result)) = self.files.get(path) {
+                return Ok(result.clone());
+            }
+        }
+        self.changed.remove(path);
+        let source = match fs.try_read_file(path) {
+            Ok(source) => source,
+            Err(error) => {
+                self.files.remove(path);
+                return Err(error);
+            }
+        };
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        let hash = hasher.finish();
+        match self.files.get(path) {
+            Some((cached, result)) if *cached == hash => Ok(result.clone()),
+            _ => {
+                let result = Arc::new(parse_schema(&source, path));
+                self.parses += 1;
+                self.files.insert(path.to_owned(), (hash, result.clone()));
+                Ok(result)
+            }
+        }
+    }
+
+    /// Forget the files whose paths don't satisfy `keep`.
+    fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.files.retain(|path, _| keep(path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Declaration;
+    use std::sync::Mutex;
+
+    /// An in-memory file system recording the paths it reads.
+    fn counting(reads: Arc<Mutex<Vec<String>>>) -> FSUtil {
+        let files = FSUtil::in_memory(HashMap::from([
+            (
+                "schema/main.teo".to_owned(),
+                "include \"./user.teo\"\ninclude \"./role.teo\"".to_owned(),
+            ),
+            (
+                "schema/user.teo".to_owned(),
+                "model User { id: Int }".to_owned(),
+            ),
+            (
+                "schema/role.teo".to_owned(),
+                "enum Role { ADMIN }".to_owned(),
+            ),
+            (
+                "schema/post.teo".to_owned(),
+                "model Post { id: Int }".to_owned(),
+            ),
+        ]));
+        let inner = files.clone();
+        files.with_read_file(move |path| {
+            reads.lock().unwrap().push(path.to_owned());
+            inner.read_file(path)
+        })
+    }
+
+    fn paths(session: &mut ParseSession) -> Vec<String> {
+        session.graph().files.keys().cloned().collect()
+    }
+
+    #[test]
+    fn reparses_only_the_edited_file() {
+        let reads = Arc::new(Mutex::new(vec![]));
+        let fs = counting(reads.clone());
+        let mut session = ParseSession::new("schema/main.teo", fs.clone());
+        assert_eq!(
+            paths(&mut session),
+            vec!["schema/main.teo", "schema/role.teo", "schema/user.teo"]
+        );
+        assert_eq!(reads.lock().unwrap().len(), 3);
+
+        reads.lock().unwrap().clear();
+        fs.write_file("schema/user.teo", "model User { id: Int, name: String }")
+            .unwrap();
+        session.file_changed("schema/user.teo");
+        let user = &session.graph().files["schema/user.teo"].schema;
+        let Some(Declaration::Model(model)) = user.declarations.first() else {
+            panic!("{:?}", user);
+        };
+        let fields: Vec<&str> = model.fields.iter().map(|f| f.name.name.as_str()).collect();
+        assert_eq!(fields, vec!["id", "name"]);
+        assert_eq!(*reads.lock().unwrap(), vec!["schema/user.teo"]);
+        assert_eq!(session.cache.parses, 4);
+    }
+
+    #[test]
+    fn reuses_a_file_whose_content_did_not_change() {
+        let reads = Arc::new(Mutex::new(vec![]));
+        let mut session = ParseSession::new("schema/main.teo", counting(reads.clone()));
+        session.graph();
+        session.file_changed("schema/role.teo");
+        session.graph();
+        assert_eq!(reads.lock().unwrap().len(), 4);
+        assert_eq!(session.cache.parses, 3);
+    }
+
+    #[test]
+    fn rebuilds_the_graph_lazily() {
+        let reads = Arc::new(Mutex::new(vec![]));
+        let fs = counting(reads.clone());
+        let mut session = ParseSession::new("schema/main.teo", fs.clone());
+        session.graph();
+        fs.write_file("schema/main.teo", "include \"./post.teo\"")
+            .unwrap();
+        session.file_changed("schema/main.teo");
+        session.file_changed("schema/main.teo");
+        assert_eq!(reads.lock().unwrap().len(), 3);
+        assert_eq!(
+            paths(&mut session),
+            vec!["schema/main.teo", "schema/post.teo"]
+        );
+        assert_eq!(reads.lock().unwrap().len(), 5);
+        assert!(!session.cache.files.contains_key("schema/user.teo"));
+    }
+
+    #[test]
+    fn shares_unchanged_files_with_the_graph() {
+        let reads = Arc::new(Mutex::new(vec![]));
+        let mut session = ParseSession::new("schema/main.teo", counting(reads));
+        let role = session.graph().files["schema/role.teo"].clone();
+        session.file_changed("schema/user.teo");
+        assert!(Arc::ptr_eq(
+            &session.graph().files["schema/role.teo"],
+            &role
+        ));
+    }
+}
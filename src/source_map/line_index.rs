@@ -0,0 +1,53 @@
+/// # Line index
+///
+/// The offsets at which the lines of a source start, for turning byte
+/// offsets into line and column numbers in `O(log n)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// The byte offset of the start of each line. The first line starts
+    /// at 0 and every other line right after a `\n`.
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    /// Index the lines of `source`.
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(offset, _)| offset + 1));
+        Self { line_starts }
+    }
+
+    /// Returns the number of lines. A source ending with a line break has
+    /// an empty last line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the 0 based line containing the byte `offset`.
+    pub fn line(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+
+    /// Returns the byte offset at which the 0 based `line` starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_line_of_an_offset() {
+        let index = LineIndex::new("ab\n\ncd\n");
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line(0), 0);
+        assert_eq!(index.line(2), 0);
+        assert_eq!(index.line(3), 1);
+        assert_eq!(index.line(4), 2);
+        assert_eq!(index.line(7), 3);
+        assert_eq!(index.line_start(2), Some(4));
+        assert_eq!(index.line_start(4), None);
+    }
+}
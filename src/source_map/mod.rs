@@ -0,0 +1,6 @@
+pub mod line_index;
+#[allow(clippy::module_inception)]
+pub mod source_map;
+
+pub use line_index::LineIndex;
+pub use source_map::{FileId, SourceLocation, SourceMap};
@@ -0,0 +1,182 @@
+use super::LineIndex;
+
+/// The identifier of a file registered in a [`SourceMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(usize);
+
+/// # Source location
+///
+/// A position in a file, the way it is shown to people.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// The path the file was registered with.
+    pub path: String,
+    /// The 1 based line number.
+    pub line: usize,
+    /// The 1 based column number, counted in characters, with a tab
+    /// advancing to the next multiple of the tab width of the source map.
+    pub column: usize,
+}
+
+/// # Source map
+///
+/// The files loaded while parsing a schema, for turning the byte offsets
+/// of spans, which are relative to their own file, into locations.
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+    /// The number of columns a tab advances to.
+    tab_width: usize,
+}
+
+#[derive(Debug, Clone)]
+struct SourceFile {
+    path: String,
+    source: String,
+    lines: LineIndex,
+}
+
+impl Default for SourceMap {
+    fn default() -> Self {
+        Self {
+            files: vec![],
+            tab_width: Self::DEFAULT_TAB_WIDTH,
+        }
+    }
+}
+
+impl SourceMap {
+    /// The tab width of a source map unless told otherwise, which counts
+    /// a tab as a single column.
+    pub const DEFAULT_TAB_WIDTH: usize = 1;
+
+    /// Create an empty source map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count columns with tabs advancing to the next multiple of
+    /// `tab_width`, to match an editor that shows tabs that wide. A width
+    /// of 0 counts as 1.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    /// Returns the number of columns a tab advances to.
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    /// Register the file at `path` with its `source` and return its id.
+    pub fn add_file(&mut self, path: impl Into<String>, source: impl Into<String>) -> FileId {
+        let source = source.into();
+        self.files.push(SourceFile {
+            path: path.into(),
+            lines: LineIndex::new(&source),
+            source,
+        });
+        FileId(self.files.len() - 1)
+    }
+
+    /// Returns the path of the file `file`.
+    pub fn path(&self, file: FileId) -> Option<&str> {
+        self.files.get(file.0).map(|file| file.path.as_str())
+    }
+
+    /// Returns the source of the file `file`.
+    pub fn source(&self, file: FileId) -> Option<&str> {
+        self.files.get(file.0).map(|file| file.source.as_str())
+    }
+
+    /// Returns the location of the byte `offset` of the file `file`.
+    ///
+    /// The end of the file is a valid offset, so that empty spans at the
+    /// end still have a location. Returns `None` for an unknown file or an
+    /// offset past the end.
+    pub fn location(&self, file: FileId, offset: usize) -> Option<SourceLocation> {
+        let file = self.files.get(file.0)?;
+        if offset > file.source.len() {
+            return None;
+        }
+        let line = file.lines.line(offset);
+        let start = file.lines.line_start(line)?;
+        // Count the bytes that start a character, so `offset` doesn't
+        // have to be on a character boundary.
+        let column = file.source.as_bytes()[start..offset]
+            .iter()
+            .filter(|byte| *byte & 0xC0 != 0x80)
+            .fold(0, |column, byte| {
+                next_column(column, *byte == b'\t', self.tab_width)
+            });
+        Some(SourceLocation {
+            path: file.path.clone(),
+            line: line + 1,
+            column: column + 1,
+        })
+    }
+}
+
+/// Returns the 0 based column after a character at `column`, which is a
+/// tab if `tab` is true, with tabs advancing to the next multiple of
+/// `tab_width`. A width of 0 counts as 1.
+pub(crate) fn next_column(column: usize, tab: bool, tab_width: usize) -> usize {
+    match tab {
+        true => (column / tab_width.max(1) + 1) * tab_width.max(1),
+        false => column + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(path: &str, line: usize, column: usize) -> Option<SourceLocation> {
+        Some(SourceLocation {
+            path: path.to_owned(),
+            line,
+            column,
+        })
+    }
+
+    #[test]
+    fn resolves_offsets_in_each_file() {
+        let mut map = SourceMap::new();
+        let main = map.add_file("main.teo", "model User {\n  id: Int\n}\n");
+        let role = map.add_file("role.teo", "enum Role {\r\n  ÄDMIN\r\n}");
+        assert_eq!(map.path(role), Some("role.teo"));
+        assert_eq!(map.location(main, 0), location("main.teo", 1, 1));
+        assert_eq!(map.location(main, 12), location("main.teo", 1, 13));
+        assert_eq!(map.location(main, 13), location("main.teo", 2, 1));
+        assert_eq!(map.location(role, 12), location("role.teo", 1, 13));
+        assert_eq!(map.location(role, 13), location("role.teo", 2, 1));
+        assert_eq!(map.location(role, 18), location("role.teo", 2, 5));
+    }
+
+    #[test]
+    fn resolves_the_end_of_a_file() {
+        let mut map = SourceMap::new();
+        let main = map.add_file("main.teo", "model User {\n  id: Int\n}\n");
+        let role = map.add_file("role.teo", "enum Role {}");
+        assert_eq!(map.location(main, 25), location("main.teo", 4, 1));
+        assert_eq!(map.location(role, 12), location("role.teo", 1, 13));
+        assert_eq!(map.location(role, 13), None);
+        assert_eq!(map.location(FileId(2), 0), None);
+    }
+
+    #[test]
+    fn counts_tabs_with_the_tab_width() {
+        let source = "model User {\n\tid:\tInt\n\t\t}";
+        let mut map = SourceMap::new();
+        let file = map.add_file("main.teo", source);
+        assert_eq!(map.location(file, 14), location("main.teo", 2, 2));
+        assert_eq!(map.location(file, 18), location("main.teo", 2, 6));
+        assert_eq!(map.location(file, 24), location("main.teo", 3, 3));
+        let mut map = SourceMap::new().with_tab_width(4);
+        let file = map.add_file("main.teo", source);
+        assert_eq!(map.location(file, 14), location("main.teo", 2, 5));
+        assert_eq!(map.location(file, 17), location("main.teo", 2, 8));
+        assert_eq!(map.location(file, 18), location("main.teo", 2, 9));
+        assert_eq!(map.location(file, 24), location("main.teo", 3, 9));
+    }
+}
@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod stats;
+
+pub use stats::{schema_stats, SchemaStats};
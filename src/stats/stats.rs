@@ -0,0 +1,105 @@
+use crate::ast::{EnumDeclaration, EnumMember, FieldDeclaration, ModelDeclaration, SchemaFile};
+use crate::lexer::{tokenize, TokenStream};
+use crate::visit::{walk, Visitor};
+use std::ops::AddAssign;
+
+/// # Schema stats
+///
+/// The size of a schema, for keeping an eye on its growth. Stats of
+/// several files add up with `+=`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchemaStats {
+    /// The number of model declarations.
+    pub models: usize,
+    /// The number of enum declarations.
+    pub enums: usize,
+    /// The number of enum members.
+    pub enum_members: usize,
+    /// The number of fields of models and structs.
+    pub fields: usize,
+    /// The number of tokens of the source, comments left out.
+    pub tokens: usize,
+}
+
+impl AddAssign for SchemaStats {
+    fn add_assign(&mut self, other: Self) {
+        self.models += other.models;
+        self.enums += other.enums;
+        self.enum_members += other.enum_members;
+        self.fields += other.fields;
+        self.tokens += other.tokens;
+    }
+}
+
+impl Visitor for SchemaStats {
+    fn visit_model(&mut self, _model: &ModelDeclaration) {
+        self.models += 1;
+    }
+
+    fn visit_field(&mut self, _field: &FieldDeclaration) {
+        self.fields += 1;
+    }
+
+    fn visit_enum(&mut self, _enum: &EnumDeclaration) {
+        self.enums += 1;
+    }
+
+    fn visit_enum_member(&mut self, _member: &EnumMember) {
+        self.enum_members += 1;
+    }
+}
+
+/// Returns the stats of `schema`, parsed from `source`. The declarations
+/// are counted from the syntax tree and the tokens from the source.
+pub fn schema_stats(source: &str, schema: &SchemaFile) -> SchemaStats {
+    let mut stats = SchemaStats {
+        tokens: TokenStream::new(tokenize(source)).significant().count(),
+        ..SchemaStats::default()
+    };
+    walk(&mut stats, schema);
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    fn stats(source: &str) -> SchemaStats {
+        schema_stats(source, &parse_schema(source, "schema.teo").schema)
+    }
+
+    #[test]
+    fn counts_declarations_and_tokens() {
+        let source = r#"
+        // The users.
+        model User {
+            @id id: Int
+            name: String
+            role: Role
+        }
+        enum Role { ADMIN, USER }
+        struct Point { x: Int }
+        "#;
+        assert_eq!(
+            stats(source),
+            SchemaStats {
+                models: 1,
+                enums: 1,
+                enum_members: 2,
+                fields: 4,
+                tokens: 29,
+            }
+        );
+    }
+
+    #[test]
+    fn adds_up_stats_of_several_files() {
+        let mut total = stats("model User { id: Int }");
+        total += stats("model Post { id: Int }\nenum Role { ADMIN }");
+        assert_eq!(total.models, 2);
+        assert_eq!(total.enums, 1);
+        assert_eq!(total.fields, 2);
+        assert_eq!(total.tokens, 19);
+    }
+}
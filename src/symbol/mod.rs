@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod symbol;
+pub mod table;
+
+pub use symbol::{Symbol, SymbolKind};
+pub use table::SymbolTable;
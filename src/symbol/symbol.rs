@@ -0,0 +1,32 @@
+use std::ops::Range;
+
+/// What a name in a schema file refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Model,
+    Enum,
+    TypeAlias,
+    /// A constant declared by a `let` binding.
+    Constant,
+    Struct,
+    Function,
+    Middleware,
+    Handler,
+    /// A `namespace` block.
+    Namespace,
+    /// A name brought in by an `import { ... } from "path"` statement.
+    Import,
+}
+
+/// # Symbol
+///
+/// A name visible at the top level of a schema file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The name of the symbol.
+    pub name: String,
+    /// What the symbol refers to.
+    pub kind: SymbolKind,
+    /// The byte span of the name where it was declared or imported.
+    pub span: Range<usize>,
+}
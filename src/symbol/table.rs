@@ -0,0 +1,157 @@
+use super::{Symbol, SymbolKind};
+use crate::ast::{Declaration, Identifier, SchemaFile, TypeAliasDeclaration, TypeExpr};
+use std::collections::BTreeMap;
+
+/// # Symbol table
+///
+/// The top level names of a schema file, looked up by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+    symbols: BTreeMap<String, Symbol>,
+    aliases: BTreeMap<String, TypeAliasDeclaration>,
+}
+
+impl SymbolTable {
+    /// Collect the declared and imported names of `schema`. When a name is
+    /// declared more than once, the first declaration wins.
+    ///
+    /// The declarations of a `namespace` block are collected under their
+    /// qualified names, like `blog::Post`, next to the namespace itself.
+    pub fn from_schema(schema: &SchemaFile) -> Self {
+        let mut table = Self::default();
+        table.collect(&schema.declarations, "");
+        table
+    }
+
+    fn collect(&mut self, declarations: &[Declaration], prefix: &str) {
+        for declaration in declarations {
+            match declaration {
+                Declaration::Import(import) if prefix.is_empty() => {
+                    for identifier in &import.identifiers {
+                        self.insert(prefix, identifier, SymbolKind::Import);
+                    }
+                }
+                Declaration::Model(model) => self.insert(prefix, &model.name, SymbolKind::Model),
+                Declaration::Enum(r#enum) => self.insert(prefix, &r#enum.name, SymbolKind::Enum),
+                Declaration::TypeAlias(alias) => {
+                    let name = format!("{}{}", prefix, alias.name.name);
+                    if !self.contains(&name) {
+                        self.aliases.insert(name, alias.clone());
+                    }
+                    self.insert(prefix, &alias.name, SymbolKind::TypeAlias)
+                }
+                Declaration::Let(binding) => {
+                    self.insert(prefix, &binding.name, SymbolKind::Constant)
+                }
+                Declaration::Struct(r#struct) => {
+                    self.insert(prefix, &r#struct.name, SymbolKind::Struct)
+                }
+                Declaration::Function(function) => {
+                    self.insert(prefix, &function.name, SymbolKind::Function)
+                }
+                Declaration::Middleware(middleware) => {
+                    self.insert(prefix, &middleware.name, SymbolKind::Middleware)
+                }
+                Declaration::Handler(handler) => {
+                    self.insert(prefix, &handler.name, SymbolKind::Handler)
+                }
+                Declaration::Namespace(namespace) => {
+                    self.insert(prefix, &namespace.name, SymbolKind::Namespace);
+                    let prefix = format!("{}{}::", prefix, namespace.name.name);
+                    self.collect(&namespace.declarations, &prefix);
+                }
+                Declaration::Import(_)
+                | Declaration::Config(_)
+                | Declaration::ConfigSection(_)
+                | Declaration::Export(_)
+                | Declaration::Unknown(_) => {}
+            }
+        }
+    }
+
+    /// Returns the symbol called `name`, if there is one.
+    pub fn get(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+    }
+
+    /// Returns true if there is a symbol called `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.symbols.contains_key(name)
+    }
+
+    /// Returns the type the alias called `name` stands for, if `name` is a
+    /// type alias.
+    pub fn alias_target(&self, name: &str) -> Option<&TypeExpr> {
+        Some(&self.alias(name)?.target)
+    }
+
+    /// Returns the declaration of the type alias called `name`, if `name`
+    /// is a type alias.
+    pub fn alias(&self, name: &str) -> Option<&TypeAliasDeclaration> {
+        self.aliases.get(name)
+    }
+
+    /// Returns the symbols ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.values()
+    }
+
+    fn insert(&mut self, prefix: &str, identifier: &Identifier, kind: SymbolKind) {
+        let name = format!("{}{}", prefix, identifier.name);
+        self.symbols.entry(name.clone()).or_insert_with(|| Symbol {
+            name,
+            kind,
+            span: identifier.span.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    #[test]
+    fn collects_declared_and_imported_names() {
+        let result = parse_schema(
+            "import { Audit } from \"./audit\"\nmodel User { id: Int }\nenum Role { ADMIN }\nmodel User { }",
+            "schema.teo",
+        );
+        let table = SymbolTable::from_schema(&result.schema);
+        let kinds: Vec<(&str, SymbolKind)> =
+            table.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Audit", SymbolKind::Import),
+                ("Role", SymbolKind::Enum),
+                ("User", SymbolKind::Model),
+            ]
+        );
+        assert_eq!(table.get("User").unwrap().span, 38..42);
+        assert!(!table.contains("Post"));
+    }
+
+    #[test]
+    fn collects_namespaced_names_qualified() {
+        let result = parse_schema(
+            "namespace blog {\n  model Post { id: Int }\n  namespace drafts { type Draft = Post }\n}",
+            "schema.teo",
+        );
+        let table = SymbolTable::from_schema(&result.schema);
+        let kinds: Vec<(&str, SymbolKind)> =
+            table.iter().map(|s| (s.name.as_str(), s.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("blog", SymbolKind::Namespace),
+                ("blog::Post", SymbolKind::Model),
+                ("blog::drafts", SymbolKind::Namespace),
+                ("blog::drafts::Draft", SymbolKind::TypeAlias),
+            ]
+        );
+        assert_eq!(table.get("blog::Post").unwrap().span, 25..29);
+        assert!(table.alias_target("blog::drafts::Draft").is_some());
+        assert!(!table.contains("Post"));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod visitor;
+pub mod visitor_mut;
+
+pub use visitor::{walk, Visitor};
+pub use visitor_mut::{walk_mut, VisitorMut};
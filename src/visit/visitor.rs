@@ -0,0 +1,285 @@
+use crate::ast::{
+    Argument, ConfigDeclaration, ConfigItem, ConfigSection, Declaration, Decorator,
+    EnumDeclaration, EnumMember, ExportStatement, Expr, FieldDeclaration, FunctionDeclaration,
+    HandlerDeclaration, ImportStatement, LetDeclaration, MiddlewareDeclaration, ModelDeclaration,
+    NamespaceDeclaration, SchemaFile, StringPart, StructDeclaration, TypeAliasDeclaration,
+    TypeExpr, UnknownDeclaration,
+};
+
+/// # Visitor
+///
+/// Hooks called by [`walk`] for the nodes of a syntax tree. Every hook does
+/// nothing by default, so a visitor only implements the ones it needs.
+#[allow(unused_variables)]
+pub trait Visitor {
+    fn visit_declaration(&mut self, declaration: &Declaration) {}
+    fn visit_import(&mut self, import: &ImportStatement) {}
+    fn visit_export(&mut self, export: &ExportStatement) {}
+    fn visit_model(&mut self, model: &ModelDeclaration) {}
+    fn visit_field(&mut self, field: &FieldDeclaration) {}
+    fn visit_enum(&mut self, r#enum: &EnumDeclaration) {}
+    fn visit_enum_member(&mut self, member: &EnumMember) {}
+    fn visit_config(&mut self, config: &ConfigDeclaration) {}
+    fn visit_config_section(&mut self, section: &ConfigSection) {}
+    fn visit_config_item(&mut self, item: &ConfigItem) {}
+    fn visit_type_alias(&mut self, alias: &TypeAliasDeclaration) {}
+    fn visit_let(&mut self, binding: &LetDeclaration) {}
+    fn visit_struct(&mut self, r#struct: &StructDeclaration) {}
+    fn visit_function(&mut self, function: &FunctionDeclaration) {}
+    fn visit_middleware(&mut self, middleware: &MiddlewareDeclaration) {}
+    fn visit_handler(&mut self, handler: &HandlerDeclaration) {}
+    fn visit_namespace(&mut self, namespace: &NamespaceDeclaration) {}
+    fn visit_unknown(&mut self, unknown: &UnknownDeclaration) {}
+    fn visit_decorator(&mut self, decorator: &Decorator) {}
+    fn visit_type_expr(&mut self, type_expr: &TypeExpr) {}
+    fn visit_expr(&mut self, expr: &Expr) {}
+}
+
+/// Walk every node of `schema` in source order and call the hook of
+/// `visitor` for each, for a node before its children.
+///
+/// Decorators are visited before the node they decorate, nested type
+/// expressions and expressions are visited too.
+pub fn walk<V: Visitor + ?Sized>(visitor: &mut V, schema: &SchemaFile) {
+    for declaration in &schema.declarations {
+        walk_declaration(visitor, declaration);
+    }
+}
+
+fn walk_declaration<V: Visitor + ?Sized>(visitor: &mut V, declaration: &Declaration) {
+    visitor.visit_declaration(declaration);
+    match declaration {
+        Declaration::Import(import) => visitor.visit_import(import),
+        Declaration::Export(export) => visitor.visit_export(export),
+        Declaration::Model(model) => {
+            walk_decorators(visitor, &model.decorators);
+            visitor.visit_model(model);
+            walk_fields(visitor, &model.fields);
+        }
+        Declaration::Enum(r#enum) => {
+            walk_decorators(visitor, &r#enum.decorators);
+            visitor.visit_enum(r#enum);
+            if let Some(backing) = &r#enum.backing {
+                walk_type_expr(visitor, backing);
+            }
+            for member in &r#enum.members {
+                walk_decorators(visitor, &member.decorators);
+                visitor.visit_enum_member(member);
+            }
+        }
+        Declaration::Config(config) => {
+            visitor.visit_config(config);
+            walk_config_items(visitor, &config.items);
+        }
+        Declaration::ConfigSection(section) => {
+            visitor.visit_config_section(section);
+            walk_config_items(visitor, &section.entries);
+        }
+        Declaration::TypeAlias(alias) => {
+            visitor.visit_type_alias(alias);
+            walk_type_expr(visitor, &alias.target);
+        }
+        Declaration::Let(binding) => {
+            visitor.visit_let(binding);
+            if let Some(type_expr) = &binding.type_expr {
+                walk_type_expr(visitor, type_expr);
+            }
+            walk_expr(visitor, &binding.value);
+        }
+        Declaration::Struct(r#struct) => {
+            visitor.visit_struct(r#struct);
+            walk_fields(visitor, &r#struct.fields);
+        }
+        Declaration::Function(function) => {
+            visitor.visit_function(function);
+            for parameter in &function.parameters {
+                walk_type_expr(visitor, &parameter.type_expr);
+                if let Some(default) = &parameter.default {
+                    walk_expr(visitor, default);
+                }
+            }
+            if let Some(return_type) = &function.return_type {
+                walk_type_expr(visitor, return_type);
+            }
+            if let Some(body) = &function.body {
+                walk_expr(visitor, body);
+            }
+        }
+        Declaration::Middleware(middleware) => {
+            walk_decorators(visitor, &middleware.decorators);
+            visitor.visit_middleware(middleware);
+            if let Some(body) = &middleware.body {
+                walk_expr(visitor, body);
+            }
+        }
+        Declaration::Handler(handler) => {
+            walk_decorators(visitor, &handler.decorators);
+            visitor.visit_handler(handler);
+            if let Some(body) = &handler.body {
+                walk_expr(visitor, body);
+            }
+        }
+        Declaration::Namespace(namespace) => {
+            visitor.visit_namespace(namespace);
+            for declaration in &namespace.declarations {
+                walk_declaration(visitor, declaration);
+            }
+        }
+        Declaration::Unknown(unknown) => visitor.visit_unknown(unknown),
+    }
+}
+
+fn walk_fields<V: Visitor + ?Sized>(visitor: &mut V, fields: &[FieldDeclaration]) {
+    for field in fields {
+        walk_decorators(visitor, &field.decorators);
+        visitor.visit_field(field);
+        walk_type_expr(visitor, &field.type_expr);
+        if let Some(default) = &field.default {
+            walk_expr(visitor, default);
+        }
+    }
+}
+
+fn walk_config_items<V: Visitor + ?Sized>(visitor: &mut V, items: &[ConfigItem]) {
+    for item in items {
+        visitor.visit_config_item(item);
+        walk_expr(visitor, &item.value);
+    }
+}
+
+fn walk_decorators<V: Visitor + ?Sized>(visitor: &mut V, decorators: &[Decorator]) {
+    for decorator in decorators {
+        visitor.visit_decorator(decorator);
+        if let Some(arguments) = &decorator.arguments {
+            walk_arguments(visitor, arguments);
+        }
+    }
+}
+
+fn walk_arguments<V: Visitor + ?Sized>(visitor: &mut V, arguments: &[Argument]) {
+    for argument in arguments {
+        walk_expr(visitor, &argument.value);
+    }
+}
+
+fn walk_type_expr<V: Visitor + ?Sized>(visitor: &mut V, type_expr: &TypeExpr) {
+    visitor.visit_type_expr(type_expr);
+    match type_expr {
+        TypeExpr::Named { arguments, .. } => {
+            for argument in arguments {
+                walk_type_expr(visitor, argument);
+            }
+        }
+        TypeExpr::Optional { inner, .. } | TypeExpr::Array { inner, .. } => {
+            walk_type_expr(visitor, inner)
+        }
+        TypeExpr::Union {
+            members: elements, ..
+        }
+        | TypeExpr::Tuple { elements, .. } => {
+            for element in elements {
+                walk_type_expr(visitor, element);
+            }
+        }
+    }
+}
+
+fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &Expr) {
+    visitor.visit_expr(expr);
+    match expr {
+        Expr::Literal(_) | Expr::Identifier(_) => (),
+        Expr::Array { elements, .. } | Expr::Tuple { elements, .. } => {
+            for element in elements {
+                walk_expr(visitor, element);
+            }
+        }
+        Expr::Dict { entries, .. } => {
+            for entry in entries {
+                walk_expr(visitor, &entry.value);
+            }
+        }
+        Expr::Unary { operand, .. } => walk_expr(visitor, operand),
+        Expr::Binary { lhs, rhs, .. } => {
+            walk_expr(visitor, lhs);
+            walk_expr(visitor, rhs);
+        }
+        Expr::Member { object, .. } | Expr::OptionalMember { object, .. } => {
+            walk_expr(visitor, object)
+        }
+        Expr::Index { object, index, .. } => {
+            walk_expr(visitor, object);
+            walk_expr(visitor, index);
+        }
+        Expr::Call {
+            callee, arguments, ..
+        } => {
+            walk_expr(visitor, callee);
+            walk_arguments(visitor, arguments);
+        }
+        Expr::InterpolatedString { parts, .. } => {
+            for part in parts {
+                if let StringPart::Expr(expr) = part {
+                    walk_expr(visitor, expr);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    #[derive(Default)]
+    struct ModelNames(Vec<String>);
+
+    impl Visitor for ModelNames {
+        fn visit_model(&mut self, model: &ModelDeclaration) {
+            self.0.push(model.name.name.clone());
+        }
+    }
+
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl Visitor for Events {
+        fn visit_model(&mut self, model: &ModelDeclaration) {
+            self.0.push(format!("model {}", model.name.name));
+        }
+
+        fn visit_field(&mut self, field: &FieldDeclaration) {
+            self.0.push(format!("field {}", field.name.name));
+        }
+
+        fn visit_decorator(&mut self, decorator: &Decorator) {
+            self.0.push(format!("@{}", decorator.name()));
+        }
+
+        fn visit_type_expr(&mut self, type_expr: &TypeExpr) {
+            self.0
+                .push(format!("type {}", type_expr.name().unwrap_or_default()));
+        }
+    }
+
+    #[test]
+    fn collects_model_names() {
+        let source = "model User { id: Int }\nenum Role { ADMIN }\nmodel Post { id: Int }";
+        let schema = parse_schema(source, "schema.teo").schema;
+        let mut names = ModelNames::default();
+        walk(&mut names, &schema);
+        assert_eq!(names.0, vec!["User", "Post"]);
+    }
+
+    #[test]
+    fn visits_nodes_before_their_children() {
+        let source = "@map(\"users\") model User { @id id: Int? }";
+        let schema = parse_schema(source, "schema.teo").schema;
+        let mut events = Events::default();
+        walk(&mut events, &schema);
+        assert_eq!(
+            events.0,
+            vec!["@map", "model User", "@id", "field id", "type ", "type Int"]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use crate::ast::{
+    Argument, ConfigDeclaration, ConfigItem, ConfigSection, Declaration, Decorator,
+    EnumDeclaration, EnumMember, ExportStatement, Expr, FieldDeclaration, FunctionDeclaration,
+    HandlerDeclaration, ImportStatement, LetDeclaration, MiddlewareDeclaration, ModelDeclaration,
+    NamespaceDeclaration, SchemaFile, StringPart, StructDeclaration, TypeAliasDeclaration,
+    TypeExpr, UnknownDeclaration,
+};
+
+/// # Mutable visitor
+///
+/// Hooks called by [`walk_mut`] with mutable access to the nodes of a
+/// syntax tree, for transformations. Like the hooks of
+/// [`Visitor`](super::Visitor), every hook does nothing by default.
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    fn visit_declaration(&mut self, declaration: &mut Declaration) {}
+    fn visit_import(&mut self, import: &mut ImportStatement) {}
+    fn visit_export(&mut self, export: &mut ExportStatement) {}
+    fn visit_model(&mut self, model: &mut ModelDeclaration) {}
+    fn visit_field(&mut self, field: &mut FieldDeclaration) {}
+    fn visit_enum(&mut self, r#enum: &mut EnumDeclaration) {}
+    fn visit_enum_member(&mut self, member: &mut EnumMember) {}
+    fn visit_config(&mut self, config: &mut ConfigDeclaration) {}
+    fn visit_config_section(&mut self, section: &mut ConfigSection) {}
+    fn visit_config_item(&mut self, item: &mut ConfigItem) {}
+    fn visit_type_alias(&mut self, alias: &mut TypeAliasDeclaration) {}
+    fn visit_let(&mut self, binding: &mut LetDeclaration) {}
+    fn visit_struct(&mut self, r#struct: &mut StructDeclaration) {}
+    fn visit_function(&mut self, function: &mut FunctionDeclaration) {}
+    fn visit_middleware(&mut self, middleware: &mut MiddlewareDeclaration) {}
+    fn visit_handler(&mut self, handler: &mut HandlerDeclaration) {}
+    fn visit_namespace(&mut self, namespace: &mut NamespaceDeclaration) {}
+    fn visit_unknown(&mut self, unknown: &mut UnknownDeclaration) {}
+    fn visit_decorator(&mut self, decorator: &mut Decorator) {}
+    fn visit_type_expr(&mut self, type_expr: &mut TypeExpr) {}
+    fn visit_expr(&mut self, expr: &mut Expr) {}
+}
+
+/// Walk every node of `schema` like [`walk`](super::walk) and call the
+/// hook of `visitor` for each. The children are walked after the hook of
+/// their parent returns, so the ones it changes or adds are walked too.
+/// The lookups by name of `schema` are indexed again afterwards.
+pub fn walk_mut<V: VisitorMut + ?Sized>(visitor: &mut V, schema: &mut SchemaFile) {
+    schema.clear_index();
+    for declaration in &mut schema.declarations {
+        walk_declaration_mut(visitor, declaration);
+    }
+}
+
+fn walk_declaration_mut<V: VisitorMut + ?Sized>(visitor: &mut V, declaration: &mut Declaration) {
+    visitor.visit_declaration(declaration);
+    match declaration {
+        Declaration::Import(import) => visitor.visit_import(import),
+        Declaration::Export(export) => visitor.visit_export(export),
+        Declaration::Model(model) => {
+            walk_decorators_mut(visitor, &mut model.decorators);
+            visitor.visit_model(model);
+            walk_fields_mut(visitor, &mut model.fields);
+        }
+        Declaration::Enum(r#enum) => {
+            walk_decorators_mut(visitor, &mut r#enum.decorators);
+            visitor.visit_enum(r#enum);
+            if let Some(backing) = &mut r#enum.backing {
+                walk_type_expr_mut(visitor, backing);
+            }
+            for member in &mut r#enum.members {
+                walk_decorators_mut(visitor, &mut member.decorators);
+                visitor.visit_enum_member(member);
+            }
+        }
+        Declaration::Config(config) => {
+            visitor.visit_config(config);
+            walk_config_items_mut(visitor, &mut config.items);
+        }
+        Declaration::ConfigSection(section) => {
+            visitor.visit_config_section(section);
+            walk_config_items_mut(visitor, &mut section.entries);
+        }
+        Declaration::TypeAlias(alias) => {
+            visitor.visit_type_alias(alias);
+            walk_type_expr_mut(visitor, &mut alias.target);
+        }
+        Declaration::Let(binding) => {
+            visitor.visit_let(binding);
+            if let Some(type_expr) = &mut binding.type_expr {
+                walk_type_expr_mut(visitor, type_expr);
+            }
+            walk_expr_mut(visitor, &mut binding.value);
+        }
+        Declaration::Struct(r#struct) => {
+            visitor.visit_struct(r#struct);
+            walk_fields_mut(visitor, &mut r#struct.fields);
+        }
+        Declaration::Function(function) => {
+            visitor.visit_function(function);
+            for parameter in &mut function.parameters {
+                walk_type_expr_mut(visitor, &mut parameter.type_expr);
+                if let Some(default) = &mut parameter.default {
+                    walk_expr_mut(visitor, default);
+                }
+            }
+            if let Some(return_type) = &mut function.return_type {
+                walk_type_expr_mut(visitor, return_type);
+            }
+            if let Some(body) = &mut function.body {
+                walk_expr_mut(visitor, body);
+            }
+        }
+        Declaration::Middleware(middleware) => {
+            walk_decorators_mut(visitor, &mut middleware.decorators);
+            visitor.visit_middleware(middleware);
+            if let Some(body) = &mut middleware.body {
+                walk_expr_mut(visitor, body);
+            }
+        }
+        Declaration::Handler(handler) => {
+            walk_decorators_mut(visitor, &mut handler.decorators);
+            visitor.visit_handler(handler);
+            if let Some(body) = &mut handler.body {
+                walk_expr_mut(visitor, body);
+            }
+        }
+        Declaration::Namespace(namespace) => {
+            visitor.visit_namespace(namespace);
+            for declaration in &mut namespace.declarations {
+                walk_declaration_mut(visitor, declaration);
+            }
+        }
+        Declaration::Unknown(unknown) => visitor.visit_unknown(unknown),
+    }
+}
+
+fn walk_fields_mut<V: VisitorMut + ?Sized>(visitor: &mut V, fields: &mut [FieldDeclaration]) {
+    for field in fields {
+        walk_decorators_mut(visitor, &mut field.decorators);
+        visitor.visit_field(field);
+        walk_type_expr_mut(visitor, &mut field.type_expr);
+        if let Some(default) = &mut field.default {
+            walk_expr_mut(visitor, default);
+        }
+    }
+}
+
+fn walk_config_items_mut<V: VisitorMut + ?Sized>(visitor: &mut V, items: &mut [ConfigItem]) {
+    for item in items {
+        visitor.visit_config_item(item);
+        walk_expr_mut(visitor, &mut item.value);
+    }
+}
+
+fn walk_decorators_mut<V: VisitorMut + ?Sized>(visitor: &mut V, decorators: &mut [Decorator]) {
+    for decorator in decorators {
+        visitor.visit_decorator(decorator);
+        if let Some(arguments) = &mut decorator.arguments {
+            walk_arguments_mut(visitor, arguments);
+        }
+    }
+}
+
+fn walk_arguments_mut<V: VisitorMut + ?Sized>(visitor: &mut V, arguments: &mut [Argument]) {
+    for argument in arguments {
+        walk_expr_mut(visitor, &mut argument.value);
+    }
+}
+
+fn walk_type_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, type_expr: &mut TypeExpr) {
+    visitor.visit_type_expr(type_expr);
+    match type_expr {
+        TypeExpr::Named { arguments, .. } => {
+            for argument in arguments {
+                walk_type_expr_mut(visitor, argument);
+            }
+        }
+        TypeExpr::Optional { inner, .. } | TypeExpr::Array { inner, .. } => {
+            walk_type_expr_mut(visitor, inner)
+        }
+        TypeExpr::Union {
+            members: elements, ..
+        }
+        | TypeExpr::Tuple { elements, .. } => {
+            for element in elements {
+                walk_type_expr_mut(visitor, element);
+            }
+        }
+    }
+}
+
+fn walk_expr_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expr: &mut Expr) {
+    visitor.visit_expr(expr);
+    match expr {
+        Expr::Literal(_) | Expr::Identifier(_) => (),
+        Expr::Array { elements, .. } | Expr::Tuple { elements, .. } => {
+            for element in elements {
+                walk_expr_mut(visitor, element);
+            }
+        }
+        Expr::Dict { entries, .. } => {
+            for entry in entries {
+                walk_expr_mut(visitor, &mut entry.value);
+            }
+        }
+        Expr::Unary { operand, .. } => walk_expr_mut(visitor, operand),
+        Expr::Binary { lhs, rhs, .. } => {
+            walk_expr_mut(visitor, lhs);
+            walk_expr_mut(visitor, rhs);
+        }
+        Expr::Member { object, .. } | Expr::OptionalMember { object, .. } => {
+            walk_expr_mut(visitor, object)
+        }
+        Expr::Index { object, index, .. } => {
+            walk_expr_mut(visitor, object);
+            walk_expr_mut(visitor, index);
+        }
+        Expr::Call {
+            callee, arguments, ..
+        } => {
+            walk_expr_mut(visitor, callee);
+            walk_arguments_mut(visitor, arguments);
+        }
+        Expr::InterpolatedString { parts, .. } => {
+            for part in parts {
+                if let StringPart::Expr(expr) = part {
+                    walk_expr_mut(visitor, expr);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_schema;
+
+    struct Prefix(&'static str);
+
+    impl VisitorMut for Prefix {
+        fn visit_model(&mut self, model: &mut ModelDeclaration) {
+            model.name.name.insert_str(0, self.0);
+        }
+
+        fn visit_type_expr(&mut self, type_expr: &mut TypeExpr) {
+            if let TypeExpr::Named { path, .. } = type_expr {
+                if path[0].name == "Post" {
+                    path[0].name.insert_str(0, self.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transforms_nodes_in_place() {
+        let source = "model User { posts: Post[] }\nmodel Post { id: Int }";
+        let mut schema = parse_schema(source, "schema.teo").schema;
+        walk_mut(&mut Prefix("App"), &mut schema);
+        let models: Vec<&ModelDeclaration> = schema
+            .declarations
+            .iter()
+            .filter_map(|declaration| match declaration {
+                Declaration::Model(model) => Some(model),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(models[0].name.name, "AppUser");
+        assert_eq!(models[1].name.name, "AppPost");
+        assert_eq!(models[0].fields[0].type_expr.to_string(), "AppPost[]");
+    }
+}
@@ -0,0 +1,68 @@
+use teo_language_parser::ast::{Declaration, Expr, TypeExpr};
+use teo_language_parser::parser::parse_schema;
+
+const SCHEMA: &str = r#"
+// Server settings.
+config server {
+    bind: ("0.0.0.0", 5000)
+}
+
+import { Audit } from "./audit"
+
+/// A registered user.
+@map("users")
+model User {
+    @id id: Int
+    email: String
+    name: String?
+    roles: Role[]
+}
+
+enum Role {
+    ADMIN = "admin"
+    USER = "user"
+}
+
+middleware timing
+handler signIn { identity.signIn(credentials) }
+"#;
+
+#[test]
+fn parses_a_multi_declaration_schema() {
+    let result = parse_schema(SCHEMA, "schema.teo");
+    assert_eq!(result.path, "schema.teo");
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    assert_eq!(result.comments.len(), 2);
+
+    let declarations = &result.schema.declarations;
+    assert_eq!(declarations.len(), 6);
+    let Declaration::Config(config) = &declarations[0] else {
+        panic!("expected a config");
+    };
+    assert!(matches!(config.items[0].value, Expr::Tuple { .. }));
+    assert!(matches!(&declarations[1], Declaration::Import(import) if import.source == "./audit"));
+
+    let Declaration::Model(model) = &declarations[2] else {
+        panic!("expected a model");
+    };
+    assert_eq!(model.decorators[0].name(), "map");
+    assert_eq!(&SCHEMA[model.name.span.clone()], "User");
+    let optional: Vec<bool> = model.fields.iter().map(|f| f.is_optional()).collect();
+    assert_eq!(optional, vec![false, false, true, false]);
+    assert!(matches!(model.fields[3].type_expr, TypeExpr::Array { .. }));
+
+    assert!(matches!(&declarations[3], Declaration::Enum(r#enum) if r#enum.members.len() == 2));
+    assert!(matches!(&declarations[4], Declaration::Middleware(_)));
+    assert!(matches!(&declarations[5], Declaration::Handler(h) if h.body.is_some()));
+    for declaration in declarations {
+        assert!(declaration.span().end <= SCHEMA.len());
+    }
+}
+
+#[test]
+fn reports_errors_with_spans_into_the_source() {
+    let source = "model User {\n  id: Int\n  email String\n}\n";
+    let result = parse_schema(source, "broken.teo");
+    assert!(result.has_errors());
+    assert_eq!(&source[result.diagnostics[0].span.clone()], "String");
+}